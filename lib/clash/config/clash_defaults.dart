@@ -15,6 +15,10 @@ class ClashDefaults {
   static const int mixedPort = 8088; // 混合端口（HTTP + SOCKS5）
   static const int httpPort = 8083; // 单独 HTTP 端口（可选）
   static const int socksPort = 8085; // 单独 SOCKS5 端口（可选）
+  static const int downloadNodePort = 8087; // 指定节点下载入站（仅监听本机）
+
+  // 指定节点下载代理组（运行时注入，界面中隐藏）
+  static const String downloadNodeGroupName = 'STELLIBERTY-DOWNLOAD';
 
   // ==================== 超时配置 ====================
   static const int apiReadyMaxRetries = 30; // API 就绪重试次数（总超时 6s）
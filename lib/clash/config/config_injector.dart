@@ -1,5 +1,6 @@
 import 'dart:io';
 import 'dart:async';
+import 'package:stelliberty/clash/config/clash_defaults.dart';
 import 'package:stelliberty/storage/clash_preferences.dart';
 import 'package:stelliberty/clash/services/dns_service.dart';
 import 'package:stelliberty/services/log_print_service.dart';
@@ -53,6 +54,45 @@ class ConfigInjector {
     }
   }
 
+  // 上次注入的指定节点下载端口（重载时由核心自身占用，视为可用）
+  static int? _lastDownloadNodePort;
+
+  // 指定节点下载入站端口搜索范围
+  static const int _downloadNodePortSearchRange = 100;
+
+  // 指定节点下载入站端口：从默认端口起避开 Clash 入站端口并探测本机是否空闲
+  static Future<int?> _resolveDownloadNodePort(List<int?> usedPorts) async {
+    final start = ClashDefaults.downloadNodePort;
+    for (var port = start; port < start + _downloadNodePortSearchRange; port++) {
+      if (usedPorts.contains(port)) {
+        continue;
+      }
+      if (port == _lastDownloadNodePort || await _isPortFree(port)) {
+        _lastDownloadNodePort = port;
+        return port;
+      }
+    }
+    Logger.warning(
+      '未找到可用的指定节点下载端口（$start-${start + _downloadNodePortSearchRange - 1}）',
+    );
+    _lastDownloadNodePort = null;
+    return null;
+  }
+
+  // 探测本机回环端口是否空闲
+  static Future<bool> _isPortFree(int port) async {
+    try {
+      final socket = await ServerSocket.bind(
+        InternetAddress.loopbackIPv4,
+        port,
+      );
+      await socket.close();
+      return true;
+    } catch (_) {
+      return false;
+    }
+  }
+
   // 注入运行时参数，生成 runtime_config.yaml
   static Future<GeneratedRuntimeConfig?> generateRuntimeConfig({
     String? configPath,
@@ -113,6 +153,17 @@ class ConfigInjector {
         }
       }
 
+      int? downloadNodePort;
+      if (prefs.getDownloadNodeEnabled()) {
+        downloadNodePort = await _resolveDownloadNodePort([
+          mixedPort,
+          socksPort,
+          httpPort,
+        ]);
+      } else {
+        _lastDownloadNodePort = null;
+      }
+
       final params = RuntimeConfigParams(
         mixedPort: mixedPort,
         socksPort: socksPort ?? 0,
@@ -146,6 +197,8 @@ class ConfigInjector {
         lanAllowedIps: lanAllowedIps,
         lanDisallowedIps: lanDisallowedIps,
         skipAuthPrefixes: skipAuthPrefixes,
+        // 仅当存在指定节点模式的订阅时注入下载入站
+        downloadNodePort: downloadNodePort,
       );
 
       final requestId =
@@ -163,7 +216,9 @@ class ConfigInjector {
         final request = GenerateRuntimeConfigRequest(
           requestId: requestId,
           baseConfigContent: content,
+          aggregation: null,
           overrides: overrides,
+          jsLimits: null,
           runtimeParams: params,
         );
 
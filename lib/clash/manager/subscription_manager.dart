@@ -4,6 +4,7 @@ import 'package:stelliberty/clash/services/subscription_service.dart';
 import 'package:stelliberty/clash/services/override_service.dart';
 import 'package:stelliberty/clash/config/clash_defaults.dart';
 import 'package:stelliberty/services/log_print_service.dart';
+import 'package:stelliberty/src/bindings/signals/signals.dart';
 
 // 订阅管理器
 // 负责订阅相关的业务逻辑
@@ -106,10 +107,7 @@ class SubscriptionManager {
   // 根据 Clash 运行状态自动选择代理模式
   Future<Subscription> downloadSubscription(Subscription subscription) async {
     final isClashRunning = _isCoreRunning();
-
-    final effectiveProxyMode = isClashRunning
-        ? subscription.proxyMode
-        : SubscriptionProxyMode.direct;
+    final effectiveProxyMode = _effectiveProxyMode(subscription);
 
     if (!isClashRunning &&
         subscription.proxyMode != SubscriptionProxyMode.direct) {
@@ -126,4 +124,28 @@ class SubscriptionManager {
       mixedPort,
     );
   }
+
+  // 同步自动更新计划到 Rust 调度器
+  void scheduleAutoUpdates(List<Subscription> subscriptions) {
+    _service.scheduleAutoUpdates(
+      subscriptions,
+      _effectiveProxyMode,
+      _getMixedPort(),
+    );
+  }
+
+  // 应用 Rust 调度器自动更新的结果
+  Future<Subscription> applyScheduledUpdate(
+    Subscription subscription,
+    SubscriptionUpdated update,
+  ) async {
+    return await _service.applyScheduledUpdate(subscription, update);
+  }
+
+  // Clash 未运行时只能直连下载
+  SubscriptionProxyMode _effectiveProxyMode(Subscription subscription) {
+    return _isCoreRunning()
+        ? subscription.proxyMode
+        : SubscriptionProxyMode.direct;
+  }
 }
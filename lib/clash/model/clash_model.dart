@@ -1,3 +1,5 @@
+import 'package:stelliberty/clash/config/clash_defaults.dart';
+
 // 代理组信息
 class ProxyGroup {
   final String name;
@@ -21,7 +23,10 @@ class ProxyGroup {
       name: name,
       type: json['type'] ?? '',
       now: json['now'],
-      all: List<String>.from(json['all'] ?? []),
+      // 运行时注入的下载代理组不作为可选项展示
+      all: List<String>.from(
+        json['all'] ?? [],
+      ).where((name) => name != ClashDefaults.downloadNodeGroupName).toList(),
       isHidden: json['hidden'] ?? false,
       icon: json['icon'],
     );
@@ -9,7 +9,10 @@ enum SubscriptionProxyMode {
   system('system'),
 
   // 使用 Clash 核心代理（自举）
-  core('core');
+  core('core'),
+
+  // 经 Clash 核心的指定节点（隐藏下载代理组）
+  coreNode('coreNode');
 
   const SubscriptionProxyMode(this.value);
 
@@ -32,7 +35,10 @@ enum AutoUpdateMode {
   onStartup('onStartup'),
 
   // 间隔更新（按分钟）
-  interval('interval');
+  interval('interval'),
+
+  // 按订阅提供方间隔更新（profile-update-interval 响应头）
+  provider('provider');
 
   const AutoUpdateMode(this.value);
 
@@ -194,6 +200,7 @@ class Subscription {
   final List<String> failedOverrideIds; // 失败的覆写 ID 列表(启动失败时记录)
   final String userAgent; // User-Agent（仅远程订阅有效，默认为 clash.meta）
   final String ageSecretKey; // age 加密订阅解密私钥
+  final String downloadNodeName; // 指定节点模式下载使用的核心节点名称
   final bool hasConfigLoadFailed; // 配置加载失败标记（用于 UI 显示警告）
   final bool autoTestAllDelaysEnabled; // 是否启用自动测试全部延迟
   final int autoTestAllDelaysIntervalMinutes; // 自动测试全部延迟间隔（分钟）
@@ -219,6 +226,7 @@ class Subscription {
     this.failedOverrideIds = const [],
     this.userAgent = ClashDefaults.defaultUserAgent,
     this.ageSecretKey = '',
+    this.downloadNodeName = '',
     this.hasConfigLoadFailed = false,
     this.autoTestAllDelaysEnabled = false,
     this.autoTestAllDelaysIntervalMinutes = 10,
@@ -285,6 +293,7 @@ class Subscription {
     List<String>? failedOverrideIds,
     String? userAgent,
     String? ageSecretKey,
+    String? downloadNodeName,
     bool? hasConfigLoadFailed,
     bool? autoTestAllDelaysEnabled,
     int? autoTestAllDelaysIntervalMinutes,
@@ -312,6 +321,7 @@ class Subscription {
       failedOverrideIds: failedOverrideIds ?? this.failedOverrideIds,
       userAgent: userAgent ?? this.userAgent,
       ageSecretKey: ageSecretKey ?? this.ageSecretKey,
+      downloadNodeName: downloadNodeName ?? this.downloadNodeName,
       hasConfigLoadFailed: hasConfigLoadFailed ?? this.hasConfigLoadFailed,
       autoTestAllDelaysEnabled:
           autoTestAllDelaysEnabled ?? this.autoTestAllDelaysEnabled,
@@ -343,6 +353,7 @@ class Subscription {
     'failedOverrideIds': failedOverrideIds,
     'userAgent': userAgent,
     'ageSecretKey': ageSecretKey,
+    'downloadNodeName': downloadNodeName,
     'hasConfigLoadFailed': hasConfigLoadFailed,
     'autoTestAllDelaysEnabled': autoTestAllDelaysEnabled,
     'autoTestAllDelaysIntervalMinutes': autoTestAllDelaysIntervalMinutes,
@@ -385,6 +396,7 @@ class Subscription {
           : const [],
       userAgent: json['userAgent'] as String? ?? ClashDefaults.defaultUserAgent,
       ageSecretKey: json['ageSecretKey'] as String? ?? '',
+      downloadNodeName: json['downloadNodeName'] as String? ?? '',
       hasConfigLoadFailed: json['hasConfigLoadFailed'] as bool? ?? false,
       autoTestAllDelaysEnabled:
           json['autoTestAllDelaysEnabled'] as bool? ?? false,
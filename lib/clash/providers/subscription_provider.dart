@@ -30,8 +30,8 @@ class SubscriptionProvider extends ChangeNotifier {
   ClashProvider? _clashProvider;
   bool _wasCoreRunning = false;
 
-  // 自动更新定时器
-  Timer? _autoUpdateTimer;
+  // Rust 调度器自动更新结果监听
+  StreamSubscription? _scheduledUpdateListener;
 
   // 自动测试所有延迟定时器
   Timer? _autoDelayTestTimer;
@@ -39,9 +39,6 @@ class SubscriptionProvider extends ChangeNotifier {
   // 启动时更新是否已完成
   bool _isStartupUpdateDone = false;
 
-  // 自动更新并发保护标记
-  bool _isAutoUpdateInProgress = false;
-
   // 订阅列表
   List<Subscription> _subscriptions = [];
   List<Subscription> get subscriptions => List.unmodifiable(_subscriptions);
@@ -294,8 +291,9 @@ class SubscriptionProvider extends ChangeNotifier {
 
       Logger.info('订阅 Provider 初始化成功，共 ${_subscriptions.length} 个订阅');
 
-      // 启动动态自动更新定时器
-      _restartAutoUpdateTimer();
+      // 自动更新由 Rust 调度器执行，此处同步计划并监听结果
+      _listenScheduledUpdates();
+      _syncAutoUpdateSchedule();
       await _restartAutoDelayTestTimer();
 
       _updateState(SubscriptionState.idle());
@@ -324,6 +322,7 @@ class SubscriptionProvider extends ChangeNotifier {
     SubscriptionProxyMode proxyMode = SubscriptionProxyMode.direct,
     String? userAgent,
     String ageSecretKey = '',
+    String downloadNodeName = '',
     bool autoTestAllDelaysEnabled = false,
     int autoTestAllDelaysIntervalMinutes = 10,
     List<String> disabledBuiltinChainProxyNames = const [],
@@ -354,6 +353,7 @@ class SubscriptionProvider extends ChangeNotifier {
         proxyMode: proxyMode,
         userAgent: effectiveUserAgent,
         ageSecretKey: ageSecretKey,
+        downloadNodeName: downloadNodeName,
         autoTestAllDelaysEnabled: autoTestAllDelaysEnabled,
         autoTestAllDelaysIntervalMinutes: autoTestAllDelaysIntervalMinutes,
         disabledBuiltinChainProxyNames: disabledBuiltinChainProxyNames,
@@ -396,7 +396,7 @@ class SubscriptionProvider extends ChangeNotifier {
       notifyListeners();
 
       // 重新计算定时器间隔（新订阅可能启用了自动更新）
-      _restartAutoUpdateTimer();
+      _syncAutoUpdateSchedule();
       if (_currentSubscriptionId == subscription.id) {
         await _restartAutoDelayTestTimer();
       }
@@ -500,6 +500,9 @@ class SubscriptionProvider extends ChangeNotifier {
     }
     _wasCoreRunning = isCoreRunning;
 
+    // 核心运行状态决定自动更新可用的代理模式
+    _syncAutoUpdateSchedule();
+
     if (isCoreRunning) {
       unawaited(handleCoreRunningRestoredForAutoDelayTest());
       return;
@@ -648,42 +651,11 @@ class SubscriptionProvider extends ChangeNotifier {
       }
       Logger.info('更新订阅成功：${subscription.name}');
 
-      // 注意：不在这里重启定时器，避免批量更新时频繁重启
-      // 定时器会在 autoUpdateSubscriptions() 完成后统一重启
-
       return true;
     } catch (e) {
       final rawError = e.toString();
       Logger.error('更新订阅失败：${subscription.name} - $rawError');
-
-      // 分析错误类型并保存
-      final errorType = _classifyError(rawError);
-      Logger.info('错误类型：$errorType');
-
-      // 判断是否为永久性错误（需要禁用自动更新）
-      final isPermanentError =
-          errorType == SubscriptionErrorState.notFound ||
-          errorType == SubscriptionErrorState.forbidden ||
-          errorType == SubscriptionErrorState.formatError;
-
-      if (isPermanentError) {
-        // 永久性错误：禁用自动更新（需要用户手动修复）
-        Logger.warning('检测到永久性错误，已禁用自动更新：${errorType.name}');
-        _subscriptions[index] = subscription.copyWith(
-          isUpdating: false,
-          lastError: errorType.name,
-          autoUpdateMode: AutoUpdateMode.disabled,
-        );
-      } else {
-        // 临时性错误：更新时间戳，按正常间隔重试
-        Logger.info('临时性错误，将按正常间隔重试：${errorType.name}');
-        _subscriptions[index] = subscription.copyWith(
-          isUpdating: false,
-          lastError: errorType.name,
-          lastUpdatedAt: DateTime.now(),
-        );
-      }
-      await _manager.saveSubscriptionList(_subscriptions);
+      await _recordUpdateFailure(index, subscription, rawError);
 
       if (didCancelCurrentDelayTests) {
         await _restartAutoDelayTestTimer();
@@ -806,135 +778,164 @@ class SubscriptionProvider extends ChangeNotifier {
     return errors;
   }
 
-  // 检查是否有启用间隔更新的订阅
-  bool _hasIntervalUpdateSubscriptions() {
-    return _subscriptions.any(
-      (s) => s.autoUpdateMode == AutoUpdateMode.interval && !s.isLocalFile,
-    );
-  }
-
-  // 启动/重启自动更新定时器（固定 1 分钟检查间隔）
-  void _restartAutoUpdateTimer() {
-    // 取消现有定时器
-    _autoUpdateTimer?.cancel();
-    _autoUpdateTimer = null;
-
-    // 如果没有需要自动更新的订阅，停止定时器
-    if (!_hasIntervalUpdateSubscriptions()) {
-      Logger.info('无启用间隔更新的订阅，定时器已停止');
-      return;
+  // 记录更新失败：永久性错误禁用自动更新，临时性错误交由调度器重试
+  Future<void> _recordUpdateFailure(
+    int index,
+    Subscription subscription,
+    String rawError,
+  ) async {
+    // 分析错误类型并保存
+    final errorType = _classifyError(rawError);
+    Logger.info('错误类型：$errorType');
+
+    // 判断是否为永久性错误（需要禁用自动更新）
+    final isPermanentError =
+        errorType == SubscriptionErrorState.notFound ||
+        errorType == SubscriptionErrorState.forbidden ||
+        errorType == SubscriptionErrorState.formatError;
+
+    if (isPermanentError) {
+      // 永久性错误：禁用自动更新（需要用户手动修复）
+      Logger.warning('检测到永久性错误，已禁用自动更新：${errorType.name}');
+      _subscriptions[index] = subscription.copyWith(
+        isUpdating: false,
+        lastError: errorType.name,
+        autoUpdateMode: AutoUpdateMode.disabled,
+      );
+    } else {
+      // 临时性错误：更新时间戳，按正常间隔重试
+      Logger.info('临时性错误，将按正常间隔重试：${errorType.name}');
+      _subscriptions[index] = subscription.copyWith(
+        isUpdating: false,
+        lastError: errorType.name,
+        lastUpdatedAt: DateTime.now(),
+      );
     }
+    await _manager.saveSubscriptionList(_subscriptions);
 
-    // 固定每分钟检查一次（简单高效）
-    _autoUpdateTimer = Timer.periodic(const Duration(minutes: 1), (_) {
-      _checkAndAutoUpdate();
-    });
-
-    // 2 秒后立即检查一次，避免错过已过期的订阅
-    Future.delayed(const Duration(seconds: 2), () {
-      if (_autoUpdateTimer != null) {
-        _checkAndAutoUpdate();
-      }
-    });
+    if (isPermanentError &&
+        subscription.autoUpdateMode != AutoUpdateMode.disabled) {
+      _syncAutoUpdateSchedule();
+    }
+  }
 
-    Logger.info('自动更新定时器已启动（固定检查间隔：1 分钟）');
+  // 同步自动更新计划到 Rust 调度器（按订阅设置的间隔定时下载）
+  void _syncAutoUpdateSchedule() {
+    _manager.scheduleAutoUpdates(_subscriptions);
+    unawaited(_syncDownloadNodeListener());
   }
 
-  // 检查并执行自动更新
-  void _checkAndAutoUpdate() async {
-    // 防止并发执行
-    if (_isAutoUpdateInProgress) {
-      Logger.debug('自动更新正在处理中，跳过本次检查');
+  // 同步指定节点下载入站：仅当有远程订阅使用指定节点模式时注入
+  Future<void> _syncDownloadNodeListener() async {
+    final isRequired = _subscriptions.any(
+      (s) => s.proxyMode == SubscriptionProxyMode.coreNode && !s.isLocalFile,
+    );
+    final prefs = ClashPreferences.instance;
+    if (prefs.getDownloadNodeEnabled() == isRequired) {
       return;
     }
+    await prefs.setDownloadNodeEnabled(isRequired);
+    Logger.info('指定节点下载入站已${isRequired ? '启用' : '停用'}');
 
-    // 防止重复执行
-    if (_state.operationState.isAutoUpdating) {
-      Logger.debug('自动更新正在执行中，跳过本次检查');
+    final clashProvider = _clashProvider;
+    if (clashProvider == null || !clashProvider.isCoreRunning) {
       return;
     }
-
-    _isAutoUpdateInProgress = true;
+    clashProvider.pauseConfigWatcher();
     try {
-      // 过滤出需要更新的订阅
-      final needUpdateSubscriptions = _subscriptions
-          .where((s) => s.shouldUpdate)
-          .toList();
-
-      if (needUpdateSubscriptions.isEmpty) {
-        Logger.debug('定时检查：没有订阅需要更新');
-        return;
-      }
-
-      Logger.info('定时检查：发现 ${needUpdateSubscriptions.length} 个订阅需要更新');
-
-      _updateState(
-        _state.copyWith(
-          operationState: SubscriptionOperationState.autoUpdating,
-        ),
-      );
-      try {
-        await autoUpdateSubscriptions();
-      } catch (e) {
-        Logger.error('自动更新订阅失败：$e');
-        _updateState(
-          _state.copyWith(
-            errorState: SubscriptionErrorState.unknown,
-            errorMessage: '自动更新失败: $e',
-          ),
-        );
-      } finally {
-        _updateState(SubscriptionState.idle());
-      }
+      await _reloadCurrentSubscriptionConfig(reason: '指定节点下载入站变更');
+    } catch (e) {
+      Logger.error('应用指定节点下载入站失败：$e');
     } finally {
-      _isAutoUpdateInProgress = false;
+      await clashProvider.resumeConfigWatcher();
     }
   }
 
-  // 自动更新需要更新的订阅
-  // 使用并发更新以提高效率
-  Future<void> autoUpdateSubscriptions() async {
-    Logger.info('开始自动更新订阅...');
+  // 监听 Rust 调度器的自动更新结果
+  void _listenScheduledUpdates() {
+    _scheduledUpdateListener?.cancel();
+    _scheduledUpdateListener = SubscriptionUpdated.rustSignalStream.listen(
+      (signal) => unawaited(_handleScheduledUpdate(signal.message)),
+    );
+  }
 
-    // 过滤出需要更新的订阅
-    final needUpdateSubscriptions = _subscriptions
-        .where((s) => s.shouldUpdate)
-        .toList();
+  // 应用自动更新结果，当前订阅更新后重新加载配置
+  Future<void> _handleScheduledUpdate(SubscriptionUpdated update) async {
+    int indexOf() =>
+        _subscriptions.indexWhere((s) => s.id == update.subscriptionId);
 
-    if (needUpdateSubscriptions.isEmpty) {
-      Logger.info('没有需要更新的订阅');
+    final index = indexOf();
+    if (index == -1) {
+      Logger.debug('自动更新的订阅已不存在，忽略结果：${update.subscriptionId}');
       return;
     }
+    final subscription = _subscriptions[index];
 
-    final includesCurrentSubscription = needUpdateSubscriptions.any(
-      (subscription) => subscription.id == _currentSubscriptionId,
-    );
-    if (includesCurrentSubscription) {
-      await _cancelDelayTestsForCurrentSubscriptionUpdate();
+    // 下载失败后回退到缓存内容：已保存的配置不变，保留上次成功时间并记录错误
+    if (update.isSuccessful && update.isStale) {
+      Logger.warning(
+        '自动更新订阅失败，使用缓存内容：${subscription.name} - ${update.errorMessage}',
+      );
+      _subscriptions[index] = subscription.copyWith(
+        lastError: _classifyError(update.errorMessage ?? '').name,
+      );
+      await _manager.saveSubscriptionList(_subscriptions);
+      notifyListeners();
+      return;
     }
 
-    Logger.info('发现 ${needUpdateSubscriptions.length} 个订阅需要更新');
-
-    // 使用并发更新，限制并发数
-    const concurrency = ClashDefaults.subscriptionUpdateConcurrency;
-
-    for (int i = 0; i < needUpdateSubscriptions.length; i += concurrency) {
-      final batch = needUpdateSubscriptions.skip(i).take(concurrency).toList();
-
-      // 并发更新一批订阅
-      await Future.wait(
-        batch.map(
-          (subscription) =>
-              updateSubscription(subscription.id, cancelDelayTests: false),
-        ),
+    // 内容未变化时仅记录更新时间，避免重新加载配置
+    if (update.isSuccessful && update.isNotModified) {
+      _subscriptions[index] = subscription.copyWith(
+        lastUpdatedAt: DateTime.now(),
+        lastError: null,
       );
+      await _manager.saveSubscriptionList(_subscriptions);
+      notifyListeners();
+      return;
     }
 
-    Logger.info('自动更新订阅完成');
+    try {
+      final updatedSubscription = await _manager.applyScheduledUpdate(
+        subscription,
+        update,
+      );
+      // 解析期间列表可能已变化，重新定位
+      final currentIndex = indexOf();
+      if (currentIndex == -1) return;
 
-    // 批量更新完成后重新计算定时器（避免单个更新时频繁重启）
-    _restartAutoUpdateTimer();
-    await _restartAutoDelayTestTimer();
+      _subscriptions[currentIndex] = updatedSubscription.copyWith(
+        lastError: null,
+        hasConfigLoadFailed: false,
+      );
+      await _manager.saveSubscriptionList(_subscriptions);
+      Logger.info('自动更新订阅成功：${subscription.name}');
+
+      if (_isCurrentSubscription(subscription.id)) {
+        Logger.info('当前订阅已自动更新，开始重新加载配置...');
+        await _cancelDelayTestsForCurrentSubscriptionUpdate();
+        _clashProvider?.pauseConfigWatcher();
+        try {
+          await _reloadCurrentSubscriptionConfig(reason: '订阅自动更新');
+          await _restartAutoDelayTestTimer();
+        } finally {
+          await _clashProvider?.resumeConfigWatcher();
+        }
+      }
+    } catch (e) {
+      final rawError = e.toString();
+      Logger.error('自动更新订阅失败：${subscription.name} - $rawError');
+      final currentIndex = indexOf();
+      if (currentIndex != -1) {
+        await _recordUpdateFailure(
+          currentIndex,
+          _subscriptions[currentIndex],
+          rawError,
+        );
+      }
+    } finally {
+      notifyListeners();
+    }
   }
 
   // 执行启动时更新（确保只执行一次）
@@ -1080,7 +1081,7 @@ class SubscriptionProvider extends ChangeNotifier {
       notifyListeners();
 
       // 本地订阅不支持自动更新，但仍需重新计算定时器
-      _restartAutoUpdateTimer();
+      _syncAutoUpdateSchedule();
       if (_currentSubscriptionId == subscription.id) {
         await _restartAutoDelayTestTimer();
       }
@@ -1113,6 +1114,7 @@ class SubscriptionProvider extends ChangeNotifier {
 
       // 从列表中移除
       _subscriptions.removeWhere((s) => s.id == subscriptionId);
+      _syncAutoUpdateSchedule();
 
       // 如果删除的是当前选中的订阅
       if (isDeletingCurrentSubscription) {
@@ -1158,7 +1160,7 @@ class SubscriptionProvider extends ChangeNotifier {
       notifyListeners();
 
       // 重新计算定时器间隔（订阅减少可能影响最短间隔）
-      _restartAutoUpdateTimer();
+      _syncAutoUpdateSchedule();
       if (_currentSubscriptionId != null) {
         await _restartAutoDelayTestTimer();
       } else {
@@ -1287,6 +1289,7 @@ class SubscriptionProvider extends ChangeNotifier {
     SubscriptionProxyMode? proxyMode,
     String? userAgent,
     String? ageSecretKey,
+    String? downloadNodeName,
     bool? autoTestAllDelaysEnabled,
     int? autoTestAllDelaysIntervalMinutes,
     List<String>? disabledBuiltinChainProxyNames,
@@ -1312,6 +1315,7 @@ class SubscriptionProvider extends ChangeNotifier {
         proxyMode: proxyMode ?? subscription.proxyMode,
         userAgent: userAgent ?? subscription.userAgent,
         ageSecretKey: ageSecretKey ?? subscription.ageSecretKey,
+        downloadNodeName: downloadNodeName ?? subscription.downloadNodeName,
         autoTestAllDelaysEnabled:
             autoTestAllDelaysEnabled ?? subscription.autoTestAllDelaysEnabled,
         autoTestAllDelaysIntervalMinutes:
@@ -1327,12 +1331,8 @@ class SubscriptionProvider extends ChangeNotifier {
       await _manager.saveSubscriptionList(_subscriptions);
       notifyListeners();
 
-      // 如果修改了自动更新配置，重新计算定时器
-      if (autoUpdateMode != null ||
-          intervalMinutes != null ||
-          shouldUpdateOnStartup != null) {
-        _restartAutoUpdateTimer();
-      }
+      // 自动更新计划包含链接、间隔与下载设置，编辑后重新同步
+      _syncAutoUpdateSchedule();
       if (subscriptionId == _currentSubscriptionId) {
         await _reloadCurrentSubscriptionConfig(reason: '订阅信息编辑');
         await _restartAutoDelayTestTimer();
@@ -1824,6 +1824,10 @@ class SubscriptionProvider extends ChangeNotifier {
 
     try {
       final uri = Uri.parse(url);
+      // 本地文件与 data: URI 由 Rust 端直接读取
+      if (uri.scheme == 'file' || uri.scheme == 'data') {
+        return true;
+      }
       // 检查是否有协议（http/https）
       if (!uri.hasScheme || (uri.scheme != 'http' && uri.scheme != 'https')) {
         return false;
@@ -1940,9 +1944,9 @@ class SubscriptionProvider extends ChangeNotifier {
   void dispose() {
     _clashProvider?.removeListener(_handleClashProviderChanged);
 
-    // 取消自动更新定时器
-    _autoUpdateTimer?.cancel();
-    Logger.debug('自动更新定时器已取消');
+    // 停止监听自动更新结果
+    _scheduledUpdateListener?.cancel();
+    Logger.debug('自动更新结果监听已取消');
 
     _stopAutoDelayTestTimer();
     Logger.debug('自动测试所有延迟定时器已取消');
@@ -109,6 +109,7 @@ class OverrideService {
             BigInt.from(ClashDefaults.overrideDownloadTimeout),
           ),
           mixedPort: mixedPort,
+          requestOptions: null,
         ).sendSignalToRust();
 
         // 等待响应
@@ -149,7 +150,9 @@ class OverrideService {
     return switch (mode) {
       SubscriptionProxyMode.direct => signals.ProxyMode.direct,
       SubscriptionProxyMode.system => signals.ProxyMode.system,
-      SubscriptionProxyMode.core => signals.ProxyMode.core,
+      // 覆写下载不提供指定节点，退回核心代理
+      SubscriptionProxyMode.core ||
+      SubscriptionProxyMode.coreNode => signals.ProxyMode.core,
     };
   }
 
@@ -272,6 +275,7 @@ class OverrideService {
         requestId: requestId,
         baseConfigContent: baseConfigContent,
         overrides: overrideConfigs,
+        jsLimits: null,
       );
 
       request.sendSignalToRust();
@@ -72,8 +72,16 @@ class SubscriptionService {
 
   // 解析订阅内容（通过 Rust）
   Future<String> _parseSubscriptionContent(String content) async {
+    final response = await _parseSubscriptionWithReport(content);
+    return response.parsedConfig;
+  }
+
+  // 解析订阅内容并返回完整响应（含解析报告）
+  Future<ParseSubscriptionResponse> _parseSubscriptionWithReport(
+    String content,
+  ) async {
     final requestId = _buildParseRequestId();
-    final completer = Completer<String>();
+    final completer = Completer<ParseSubscriptionResponse>();
     StreamSubscription? subscription;
 
     try {
@@ -84,7 +92,7 @@ class SubscriptionService {
         if (result.message.requestId != requestId) return;
 
         if (result.message.isSuccessful) {
-          completer.complete(result.message.parsedConfig);
+          completer.complete(result.message);
         } else {
           completer.completeError(Exception(result.message.errorMessage));
         }
@@ -94,6 +102,7 @@ class SubscriptionService {
       final parseRequest = ParseSubscriptionRequest(
         requestId: requestId,
         content: content,
+        options: null,
       );
       parseRequest.sendSignalToRust();
 
@@ -155,6 +164,9 @@ class SubscriptionService {
         ),
         mixedPort: mixedPort,
         ageSecretKey: _normalizeAgeSecretKey(subscription.ageSecretKey),
+        retryPolicy: null,
+        requestOptions: _buildRequestOptions(subscription, proxyMode),
+        maxBodyBytes: null,
       );
       downloadRequest.sendSignalToRust();
 
@@ -170,40 +182,14 @@ class SubscriptionService {
         throw Exception(downloadResult.errorMessage ?? '下载失败');
       }
 
-      // 解析订阅信息
-      final info = _convertSubscriptionInfo(downloadResult.subscriptionInfo);
-
-      // 获取配置内容并解析
-      final parsedConfigContent = await _parseSubscriptionContent(
-        downloadResult.content,
-      );
-
-      // 验证配置文件
-      _validateConfig(parsedConfigContent);
+      if (downloadResult.isStale) {
+        Logger.warning('订阅下载失败，使用缓存内容：${downloadResult.errorMessage}');
+      }
 
-      final chainRuntimeConfig = await _chainProxyService.analyzeAndApply(
-        parsedConfigContent,
+      return await _saveDownloadedContent(
         subscription,
-      );
-
-      // 【重要】保存原始订阅文件，不应用任何覆写
-      // 覆写和链式代理注入将在运行时配置生成时应用
-      final configPath = PathService.instance.getSubscriptionConfigPath(
-        subscription.id,
-      );
-      final configFile = File(configPath);
-      // 确保父目录存在
-      await configFile.parent.create(recursive: true);
-      await configFile.writeAsString(parsedConfigContent);
-
-      Logger.debug('订阅已保存至：$configPath');
-
-      // 返回更新后的订阅
-      return subscription.copyWith(
-        lastUpdatedAt: DateTime.now(),
-        info: info,
-        isUpdating: false,
-        builtinChainProxyNames: chainRuntimeConfig.builtinChainProxyNames,
+        downloadResult.content,
+        downloadResult.subscriptionInfo,
       );
     } catch (e) {
       Logger.error('下载订阅失败：${subscription.name} - $e');
@@ -214,6 +200,115 @@ class SubscriptionService {
     }
   }
 
+  // 应用 Rust 调度器自动更新的结果
+  Future<Subscription> applyScheduledUpdate(
+    Subscription subscription,
+    SubscriptionUpdated update,
+  ) async {
+    if (!update.isSuccessful) {
+      throw Exception(update.errorMessage ?? '下载失败');
+    }
+    if (update.isStale) {
+      Logger.warning('订阅自动更新失败，使用缓存内容：${update.errorMessage}');
+    }
+    return await _saveDownloadedContent(
+      subscription,
+      update.content,
+      update.subscriptionInfo,
+    );
+  }
+
+  // 解析、校验并保存下载的订阅内容
+  Future<Subscription> _saveDownloadedContent(
+    Subscription subscription,
+    String content,
+    SubscriptionInfoData? rustInfo,
+  ) async {
+    // 获取配置内容并解析
+    final parseResponse = await _parseSubscriptionWithReport(content);
+    final parsedConfigContent = parseResponse.parsedConfig;
+
+    // 解析订阅信息；缺少订阅信息头时使用解析报告中从信息节点推导的用量
+    final info = _mergeSubscriptionInfo(
+      _convertSubscriptionInfo(rustInfo),
+      parseResponse.report.usage,
+    );
+
+    // 验证配置文件
+    _validateConfig(parsedConfigContent);
+
+    final chainRuntimeConfig = await _chainProxyService.analyzeAndApply(
+      parsedConfigContent,
+      subscription,
+    );
+
+    // 【重要】保存原始订阅文件，不应用任何覆写
+    // 覆写和链式代理注入将在运行时配置生成时应用
+    final configPath = PathService.instance.getSubscriptionConfigPath(
+      subscription.id,
+    );
+    final configFile = File(configPath);
+    // 确保父目录存在
+    await configFile.parent.create(recursive: true);
+    await configFile.writeAsString(parsedConfigContent);
+
+    Logger.debug('订阅已保存至：$configPath');
+
+    // 返回更新后的订阅
+    return subscription.copyWith(
+      lastUpdatedAt: DateTime.now(),
+      info: info,
+      isUpdating: false,
+      builtinChainProxyNames: chainRuntimeConfig.builtinChainProxyNames,
+    );
+  }
+
+  // 发送自动更新计划到 Rust 调度器（替换现有全部计划）
+  void scheduleAutoUpdates(
+    List<Subscription> subscriptions,
+    SubscriptionProxyMode Function(Subscription) resolveProxyMode,
+    int mixedPort,
+  ) {
+    final scheduled = subscriptions
+        .where(
+          (s) =>
+              (s.autoUpdateMode == AutoUpdateMode.interval ||
+                  s.autoUpdateMode == AutoUpdateMode.provider) &&
+              !s.isLocalFile,
+        )
+        .map(
+          (s) => ScheduledSubscription(
+            subscriptionId: s.id,
+            url: s.url,
+            proxyMode: _convertProxyMode(resolveProxyMode(s)),
+            userAgent: s.userAgent,
+            timeoutSeconds: Uint64(
+              BigInt.from(ClashDefaults.subscriptionDownloadTimeout),
+            ),
+            mixedPort: mixedPort,
+            ageSecretKey: _normalizeAgeSecretKey(s.ageSecretKey),
+            retryPolicy: null,
+            requestOptions: _buildRequestOptions(s, resolveProxyMode(s)),
+            maxBodyBytes: null,
+            // 0 表示跟随订阅响应头 profile-update-interval
+            intervalMinutes: Uint64(
+              BigInt.from(
+                s.autoUpdateMode == AutoUpdateMode.provider
+                    ? 0
+                    : s.intervalMinutes,
+              ),
+            ),
+          ),
+        )
+        .toList();
+
+    ScheduleSubscriptionsRequest(
+      subscriptions: scheduled,
+      maxConcurrency: ClashDefaults.subscriptionUpdateConcurrency,
+    ).sendSignalToRust();
+    Logger.info('已同步订阅自动更新计划：${scheduled.length} 个订阅');
+  }
+
   // 转换代理模式枚举（Dart → Rust）
   ProxyMode _convertProxyMode(SubscriptionProxyMode mode) {
     switch (mode) {
@@ -223,7 +318,29 @@ class SubscriptionService {
         return ProxyMode.system;
       case SubscriptionProxyMode.core:
         return ProxyMode.core;
+      case SubscriptionProxyMode.coreNode:
+        return ProxyMode.coreNode;
+    }
+  }
+
+  // 构建请求选项：目前仅指定节点模式需要携带节点名称
+  HttpRequestOptions? _buildRequestOptions(
+    Subscription subscription,
+    SubscriptionProxyMode proxyMode,
+  ) {
+    if (proxyMode != SubscriptionProxyMode.coreNode) {
+      return null;
     }
+    return HttpRequestOptions(
+      headers: [],
+      basicAuthUsername: null,
+      basicAuthPassword: null,
+      clientCertificatePem: null,
+      clientKeyPem: null,
+      pinnedCertificateSha256: [],
+      upstreamProxyUrl: null,
+      coreNodeName: subscription.downloadNodeName,
+    );
   }
 
   // 转换订阅信息（Rust → Dart）
@@ -238,6 +355,28 @@ class SubscriptionService {
     );
   }
 
+  // 订阅信息头缺少流量与到期时间时，以信息节点推导的用量补全
+  SubscriptionInfo? _mergeSubscriptionInfo(
+    SubscriptionInfo? info,
+    SubscriptionUsage? usage,
+  ) {
+    final hasUsage =
+        info != null &&
+        (info.upload > 0 ||
+            info.download > 0 ||
+            info.total > 0 ||
+            info.expire > 0);
+    if (hasUsage || usage == null) return info;
+
+    Logger.info('缺少订阅信息头，已从信息节点推导订阅用量');
+    return SubscriptionInfo(
+      upload: usage.upload?.toInt() ?? 0,
+      download: usage.download?.toInt() ?? 0,
+      total: usage.total?.toInt() ?? 0,
+      expire: usage.expire?.toInt() ?? 0,
+    );
+  }
+
   String? _normalizeAgeSecretKey(String secretKey) {
     final trimmed = secretKey.trim();
     return trimmed.isEmpty ? null : trimmed;
@@ -358,6 +497,9 @@ class SubscriptionService {
         ),
         mixedPort: mixedPort,
         ageSecretKey: _normalizeAgeSecretKey(ageSecretKey),
+        retryPolicy: null,
+        requestOptions: null,
+        maxBodyBytes: null,
       );
       downloadRequest.sendSignalToRust();
 
@@ -435,6 +577,11 @@ class SubscriptionService {
       Logger.info('已删除订阅配置：${subscription.name}');
     }
 
+    // 清理该订阅的下载缓存（本地文件订阅没有缓存）
+    if (!subscription.isLocalFile) {
+      RemoveSubscriptionCacheRequest(url: subscription.url).sendSignalToRust();
+    }
+
     // 清理该订阅相关的所有节点选择持久化数据
     await ClashPreferences.instance.clearProxySelectionsForSubscription(
       subscription.id,
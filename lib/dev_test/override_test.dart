@@ -95,6 +95,7 @@ class OverrideTest {
     final request = ParseSubscriptionRequest(
       requestId: 'test-parse-${DateTime.now().millisecondsSinceEpoch}',
       content: content,
+      options: null,
     );
     request.sendSignalToRust();
 
@@ -185,6 +186,7 @@ class OverrideTest {
         requestId: requestId,
         baseConfigContent: baseConfig,
         overrides: overrideConfigs,
+        jsLimits: null,
       );
 
       request.sendSignalToRust();
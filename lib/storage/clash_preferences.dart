@@ -147,6 +147,8 @@ class ClashPreferences {
       'clash_external_controller_secret';
   static const String _kKeepAliveEnabled = 'clash_keep_alive_enabled';
   static const String _kKeepAliveInterval = 'clash_keep_alive_interval';
+  // 指定节点下载入站（由订阅列表推导，不参与导出与重置）
+  static const String _kDownloadNodeEnabled = 'clash_download_node_enabled';
 
   // 虚拟网卡模式配置键
   static const String _kTunEnable = 'clash_tun_enable';
@@ -377,6 +379,15 @@ class ClashPreferences {
   Future<void> setKeepAliveEnabled(bool enabled) =>
       _setBool(_kKeepAliveEnabled, enabled);
 
+  // ==================== 指定节点下载 ====================
+
+  // 获取是否注入指定节点下载入站
+  bool getDownloadNodeEnabled() => _getBool(_kDownloadNodeEnabled, false);
+
+  // 保存是否注入指定节点下载入站
+  Future<void> setDownloadNodeEnabled(bool enabled) =>
+      _setBool(_kDownloadNodeEnabled, enabled);
+
   // 获取 TCP 保持活动间隔（秒）
   int getKeepAliveInterval() =>
       _getInt(_kKeepAliveInterval, ClashDefaults.defaultKeepAliveInterval);
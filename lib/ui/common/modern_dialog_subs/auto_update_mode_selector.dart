@@ -41,6 +41,11 @@ class AutoUpdateModeSelector extends StatelessWidget {
           title: trans.auto_update_interval,
           subtitle: trans.auto_update_interval_desc,
         ),
+        OptionItem(
+          value: AutoUpdateMode.provider,
+          title: trans.auto_update_provider,
+          subtitle: trans.auto_update_provider_desc,
+        ),
       ],
       selectedValue: selectedValue,
       onChanged: onChanged,
@@ -9,11 +9,13 @@ import 'package:stelliberty/i18n/i18n.dart';
 class ProxyModeSelector extends StatelessWidget {
   final SubscriptionProxyMode selectedValue;
   final ValueChanged<SubscriptionProxyMode> onChanged;
+  final bool isCoreNodeEnabled; // 是否提供指定节点模式（仅订阅对话框）
 
   const ProxyModeSelector({
     super.key,
     required this.selectedValue,
     required this.onChanged,
+    this.isCoreNodeEnabled = false,
   });
 
   @override
@@ -41,6 +43,12 @@ class ProxyModeSelector extends StatelessWidget {
           title: trans.subscription_dialog.proxy_mode_core_title,
           subtitle: trans.subscription_dialog.proxy_mode_core,
         ),
+        if (isCoreNodeEnabled)
+          OptionItem(
+            value: SubscriptionProxyMode.coreNode,
+            title: trans.subscription_dialog.proxy_mode_core_node_title,
+            subtitle: trans.subscription_dialog.proxy_mode_core_node,
+          ),
       ],
       selectedValue: selectedValue,
       onChanged: onChanged,
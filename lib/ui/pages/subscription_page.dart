@@ -373,6 +373,7 @@ class _SubscriptionPageState extends State<SubscriptionPage> {
           proxyMode: result.proxyMode,
           userAgent: result.userAgent,
           ageSecretKey: result.ageSecretKey,
+          downloadNodeName: result.downloadNodeName,
           autoTestAllDelaysEnabled: result.autoTestAllDelaysEnabled,
           autoTestAllDelaysIntervalMinutes:
               result.autoTestAllDelaysIntervalMinutes,
@@ -467,6 +468,7 @@ class _SubscriptionPageState extends State<SubscriptionPage> {
         proxyMode: result.proxyMode,
         userAgent: result.userAgent,
         ageSecretKey: result.ageSecretKey,
+        downloadNodeName: result.downloadNodeName,
         autoTestAllDelaysEnabled: result.autoTestAllDelaysEnabled,
         autoTestAllDelaysIntervalMinutes:
             result.autoTestAllDelaysIntervalMinutes,
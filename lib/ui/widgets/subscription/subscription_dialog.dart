@@ -38,6 +38,7 @@ class SubscriptionDialog extends StatefulWidget {
   final SubscriptionProxyMode? initialProxyMode;
   final String? initialUserAgent;
   final String? initialAgeSecretKey;
+  final String? initialDownloadNodeName;
   final bool? initialAutoTestAllDelaysEnabled;
   final int? initialAutoTestAllDelaysIntervalMinutes;
   final List<String> initialBuiltinChainProxyNames;
@@ -60,6 +61,7 @@ class SubscriptionDialog extends StatefulWidget {
     this.initialProxyMode,
     this.initialUserAgent,
     this.initialAgeSecretKey,
+    this.initialDownloadNodeName,
     this.initialAutoTestAllDelaysEnabled,
     this.initialAutoTestAllDelaysIntervalMinutes,
     this.initialBuiltinChainProxyNames = const [],
@@ -112,6 +114,7 @@ class SubscriptionDialog extends StatefulWidget {
         initialProxyMode: subscription.proxyMode,
         initialUserAgent: subscription.userAgent,
         initialAgeSecretKey: subscription.ageSecretKey,
+        initialDownloadNodeName: subscription.downloadNodeName,
         initialAutoTestAllDelaysEnabled: subscription.autoTestAllDelaysEnabled,
         initialAutoTestAllDelaysIntervalMinutes:
             subscription.autoTestAllDelaysIntervalMinutes,
@@ -137,6 +140,7 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
   late final TextEditingController _autoDelayTestIntervalController;
   late final TextEditingController _userAgentController;
   late final TextEditingController _ageSecretKeyController;
+  late final TextEditingController _downloadNodeNameController;
   late final FocusNode _autoDelayTestIntervalFocusNode;
   late int _autoDelayTestIntervalMinutes;
   late AutoUpdateMode _autoUpdateMode;
@@ -189,6 +193,9 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
     _ageSecretKeyController = TextEditingController(
       text: widget.initialAgeSecretKey ?? '',
     );
+    _downloadNodeNameController = TextEditingController(
+      text: widget.initialDownloadNodeName ?? '',
+    );
 
     // 初始化自动更新模式和代理模式
     _autoUpdateMode = widget.initialAutoUpdateMode ?? AutoUpdateMode.disabled;
@@ -213,6 +220,7 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
     );
     _userAgentController.addListener(_checkForChanges);
     _ageSecretKeyController.addListener(_checkForChanges);
+    _downloadNodeNameController.addListener(_checkForChanges);
     _autoDelayTestIntervalFocusNode.addListener(
       _handleAutoDelayTestFocusChanged,
     );
@@ -266,6 +274,12 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
       if (currentAgeSecretKey != initialAgeSecretKey) {
         return true;
       }
+
+      if (_proxyMode == SubscriptionProxyMode.coreNode &&
+          _downloadNodeNameController.text.trim() !=
+              (widget.initialDownloadNodeName ?? '')) {
+        return true;
+      }
     }
 
     final initialAutoDelayTestIntervalMinutes =
@@ -390,6 +404,7 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
     );
     _userAgentController.removeListener(_checkForChanges);
     _ageSecretKeyController.removeListener(_checkForChanges);
+    _downloadNodeNameController.removeListener(_checkForChanges);
     _autoDelayTestIntervalFocusNode.removeListener(
       _handleAutoDelayTestFocusChanged,
     );
@@ -400,6 +415,7 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
     _autoDelayTestIntervalController.dispose();
     _userAgentController.dispose();
     _ageSecretKeyController.dispose();
+    _downloadNodeNameController.dispose();
     _autoDelayTestIntervalFocusNode.dispose();
     super.dispose();
   }
@@ -503,6 +519,11 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
                     return trans.subscription_dialog.link_format_error;
                   }
 
+                  // 本地文件与 data: URI 无需校验主机名
+                  if (uri.scheme == 'file' || uri.scheme == 'data') {
+                    return null;
+                  }
+
                   if (uri.scheme != 'http' && uri.scheme != 'https') {
                     return context
                         .translate
@@ -601,12 +622,38 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
 
   // 构建代理模式选择区域
   Widget _buildProxyModeSection() {
-    return ProxyModeSelector(
+    final selector = ProxyModeSelector(
       selectedValue: _proxyMode,
+      isCoreNodeEnabled: true,
       onChanged: (value) {
         setState(() => _proxyMode = value);
       },
     );
+    if (_proxyMode != SubscriptionProxyMode.coreNode) {
+      return selector;
+    }
+
+    // 指定节点模式需填写下载使用的节点名称
+    final dialogTrans = context.translate.subscription_dialog;
+    return Column(
+      crossAxisAlignment: CrossAxisAlignment.stretch,
+      children: [
+        selector,
+        const SizedBox(height: _dialogItemSpacing),
+        TextInputField(
+          controller: _downloadNodeNameController,
+          label: dialogTrans.download_node_name_label,
+          hint: dialogTrans.download_node_name_hint,
+          icon: Icons.hub_outlined,
+          validator: (value) {
+            if (value == null || value.trim().isEmpty) {
+              return dialogTrans.download_node_name_error;
+            }
+            return null;
+          },
+        ),
+      ],
+    );
   }
 
   Widget _buildAutoTestAllDelaysSection() {
@@ -738,6 +785,9 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
       // 获取 UA 值，如果为空则使用默认值
       final userAgent = _userAgentController.text.trim();
       final ageSecretKey = _ageSecretKeyController.text.trim();
+      final downloadNodeName = _proxyMode == SubscriptionProxyMode.coreNode
+          ? _downloadNodeNameController.text.trim()
+          : '';
       final result = SubscriptionDialogResult(
         name: _nameController.text.trim(),
         url: _importMethod == SubscriptionImportMethod.link
@@ -751,6 +801,7 @@ class _SubscriptionDialogState extends State<SubscriptionDialog> {
         proxyMode: _proxyMode,
         userAgent: userAgent.isEmpty ? _defaultUserAgent : userAgent,
         ageSecretKey: ageSecretKey,
+        downloadNodeName: downloadNodeName,
         autoTestAllDelaysIntervalMinutes: _autoDelayTestIntervalMinutes,
         autoTestAllDelaysEnabled: _autoDelayTestIntervalMinutes > 0,
         builtinChainProxyNames: _builtinChainProxyNames,
@@ -827,6 +878,7 @@ class SubscriptionDialogResult {
   final SubscriptionProxyMode proxyMode;
   final String userAgent;
   final String ageSecretKey;
+  final String downloadNodeName;
   final bool autoTestAllDelaysEnabled;
   final int autoTestAllDelaysIntervalMinutes;
   final List<String> builtinChainProxyNames;
@@ -844,6 +896,7 @@ class SubscriptionDialogResult {
     this.proxyMode = SubscriptionProxyMode.direct,
     String? userAgent,
     this.ageSecretKey = '',
+    this.downloadNodeName = '',
     this.autoTestAllDelaysEnabled = false,
     this.autoTestAllDelaysIntervalMinutes = 10,
     this.builtinChainProxyNames = const [],
@@ -7,6 +7,7 @@ pub mod logger;
 pub mod network_interfaces;
 pub mod override_processor;
 pub mod path_resolver;
+pub mod proxy_node;
 pub mod proxy_parser;
 pub mod shared_types;
 pub mod system_proxy;
@@ -15,5 +16,6 @@ pub use ipc_client::{IpcClient, IpcHttpResponse};
 pub use logger::init;
 pub use override_processor::OverrideProcessor;
 pub use path_resolver as path_service;
-pub use proxy_parser::ProxyParser;
-pub use shared_types::{OverrideConfig, OverrideFormat};
+pub use proxy_node::ProxyNode;
+pub use proxy_parser::{LinkExport, ParseOptions, ParseReport, ProxyParser, SubscriptionUsage};
+pub use shared_types::{JsSandboxLimits, OverrideConfig, OverrideFormat};
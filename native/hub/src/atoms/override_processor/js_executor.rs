@@ -1,33 +1,38 @@
 // JavaScript 覆写执行器：负责在 QuickJS 中执行覆写脚本并返回结果。
 // 入口约定： main(config) 返回可 JSON 序列化的配置对象。
+// 每个脚本在独立的运行时中执行，受执行时限与堆内存上限约束。
 
+use crate::atoms::shared_types::JsSandboxLimits;
 use serde_json::Value as JsonValue;
 use serde_yaml_ng::Value as YamlValue;
 
 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
-use rquickjs::{Context, Runtime};
+use rquickjs::{Context, Ctx, Runtime};
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+use std::sync::Arc;
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+use std::time::{Duration, Instant};
 
 // JavaScript 执行器
 pub struct JsExecutor {
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
-    runtime: Runtime,
-    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
-    context: Context,
+    limits: JsSandboxLimits,
 }
 
 impl JsExecutor {
-    // 创建 JavaScript 执行器并初始化 QuickJS 上下文。
+    // 创建 JavaScript 执行器；运行时与上下文在每次执行时新建。
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
-    pub fn new() -> Result<Self, String> {
-        let runtime = Runtime::new().map_err(|e| format!("初始化 JavaScript 运行时失败：{}", e))?;
-        let context =
-            Context::full(&runtime).map_err(|e| format!("初始化 JavaScript 上下文失败：{}", e))?;
-
-        Ok(Self { runtime, context })
+    pub fn new(limits: JsSandboxLimits) -> Result<Self, String> {
+        if limits.timeout_ms == 0 || limits.memory_limit_bytes == 0 {
+            return Err("JavaScript 执行时限与内存上限必须大于 0".to_string());
+        }
+        Ok(Self { limits })
     }
 
     #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
-    pub fn new() -> Result<Self, String> {
+    pub fn new(_limits: JsSandboxLimits) -> Result<Self, String> {
         Ok(Self {})
     }
 
@@ -162,10 +167,106 @@ impl JsExecutor {
 
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
     fn execute_js(&self, full_js_code: &str) -> Result<String, String> {
-        // 保持运行时生命周期，避免上下文提前释放
-        let _runtime = &self.runtime;
-        self.context
-            .with(|ctx| ctx.eval::<String, _>(full_js_code))
-            .map_err(|e| format!("JavaScript 执行失败：{}", e))
+        // 每个覆写使用全新的运行时与上下文，全局变量不会泄漏到后续覆写
+        let runtime = Runtime::new().map_err(|e| format!("初始化 JavaScript 运行时失败：{}", e))?;
+        runtime.set_memory_limit(
+            usize::try_from(self.limits.memory_limit_bytes).unwrap_or(usize::MAX),
+        );
+
+        // 超过时限后由中断处理器终止脚本（如 while(true){}）
+        let deadline = Instant::now() + Duration::from_millis(self.limits.timeout_ms);
+        let is_timed_out = Arc::new(AtomicBool::new(false));
+        let timed_out_flag = Arc::clone(&is_timed_out);
+        runtime.set_interrupt_handler(Some(Box::new(move || {
+            let is_expired = Instant::now() >= deadline;
+            if is_expired {
+                timed_out_flag.store(true, Ordering::Relaxed);
+            }
+            is_expired
+        })));
+
+        let context =
+            Context::full(&runtime).map_err(|e| format!("初始化 JavaScript 上下文失败：{}", e))?;
+        let result = context.with(|ctx| {
+            ctx.eval::<String, _>(full_js_code)
+                .map_err(|e| describe_error(&ctx, e))
+        });
+
+        match result {
+            Ok(output) => Ok(output),
+            Err(_) if is_timed_out.load(Ordering::Relaxed) => Err(format!(
+                "JavaScript 执行超时（超过 {} 毫秒），请检查脚本是否存在死循环",
+                self.limits.timeout_ms
+            )),
+            Err(message) if message.contains("out of memory") => Err(format!(
+                "JavaScript 内存不足（超过上限 {:.1} MB）",
+                self.limits.memory_limit_bytes as f64 / 1024.0 / 1024.0
+            )),
+            Err(message) => Err(format!("JavaScript 执行失败：{}", message)),
+        }
+    }
+}
+
+// 提取脚本抛出的异常信息
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+fn describe_error(ctx: &Ctx<'_>, error: rquickjs::Error) -> String {
+    match error {
+        rquickjs::Error::Exception => {
+            let exception = ctx.catch();
+            match exception.as_exception() {
+                Some(exception) => exception.to_string(),
+                None => exception
+                    .as_string()
+                    .and_then(|value| value.to_string().ok())
+                    .unwrap_or_else(|| format!("{:?}", exception)),
+            }
+        }
+        rquickjs::Error::Allocation => "out of memory".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(all(
+    test,
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+mod tests {
+    use super::JsExecutor;
+    use crate::atoms::shared_types::JsSandboxLimits;
+
+    #[test]
+    fn enforce_sandbox_limits_and_isolate_overrides() -> Result<(), String> {
+        let mut executor = JsExecutor::new(JsSandboxLimits {
+            timeout_ms: 200,
+            memory_limit_bytes: 32 * 1024 * 1024,
+        })?;
+        let base = "proxies: []\n";
+
+        let err = match executor.apply(base, "function main(config) { while (true) {} }") {
+            Ok(_) => return Err("死循环脚本应超时".to_string()),
+            Err(e) => e,
+        };
+        assert!(err.contains("超时"), "{}", err);
+
+        let err = match executor.apply(
+            base,
+            "function main(config) { var a = []; while (true) { a.push(new Array(100000).fill(1)); } }",
+        ) {
+            Ok(_) => return Err("超出内存上限的脚本应失败".to_string()),
+            Err(e) => e,
+        };
+        assert!(err.contains("内存不足"), "{}", err);
+
+        // 前一个覆写定义的全局变量不可见
+        executor.apply(
+            base,
+            "globalThis.leaked = 1; function main(config) { return config; }",
+        )?;
+        let result = executor.apply(
+            base,
+            "function main(config) { config.leaked = typeof leaked; return config; }",
+        )?;
+        assert!(result.contains("leaked: undefined"), "{}", result);
+        Ok(())
     }
 }
@@ -3,7 +3,7 @@
 
 use super::js_executor::JsExecutor;
 use super::yaml_merger::YamlMerger;
-use crate::atoms::shared_types::{OverrideConfig, OverrideFormat};
+use crate::atoms::shared_types::{JsSandboxLimits, OverrideConfig, OverrideFormat};
 
 // 覆写处理器
 pub struct OverrideProcessor {
@@ -12,11 +12,16 @@ pub struct OverrideProcessor {
 }
 
 impl OverrideProcessor {
-    // 创建覆写处理器并初始化执行环境。
+    // 创建覆写处理器并初始化执行环境（使用默认沙箱限制）。
     pub fn new() -> Result<Self, String> {
+        Self::with_js_limits(JsSandboxLimits::default())
+    }
+
+    // 使用指定的 JavaScript 沙箱限制创建覆写处理器。
+    pub fn with_js_limits(limits: JsSandboxLimits) -> Result<Self, String> {
         let yaml_merger = YamlMerger::new();
         let js_executor =
-            JsExecutor::new().map_err(|e| format!("初始化 JavaScript 引擎失败：{}", e))?;
+            JsExecutor::new(limits).map_err(|e| format!("初始化 JavaScript 引擎失败：{}", e))?;
 
         Ok(Self {
             yaml_merger,
@@ -34,6 +34,12 @@ pub struct PathService {
     // 日志文件路径
     log_file: PathBuf,
 
+    // 订阅下载缓存目录
+    subscription_cache_dir: PathBuf,
+
+    // 订阅自动更新计划文件
+    subscription_schedule_file: PathBuf,
+
     // Windows 特有：自启动任务目录
     #[cfg(target_os = "windows")]
     tasks_dir: PathBuf,
@@ -74,6 +80,12 @@ impl PathService {
         // 日志文件路径
         let log_file = app_data_dir.join("running.logs");
 
+        // 订阅下载缓存目录
+        let subscription_cache_dir = app_data_dir.join("subscription_cache");
+
+        // 订阅自动更新计划文件
+        let subscription_schedule_file = app_data_dir.join("subscription_schedule.json");
+
         // Windows 自启动任务目录
         #[cfg(target_os = "windows")]
         let tasks_dir = {
@@ -90,6 +102,8 @@ impl PathService {
             assets_service_dir,
             assets_service_binary,
             log_file,
+            subscription_cache_dir,
+            subscription_schedule_file,
             #[cfg(target_os = "windows")]
             tasks_dir,
         })
@@ -153,6 +167,8 @@ impl PathService {
                 .join("service")
                 .join("stelliberty-service"),
             log_file: current_dir.join("data").join("running.logs"),
+            subscription_cache_dir: current_dir.join("data").join("subscription_cache"),
+            subscription_schedule_file: current_dir.join("data").join("subscription_schedule.json"),
             #[cfg(target_os = "windows")]
             tasks_dir: current_dir.join("tasks"),
         }
@@ -188,6 +204,16 @@ impl PathService {
         &self.log_file
     }
 
+    // 获取订阅下载缓存目录
+    pub fn subscription_cache_dir(&self) -> &PathBuf {
+        &self.subscription_cache_dir
+    }
+
+    // 获取订阅自动更新计划文件路径
+    pub fn subscription_schedule_file(&self) -> &PathBuf {
+        &self.subscription_schedule_file
+    }
+
     // 获取自启动任务目录（仅 Windows）
     #[cfg(target_os = "windows")]
     pub fn tasks_dir(&self) -> &PathBuf {
@@ -268,6 +294,22 @@ pub fn log_file() -> PathBuf {
         .unwrap_or_else(|_| PathBuf::from("running.logs"))
 }
 
+// 获取订阅下载缓存目录
+pub fn subscription_cache_dir() -> PathBuf {
+    PATH_SERVICE
+        .read()
+        .map(|s| s.subscription_cache_dir().clone())
+        .unwrap_or_else(|_| PathBuf::from("subscription_cache"))
+}
+
+// 获取订阅自动更新计划文件路径
+pub fn subscription_schedule_file() -> PathBuf {
+    PATH_SERVICE
+        .read()
+        .map(|s| s.subscription_schedule_file().clone())
+        .unwrap_or_else(|_| PathBuf::from("subscription_schedule.json"))
+}
+
 // 获取自启动任务目录（仅 Windows）
 #[cfg(target_os = "windows")]
 pub fn tasks_dir() -> PathBuf {
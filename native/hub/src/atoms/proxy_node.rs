@@ -0,0 +1,15 @@
+// 代理节点模型原子模块
+// 提供与 mihomo 配置字段一一对应的强类型节点，供解析器与链式代理等模块共用
+
+mod error;
+mod node;
+mod validate;
+
+pub use error::{ParseError, ParseErrorKind};
+pub use node::{
+    AnyTlsNode, Bandwidth, BasicNode, GrpcOptions, H2Options, HttpNode, HttpOptions, Hysteria2Node,
+    HysteriaNode, MieruNode, ProxyCommon, ProxyNode, RealityOptions, ShadowsocksNode,
+    ShadowsocksRNode, SnellNode, Socks5Node, SshNode, TlsOptions, TransportOptions, TrojanNode,
+    TuicNode, UnknownNode, VlessNode, VmessNode, WireGuardNode, WireGuardPeer, WsOptions,
+    XhttpOptions,
+};
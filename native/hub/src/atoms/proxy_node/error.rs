@@ -0,0 +1,62 @@
+// 节点解析错误：携带错误类别，供解析报告区分失败原因
+
+// 解析错误类别
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Unsupported,  // 不支持的协议、节点类型或传输层
+    MissingField, // 缺少必需字段
+    Invalid,      // 其他格式错误
+}
+
+// 节点解析错误
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        Self::new(ParseErrorKind::Unsupported, message)
+    }
+
+    pub fn missing(message: impl Into<String>) -> Self {
+        Self::new(ParseErrorKind::MissingField, message)
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(ParseErrorKind::Invalid, message)
+    }
+}
+
+// 未分类的错误信息视为格式错误
+impl From<String> for ParseError {
+    fn from(message: String) -> Self {
+        Self::invalid(message)
+    }
+}
+
+impl From<&str> for ParseError {
+    fn from(message: &str) -> Self {
+        Self::invalid(message)
+    }
+}
+
+impl From<ParseError> for String {
+    fn from(error: ParseError) -> Self {
+        error.message
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,951 @@
+// mihomo 代理节点类型定义：每种代理类型对应一个结构体，字段名与 mihomo 配置一致。
+// 未建模的字段保存在 extra 中，序列化时原样输出，避免丢失配置。
+
+use super::error::ParseError;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use std::collections::BTreeMap;
+
+// 代理节点（反序列化按 type 字段分派，见下方 Deserialize 实现）
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum ProxyNode {
+    #[serde(rename = "ss")]
+    Shadowsocks(ShadowsocksNode),
+    #[serde(rename = "ssr")]
+    ShadowsocksR(ShadowsocksRNode),
+    #[serde(rename = "vmess")]
+    Vmess(VmessNode),
+    #[serde(rename = "vless")]
+    Vless(VlessNode),
+    #[serde(rename = "trojan")]
+    Trojan(TrojanNode),
+    #[serde(rename = "hysteria")]
+    Hysteria(HysteriaNode),
+    #[serde(rename = "hysteria2")]
+    Hysteria2(Hysteria2Node),
+    #[serde(rename = "tuic")]
+    Tuic(TuicNode),
+    #[serde(rename = "http")]
+    Http(HttpNode),
+    #[serde(rename = "socks5")]
+    Socks5(Socks5Node),
+    #[serde(rename = "wireguard")]
+    WireGuard(WireGuardNode),
+    #[serde(rename = "anytls")]
+    AnyTls(AnyTlsNode),
+    #[serde(rename = "mieru")]
+    Mieru(MieruNode),
+    #[serde(rename = "snell")]
+    Snell(SnellNode),
+    #[serde(rename = "ssh")]
+    Ssh(SshNode),
+    #[serde(rename = "direct")]
+    Direct(BasicNode),
+    #[serde(rename = "dns")]
+    Dns(BasicNode),
+    // 尚未建模的类型（如新版 mihomo 新增协议）
+    #[serde(untagged)]
+    Unknown(UnknownNode),
+}
+
+// 所有节点共有的字段
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProxyCommon {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub server: String,
+    #[serde(
+        default,
+        deserialize_with = "lenient_port",
+        skip_serializing_if = "is_zero"
+    )]
+    pub port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub udp: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dialer_proxy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tfo: Option<bool>,
+}
+
+// TLS 相关字段（VMess/VLESS/Trojan/QUIC 系协议共用）
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TlsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servername: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sni: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_cert_verify: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alpn: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reality_opts: Option<RealityOptions>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct RealityOptions {
+    pub public_key: String,
+    // 短 ID 可能被 YAML 解析为数字
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub short_id: Option<String>,
+}
+
+// 传输层字段
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransportOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ws_opts: Option<WsOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_opts: Option<HttpOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h2_opts: Option<H2Options>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc_opts: Option<GrpcOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xhttp_opts: Option<XhttpOptions>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct WsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, String>>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_int",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_early_data: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub early_data_header_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v2ray_http_upgrade: Option<bool>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct HttpOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct H2Options {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct GrpcOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc_service_name: Option<String>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct XhttpOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+// 带宽：数字（Mbps）或带单位字符串（如 "100 Mbps"）
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Bandwidth {
+    Mbps(u64),
+    Text(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ShadowsocksNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub cipher: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_opts: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub udp_over_tcp: Option<bool>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ShadowsocksRNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub cipher: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub password: String,
+    pub obfs: String,
+    pub protocol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obfs_param: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_param: Option<String>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct VmessNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub uuid: String,
+    #[serde(rename = "alterId", default, deserialize_with = "lenient_int")]
+    pub alter_id: i64,
+    #[serde(default = "default_vmess_cipher")]
+    pub cipher: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packet_encoding: Option<String>,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub transport: TransportOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct VlessNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub uuid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packet_encoding: Option<String>,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub transport: TransportOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TrojanNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub password: String,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub transport: TransportOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct HysteriaNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_str: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub up: Option<Bandwidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub down: Option<Bandwidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obfs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Hysteria2Node {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub up: Option<Bandwidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub down: Option<Bandwidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obfs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obfs_password: Option<String>,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TuicNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub congestion_control: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub udp_relay_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_rtt: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_sni: Option<bool>,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct HttpNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, String>>,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Socks5Node {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub password: Option<String>,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct WireGuardNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub private_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_shared_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv6: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_ips: Option<Vec<String>>,
+    // 列表（[1, 2, 3]）或 Base64 字符串
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserved: Option<JsonValue>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_int",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mtu: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_dns_resolve: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_int",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub persistent_keepalive: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peers: Option<Vec<WireGuardPeer>>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct WireGuardPeer {
+    pub server: String,
+    #[serde(deserialize_with = "lenient_port")]
+    pub port: u16,
+    pub public_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_shared_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_ips: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserved: Option<JsonValue>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct AnyTlsNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub password: String,
+    #[serde(flatten)]
+    pub tls: TlsOptions,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct MieruNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub username: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port_range: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiplexing: Option<String>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnellNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub psk: String,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_int",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub version: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obfs_opts: Option<JsonValue>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct SshNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    pub username: String,
+    #[serde(
+        default,
+        deserialize_with = "lenient_opt_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key_passphrase: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_key: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_key_algorithms: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+// 仅含公共字段的节点（direct/dns）
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct BasicNode {
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+// 未建模类型的节点，保留全部原始字段
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct UnknownNode {
+    #[serde(rename = "type", skip_serializing_if = "String::is_empty")]
+    pub proxy_type: String,
+    #[serde(flatten)]
+    pub common: ProxyCommon,
+    #[serde(flatten)]
+    pub extra: JsonMap<String, JsonValue>,
+}
+
+impl UnknownNode {
+    // 由原始字段构造：仅取出类型相符的公共字段，其余字段原样保留在 extra
+    fn raw(mut map: JsonMap<String, JsonValue>) -> Self {
+        let mut take_string = |key: &str| match map.get(key) {
+            Some(JsonValue::String(_)) => match map.remove(key) {
+                Some(JsonValue::String(value)) => Some(value),
+                _ => None,
+            },
+            _ => None,
+        };
+        let proxy_type = take_string("type").unwrap_or_default();
+        let name = take_string("name").unwrap_or_default();
+        let server = take_string("server").unwrap_or_default();
+        let dialer_proxy = take_string("dialer-proxy");
+        let port = match map.get("port").and_then(JsonValue::as_u64) {
+            Some(port) => match u16::try_from(port) {
+                Ok(port) => {
+                    map.remove("port");
+                    port
+                }
+                Err(_) => 0,
+            },
+            None => 0,
+        };
+
+        Self {
+            proxy_type,
+            common: ProxyCommon {
+                name,
+                server,
+                port,
+                dialer_proxy,
+                ..ProxyCommon::default()
+            },
+            extra: map,
+        }
+    }
+}
+
+impl ProxyNode {
+    // mihomo 代理类型名称
+    pub fn type_name(&self) -> &str {
+        match self {
+            Self::Shadowsocks(_) => "ss",
+            Self::ShadowsocksR(_) => "ssr",
+            Self::Vmess(_) => "vmess",
+            Self::Vless(_) => "vless",
+            Self::Trojan(_) => "trojan",
+            Self::Hysteria(_) => "hysteria",
+            Self::Hysteria2(_) => "hysteria2",
+            Self::Tuic(_) => "tuic",
+            Self::Http(_) => "http",
+            Self::Socks5(_) => "socks5",
+            Self::WireGuard(_) => "wireguard",
+            Self::AnyTls(_) => "anytls",
+            Self::Mieru(_) => "mieru",
+            Self::Snell(_) => "snell",
+            Self::Ssh(_) => "ssh",
+            Self::Direct(_) => "direct",
+            Self::Dns(_) => "dns",
+            Self::Unknown(node) => &node.proxy_type,
+        }
+    }
+
+    pub fn common(&self) -> &ProxyCommon {
+        match self {
+            Self::Shadowsocks(node) => &node.common,
+            Self::ShadowsocksR(node) => &node.common,
+            Self::Vmess(node) => &node.common,
+            Self::Vless(node) => &node.common,
+            Self::Trojan(node) => &node.common,
+            Self::Hysteria(node) => &node.common,
+            Self::Hysteria2(node) => &node.common,
+            Self::Tuic(node) => &node.common,
+            Self::Http(node) => &node.common,
+            Self::Socks5(node) => &node.common,
+            Self::WireGuard(node) => &node.common,
+            Self::AnyTls(node) => &node.common,
+            Self::Mieru(node) => &node.common,
+            Self::Snell(node) => &node.common,
+            Self::Ssh(node) => &node.common,
+            Self::Direct(node) | Self::Dns(node) => &node.common,
+            Self::Unknown(node) => &node.common,
+        }
+    }
+
+    pub fn common_mut(&mut self) -> &mut ProxyCommon {
+        match self {
+            Self::Shadowsocks(node) => &mut node.common,
+            Self::ShadowsocksR(node) => &mut node.common,
+            Self::Vmess(node) => &mut node.common,
+            Self::Vless(node) => &mut node.common,
+            Self::Trojan(node) => &mut node.common,
+            Self::Hysteria(node) => &mut node.common,
+            Self::Hysteria2(node) => &mut node.common,
+            Self::Tuic(node) => &mut node.common,
+            Self::Http(node) => &mut node.common,
+            Self::Socks5(node) => &mut node.common,
+            Self::WireGuard(node) => &mut node.common,
+            Self::AnyTls(node) => &mut node.common,
+            Self::Mieru(node) => &mut node.common,
+            Self::Snell(node) => &mut node.common,
+            Self::Ssh(node) => &mut node.common,
+            Self::Direct(node) | Self::Dns(node) => &mut node.common,
+            Self::Unknown(node) => &mut node.common,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.common().name
+    }
+
+    // 从 JSON 构造并校验节点
+    pub fn from_json(value: JsonValue) -> Result<Self, ParseError> {
+        let node = Self::from_json_unchecked(value)?;
+        node.validate()?;
+        Ok(node)
+    }
+
+    // 从 JSON 构造节点（仅检查字段类型，不校验必需字段取值）
+    pub fn from_json_unchecked(value: JsonValue) -> Result<Self, ParseError> {
+        let JsonValue::Object(mut map) = value else {
+            return Err("节点格式无效：不是对象".into());
+        };
+        let name = map
+            .get("name")
+            .and_then(JsonValue::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let proxy_type = match map.get("type") {
+            Some(JsonValue::String(proxy_type)) => proxy_type.clone(),
+            _ => return Err(ParseError::missing(format!("节点 {} 缺少 type", name))),
+        };
+
+        // 已知类型移除 type 字段，避免进入 extra
+        if KNOWN_TYPES.contains(&proxy_type.as_str()) {
+            map.remove("type");
+        }
+        let value = JsonValue::Object(map);
+
+        let node = match proxy_type.as_str() {
+            "ss" => from_value(value).map(Self::Shadowsocks),
+            "ssr" => from_value(value).map(Self::ShadowsocksR),
+            "vmess" => from_value(value).map(Self::Vmess),
+            "vless" => from_value(value).map(Self::Vless),
+            "trojan" => from_value(value).map(Self::Trojan),
+            "hysteria" => from_value(value).map(Self::Hysteria),
+            "hysteria2" => from_value(value).map(Self::Hysteria2),
+            "tuic" => from_value(value).map(Self::Tuic),
+            "http" => from_value(value).map(Self::Http),
+            "socks5" => from_value(value).map(Self::Socks5),
+            "wireguard" => from_value(value).map(Self::WireGuard),
+            "anytls" => from_value(value).map(Self::AnyTls),
+            "mieru" => from_value(value).map(Self::Mieru),
+            "snell" => from_value(value).map(Self::Snell),
+            "ssh" => from_value(value).map(Self::Ssh),
+            "direct" => from_value(value).map(Self::Direct),
+            "dns" => from_value(value).map(Self::Dns),
+            _ => from_value(value).map(Self::Unknown),
+        };
+
+        node.map_err(|e| describe_error(&name, &e))
+    }
+
+    // 从 JSON 构造节点；字段类型与模型不符时（mihomo 按弱类型解码仍可接受，
+    // 如 alpn: h2、udp: "true"）保留为原始节点，原样输出全部字段
+    pub fn from_json_lenient(value: JsonValue) -> Result<Self, String> {
+        let JsonValue::Object(map) = value else {
+            return Err("节点格式无效：不是对象".to_string());
+        };
+        match Self::from_json_unchecked(JsonValue::Object(map.clone())) {
+            Ok(node) => Ok(node),
+            Err(e) => {
+                log::warn!("{}，按原始字段保留", e);
+                Ok(Self::Unknown(UnknownNode::raw(map)))
+            }
+        }
+    }
+
+    // 从 YAML 构造节点，规则同 from_json_lenient
+    pub fn from_yaml_lenient(value: &serde_yaml_ng::Value) -> Result<Self, String> {
+        let value = serde_json::to_value(value).map_err(|e| format!("节点格式无效：{}", e))?;
+        Self::from_json_lenient(value)
+    }
+
+    // 序列化为 JSON（字段名与 mihomo 配置一致）
+    pub fn to_json(&self) -> Result<JsonValue, String> {
+        serde_json::to_value(self).map_err(|e| format!("节点序列化失败：{}", e))
+    }
+}
+
+impl<'de> Deserialize<'de> for ProxyNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = JsonValue::deserialize(deserializer)?;
+        Self::from_json_unchecked(value).map_err(serde::de::Error::custom)
+    }
+}
+
+// 已建模的代理类型
+const KNOWN_TYPES: &[&str] = &[
+    "ss",
+    "ssr",
+    "vmess",
+    "vless",
+    "trojan",
+    "hysteria",
+    "hysteria2",
+    "tuic",
+    "http",
+    "socks5",
+    "wireguard",
+    "anytls",
+    "mieru",
+    "snell",
+    "ssh",
+    "direct",
+    "dns",
+];
+
+fn from_value<T: serde::de::DeserializeOwned>(value: JsonValue) -> Result<T, serde_json::Error> {
+    serde_json::from_value(value)
+}
+
+// 将 serde 错误转换为可读信息（缺失字段单独提示）
+fn describe_error(name: &str, error: &serde_json::Error) -> ParseError {
+    let message = error.to_string();
+    match message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split_once('`'))
+    {
+        Some((field, _)) => ParseError::missing(format!("节点 {} 缺少 {}", name, field)),
+        None => ParseError::invalid(format!("节点 {} 格式无效：{}", name, message)),
+    }
+}
+
+fn default_vmess_cipher() -> String {
+    "auto".to_string()
+}
+
+fn is_zero(value: &u16) -> bool {
+    *value == 0
+}
+
+// 宽松数值/字符串解析：兼容订阅中以字符串书写的数字与以数字书写的字符串
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl NumberOrString {
+    fn into_int(self) -> Result<i64, String> {
+        match self {
+            Self::Integer(value) => Ok(value),
+            Self::Float(value) if value.fract() == 0.0 => Ok(value as i64),
+            Self::Float(value) => Err(format!("不是整数：{}", value)),
+            Self::Text(text) => text
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| format!("不是整数：{}", text)),
+        }
+    }
+
+    fn into_string(self) -> String {
+        match self {
+            Self::Integer(value) => value.to_string(),
+            Self::Float(value) => value.to_string(),
+            Self::Text(text) => text,
+        }
+    }
+}
+
+fn lenient_port<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u16, D::Error> {
+    let value = NumberOrString::deserialize(deserializer)?
+        .into_int()
+        .map_err(serde::de::Error::custom)?;
+    u16::try_from(value).map_err(|_| serde::de::Error::custom(format!("端口超出范围：{}", value)))
+}
+
+fn lenient_int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    NumberOrString::deserialize(deserializer)?
+        .into_int()
+        .map_err(serde::de::Error::custom)
+}
+
+fn lenient_opt_int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    Option::<NumberOrString>::deserialize(deserializer)?
+        .map(NumberOrString::into_int)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+fn lenient_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(NumberOrString::deserialize(deserializer)?.into_string())
+}
+
+fn lenient_opt_string<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(Option::<NumberOrString>::deserialize(deserializer)?.map(NumberOrString::into_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyNode;
+    use crate::atoms::proxy_node::{ParseError, ParseErrorKind};
+    use serde_json::json;
+
+    #[test]
+    fn round_trip_typed_and_unknown_nodes() -> Result<(), String> {
+        let vless = json!({
+            "name": "A", "type": "vless", "server": "a.com", "port": "443",
+            "uuid": "id", "tls": true, "servername": "a.com", "network": "ws",
+            "ws-opts": {"path": "/ws", "headers": {"Host": "cdn.com"}, "v2ray-http-upgrade-fast-open": true},
+            "reality-opts": {"public-key": "key", "short-id": 1234},
+            "smux": {"enabled": true},
+        });
+        let node = ProxyNode::from_json(vless)?;
+        let ProxyNode::Vless(inner) = &node else {
+            return Err(format!("类型错误：{:?}", node));
+        };
+        assert_eq!(inner.common.port, 443);
+        assert_eq!(
+            inner
+                .tls
+                .reality_opts
+                .as_ref()
+                .and_then(|r| r.short_id.as_deref()),
+            Some("1234")
+        );
+        assert!(inner.extra.contains_key("smux"));
+        assert!(!inner.extra.contains_key("servername"));
+
+        let value = node.to_json()?;
+        assert_eq!(value["type"], "vless");
+        assert_eq!(value["port"], 443);
+        assert_eq!(value["ws-opts"]["v2ray-http-upgrade-fast-open"], true);
+
+        let unknown =
+            json!({"name": "M", "type": "masque", "server": "m.com", "port": 443, "key": "v"});
+        let node = ProxyNode::from_json(unknown)?;
+        assert_eq!(node.type_name(), "masque");
+        assert_eq!(node.to_json()?["key"], "v");
+        Ok(())
+    }
+
+    #[test]
+    fn keep_loosely_typed_nodes_as_raw() -> Result<(), String> {
+        let loose = json!({
+            "name": "L", "type": "trojan", "server": "l.com", "port": 443, "password": "pw",
+            "alpn": "h2", "udp": "true", "dialer-proxy": "HK",
+            "ws-opts": {"headers": {"X-Id": 1}},
+        });
+        assert!(ProxyNode::from_json_unchecked(loose.clone()).is_err());
+
+        let node = ProxyNode::from_json_lenient(loose.clone())?;
+        assert_eq!(node.name(), "L");
+        assert_eq!(node.type_name(), "trojan");
+        assert_eq!(node.common().dialer_proxy.as_deref(), Some("HK"));
+        assert_eq!(node.to_json()?, loose);
+        Ok(())
+    }
+
+    #[test]
+    fn reject_nodes_missing_required_fields() {
+        let missing_uuid = json!({"name": "A", "type": "vmess", "server": "a.com", "port": 443});
+        assert!(ProxyNode::from_json(missing_uuid).is_err());
+
+        let empty_uuid =
+            json!({"name": "A", "type": "vless", "server": "a.com", "port": 443, "uuid": ""});
+        assert_eq!(
+            ProxyNode::from_json(empty_uuid).err(),
+            Some(ParseError::missing("节点 A 缺少 uuid"))
+        );
+
+        let bad_transport = json!({
+            "name": "A", "type": "trojan", "server": "a.com", "port": 443,
+            "password": "pw", "network": "kcp",
+        });
+        assert_eq!(
+            ProxyNode::from_json(bad_transport).err().map(|e| e.kind),
+            Some(ParseErrorKind::Unsupported)
+        );
+    }
+}
@@ -0,0 +1,175 @@
+// 代理节点校验：检查 mihomo 启动时必需的字段，提前拒绝无法连接的节点。
+
+use super::error::ParseError;
+use super::node::{ProxyCommon, ProxyNode, TransportOptions};
+
+// mihomo 支持的传输层
+const SUPPORTED_NETWORKS: &[&str] = &["tcp", "http", "h2", "grpc", "ws", "xhttp"];
+
+impl ProxyNode {
+    // 校验必需字段
+    pub fn validate(&self) -> Result<(), ParseError> {
+        let common = self.common();
+        if common.name.trim().is_empty() {
+            return Err(ParseError::missing(format!(
+                "{} 节点缺少名称",
+                self.type_name()
+            )));
+        }
+
+        match self {
+            Self::Shadowsocks(node) => {
+                require_endpoint(common)?;
+                require(common, "cipher", &node.cipher)?;
+                require(common, "password", &node.password)
+            }
+            Self::ShadowsocksR(node) => {
+                require_endpoint(common)?;
+                require(common, "cipher", &node.cipher)?;
+                require(common, "password", &node.password)?;
+                require(common, "obfs", &node.obfs)?;
+                require(common, "protocol", &node.protocol)
+            }
+            Self::Vmess(node) => {
+                require_endpoint(common)?;
+                require(common, "uuid", &node.uuid)?;
+                validate_transport(common, &node.transport)
+            }
+            Self::Vless(node) => {
+                require_endpoint(common)?;
+                require(common, "uuid", &node.uuid)?;
+                validate_transport(common, &node.transport)
+            }
+            Self::Trojan(node) => {
+                require_endpoint(common)?;
+                require(common, "password", &node.password)?;
+                validate_transport(common, &node.transport)
+            }
+            Self::Tuic(node) => {
+                require_endpoint(common)?;
+                let has_v5_auth = node.uuid.as_deref().is_some_and(|u| !u.is_empty());
+                let has_v4_auth = node.token.as_deref().is_some_and(|t| !t.is_empty());
+                if !has_v5_auth && !has_v4_auth {
+                    return Err(ParseError::missing(format!(
+                        "节点 {} 缺少 uuid 或 token",
+                        common.name
+                    )));
+                }
+                Ok(())
+            }
+            Self::WireGuard(node) => {
+                require(common, "private-key", &node.private_key)?;
+                match &node.peers {
+                    Some(peers) if !peers.is_empty() => {
+                        for peer in peers {
+                            if peer.server.is_empty() || peer.port == 0 {
+                                return Err(ParseError::missing(format!(
+                                    "节点 {} 的 peer 缺少服务器地址或端口",
+                                    common.name
+                                )));
+                            }
+                            require(common, "peer public-key", &peer.public_key)?;
+                        }
+                        Ok(())
+                    }
+                    _ => {
+                        require_endpoint(common)?;
+                        require(
+                            common,
+                            "public-key",
+                            node.public_key.as_deref().unwrap_or_default(),
+                        )
+                    }
+                }
+            }
+            Self::AnyTls(node) => {
+                require_endpoint(common)?;
+                require(common, "password", &node.password)
+            }
+            Self::Mieru(node) => {
+                if common.server.is_empty() {
+                    return Err(ParseError::missing(format!(
+                        "节点 {} 缺少服务器地址",
+                        common.name
+                    )));
+                }
+                if common.port == 0 && node.port_range.as_deref().unwrap_or_default().is_empty() {
+                    return Err(ParseError::missing(format!(
+                        "节点 {} 缺少端口或 port-range",
+                        common.name
+                    )));
+                }
+                require(common, "username", &node.username)?;
+                require(common, "password", &node.password)
+            }
+            Self::Snell(node) => {
+                require_endpoint(common)?;
+                require(common, "psk", &node.psk)?;
+                match node.version {
+                    Some(version) if !(1..=5).contains(&version) => Err(ParseError::invalid(
+                        format!("节点 {} 的 Snell 版本无效：{}", common.name, version),
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            Self::Ssh(node) => {
+                require_endpoint(common)?;
+                require(common, "username", &node.username)?;
+                let has_password = node.password.as_deref().is_some_and(|p| !p.is_empty());
+                let has_key = node.private_key.as_deref().is_some_and(|k| !k.is_empty());
+                if !has_password && !has_key {
+                    return Err(ParseError::missing(format!(
+                        "节点 {} 缺少密码或私钥",
+                        common.name
+                    )));
+                }
+                Ok(())
+            }
+            Self::Hysteria(_) | Self::Hysteria2(_) | Self::Http(_) | Self::Socks5(_) => {
+                require_endpoint(common)
+            }
+            Self::Direct(_) | Self::Dns(_) | Self::Unknown(_) => Ok(()),
+        }
+    }
+}
+
+fn require_endpoint(common: &ProxyCommon) -> Result<(), ParseError> {
+    if common.server.trim().is_empty() {
+        return Err(ParseError::missing(format!(
+            "节点 {} 缺少服务器地址",
+            common.name
+        )));
+    }
+    if common.port == 0 {
+        return Err(ParseError::missing(format!(
+            "节点 {} 缺少端口",
+            common.name
+        )));
+    }
+    Ok(())
+}
+
+fn require(common: &ProxyCommon, field: &str, value: &str) -> Result<(), ParseError> {
+    if value.trim().is_empty() {
+        return Err(ParseError::missing(format!(
+            "节点 {} 缺少 {}",
+            common.name, field
+        )));
+    }
+    Ok(())
+}
+
+fn validate_transport(
+    common: &ProxyCommon,
+    transport: &TransportOptions,
+) -> Result<(), ParseError> {
+    match transport.network.as_deref() {
+        Some(network) if !SUPPORTED_NETWORKS.contains(&network) => {
+            Err(ParseError::unsupported(format!(
+                "节点 {} 使用了 mihomo 不支持的 {} 传输",
+                common.name, network
+            )))
+        }
+        _ => Ok(()),
+    }
+}
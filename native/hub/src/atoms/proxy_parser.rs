@@ -1,5 +1,26 @@
 // 代理链接解析器原子模块
 
+mod exporter;
+mod groups;
+mod info_nodes;
+mod json_config;
+mod line_format;
+mod normalize;
+mod options;
 mod parser;
+mod pipeline;
+mod report;
+mod shadowsocks;
+mod transport;
+mod wireguard;
 
+pub use exporter::LinkExport;
+pub use groups::{GroupKind, GroupTemplate, RegionDefinition};
+pub use info_nodes::SubscriptionUsage;
+pub use options::ParseOptions;
 pub use parser::ProxyParser;
+pub use pipeline::{NodePipeline, RenameRule};
+pub use report::{
+    LineOutcome, LineStatus, NodeChange, NodeChangeKind, ParseReport, ProtocolCount,
+    SubscriptionFormat,
+};
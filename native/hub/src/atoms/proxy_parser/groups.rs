@@ -0,0 +1,292 @@
+// 代理组模板：按节点名称关键字或旗帜 emoji 生成地区分组，
+// 可选故障转移/负载均衡组，并附加用户规则。
+
+use regex::Regex;
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+
+// 默认测速地址与间隔（秒）
+const DEFAULT_TEST_URL: &str = "https://www.gstatic.com/generate_204";
+const DEFAULT_TEST_INTERVAL: u32 = 300;
+
+// 主选择组名称（规则兜底指向此组）
+const MAIN_GROUP: &str = "PROXY";
+const AUTO_GROUP: &str = "AUTO";
+const FALLBACK_GROUP: &str = "FALLBACK";
+const LOAD_BALANCE_GROUP: &str = "LOAD-BALANCE";
+
+// 未提供模板时生成的默认代理组
+pub(super) const DEFAULT_GROUP_NAMES: &[&str] = &[MAIN_GROUP, AUTO_GROUP];
+
+// 内置地区：(分组名称, 旗帜, 地区代码, 名称关键字)
+const BUILTIN_REGIONS: &[(&str, &str, &str, &str)] = &[
+    ("🇭🇰 香港", "🇭🇰", "hk|hkg", "香港|hong\\s?kong"),
+    ("🇹🇼 台湾", "🇹🇼", "tw|twn", "台湾|台灣|taiwan|台北"),
+    (
+        "🇯🇵 日本",
+        "🇯🇵",
+        "jp|jpn",
+        "日本|japan|东京|東京|tokyo|大阪|osaka",
+    ),
+    ("🇸🇬 新加坡", "🇸🇬", "sg|sgp", "新加坡|狮城|獅城|singapore"),
+    (
+        "🇺🇸 美国",
+        "🇺🇸",
+        "us|usa",
+        "美国|美國|united\\s?states|america|洛杉矶|硅谷|los\\s?angeles|san\\s?jose",
+    ),
+    ("🇰🇷 韩国", "🇰🇷", "kr|kor", "韩国|韓國|korea|首尔|首爾|seoul"),
+    (
+        "🇬🇧 英国",
+        "🇬🇧",
+        "uk|gb|gbr",
+        "英国|英國|united\\s?kingdom|london|伦敦",
+    ),
+    (
+        "🇩🇪 德国",
+        "🇩🇪",
+        "de|deu",
+        "德国|德國|germany|frankfurt|法兰克福",
+    ),
+];
+
+// 代理组类型
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupKind {
+    Select = 0,
+    UrlTest = 1,
+    Fallback = 2,
+    LoadBalance = 3,
+}
+
+// 自定义地区分组
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct RegionDefinition {
+    pub name: String,    // 分组名称
+    pub pattern: String, // 匹配节点名称的正则表达式
+}
+
+// 代理组模板
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, Default)]
+pub struct GroupTemplate {
+    pub test_url: String,                     // 为空时使用默认测速地址
+    pub test_interval: u32,                   // 为 0 时使用默认间隔
+    pub region_groups: bool,                  // 是否生成地区分组
+    pub regions: Vec<RegionDefinition>,       // 为空时使用内置地区
+    pub region_group_kind: Option<GroupKind>, // 为空时使用 url-test
+    pub fallback_group: bool,                 // 生成 FALLBACK 组
+    pub load_balance_group: bool,             // 生成 LOAD-BALANCE 组
+    pub rules: Vec<String>,                   // 追加在 MATCH 之前的规则
+}
+
+// 编译后的地区匹配器
+pub(super) struct RegionMatcher {
+    pub(super) name: String,
+    pub(super) flag: Option<&'static str>, // 内置地区的旗帜 emoji
+    pub(super) regex: Regex,
+}
+
+// 编译内置地区匹配器
+pub(super) fn builtin_region_matchers() -> Result<Vec<RegionMatcher>, String> {
+    BUILTIN_REGIONS
+        .iter()
+        .map(|(name, flag, codes, keywords)| {
+            let pattern = format!(
+                "(?i){}|(?:^|[^a-z])(?:{})(?:[^a-z]|$)|{}",
+                flag, codes, keywords
+            );
+            Regex::new(&pattern)
+                .map(|regex| RegionMatcher {
+                    name: name.to_string(),
+                    flag: Some(flag),
+                    regex,
+                })
+                .map_err(|e| format!("内置地区规则无效：{}", e))
+        })
+        .collect()
+}
+
+impl GroupTemplate {
+    // 根据节点名称生成代理组与规则
+    pub(super) fn build(
+        &self,
+        proxy_names: &[String],
+    ) -> Result<(Vec<JsonValue>, Vec<String>), String> {
+        let test_url = if self.test_url.trim().is_empty() {
+            DEFAULT_TEST_URL
+        } else {
+            self.test_url.trim()
+        };
+        let interval = if self.test_interval == 0 {
+            DEFAULT_TEST_INTERVAL
+        } else {
+            self.test_interval
+        };
+        let group = |name: &str, kind: GroupKind, members: Vec<String>| -> JsonValue {
+            let mut group = json!({
+                "name": name,
+                "type": kind.type_name(),
+                "proxies": members,
+            });
+            if kind != GroupKind::Select {
+                group["url"] = json!(test_url);
+                group["interval"] = json!(interval);
+            }
+            if kind == GroupKind::LoadBalance {
+                group["strategy"] = json!("consistent-hashing");
+            }
+            group
+        };
+
+        // 地区分组（每个节点只归入第一个匹配的地区，空分组不生成）
+        let mut region_groups = Vec::new();
+        if self.region_groups {
+            let region_kind = self.region_group_kind.unwrap_or(GroupKind::UrlTest);
+            let matchers = self.region_matchers()?;
+            let mut members: Vec<Vec<String>> = vec![Vec::new(); matchers.len()];
+            for name in proxy_names {
+                if let Some(index) = matchers.iter().position(|m| m.regex.is_match(name)) {
+                    members[index].push(name.clone());
+                }
+            }
+            for (matcher, members) in matchers.iter().zip(members) {
+                if !members.is_empty() {
+                    region_groups.push(group(&matcher.name, region_kind, members));
+                }
+            }
+        }
+
+        let mut auxiliary_groups =
+            vec![group(AUTO_GROUP, GroupKind::UrlTest, proxy_names.to_vec())];
+        if self.fallback_group {
+            auxiliary_groups.push(group(
+                FALLBACK_GROUP,
+                GroupKind::Fallback,
+                proxy_names.to_vec(),
+            ));
+        }
+        if self.load_balance_group {
+            auxiliary_groups.push(group(
+                LOAD_BALANCE_GROUP,
+                GroupKind::LoadBalance,
+                proxy_names.to_vec(),
+            ));
+        }
+
+        // 主选择组：辅助组、地区组在前，全部节点在后
+        let main_members: Vec<String> = auxiliary_groups
+            .iter()
+            .chain(&region_groups)
+            .filter_map(|g| g["name"].as_str().map(str::to_string))
+            .chain(proxy_names.iter().cloned())
+            .collect();
+
+        let mut groups = vec![group(MAIN_GROUP, GroupKind::Select, main_members)];
+        groups.extend(auxiliary_groups);
+        groups.extend(region_groups);
+
+        let mut rules: Vec<String> = self
+            .rules
+            .iter()
+            .map(|rule| rule.trim())
+            .filter(|rule| !rule.is_empty() && !rule.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        rules.push(format!("MATCH,{}", MAIN_GROUP));
+
+        Ok((groups, rules))
+    }
+
+    // 模板可能生成的全部代理组名称，节点名称需避开（空地区组同样保留）
+    pub(super) fn group_names(&self) -> Vec<String> {
+        let mut names = vec![MAIN_GROUP.to_string(), AUTO_GROUP.to_string()];
+        if self.fallback_group {
+            names.push(FALLBACK_GROUP.to_string());
+        }
+        if self.load_balance_group {
+            names.push(LOAD_BALANCE_GROUP.to_string());
+        }
+        if self.region_groups {
+            if self.regions.is_empty() {
+                names.extend(BUILTIN_REGIONS.iter().map(|(name, ..)| name.to_string()));
+            } else {
+                names.extend(self.regions.iter().map(|region| region.name.clone()));
+            }
+        }
+        names
+    }
+
+    fn region_matchers(&self) -> Result<Vec<RegionMatcher>, String> {
+        if self.regions.is_empty() {
+            return builtin_region_matchers();
+        }
+
+        self.regions
+            .iter()
+            .map(|region| {
+                Regex::new(&region.pattern)
+                    .map(|regex| RegionMatcher {
+                        name: region.name.clone(),
+                        flag: None,
+                        regex,
+                    })
+                    .map_err(|e| format!("地区分组 {} 的匹配规则无效：{}", region.name, e))
+            })
+            .collect()
+    }
+}
+
+impl GroupKind {
+    fn type_name(self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::UrlTest => "url-test",
+            Self::Fallback => "fallback",
+            Self::LoadBalance => "load-balance",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GroupKind, GroupTemplate, RegionDefinition};
+
+    #[test]
+    fn build_region_groups_from_names_and_flags() -> Result<(), String> {
+        let names: Vec<String> = ["🇭🇰 01", "HK-02", "日本 东京", "US01", "Russia", "Other"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        let template = GroupTemplate {
+            region_groups: true,
+            fallback_group: true,
+            test_interval: 600,
+            rules: vec!["DOMAIN-SUFFIX,cn,DIRECT".to_string()],
+            ..Default::default()
+        };
+
+        let (groups, rules) = template.build(&names)?;
+        let group_names: Vec<_> = groups.iter().filter_map(|g| g["name"].as_str()).collect();
+        assert_eq!(
+            group_names,
+            vec!["PROXY", "AUTO", "FALLBACK", "🇭🇰 香港", "🇯🇵 日本", "🇺🇸 美国"]
+        );
+        assert_eq!(groups[3]["proxies"], serde_json::json!(["🇭🇰 01", "HK-02"]));
+        assert_eq!(groups[5]["proxies"], serde_json::json!(["US01"]));
+        assert_eq!(groups[2]["interval"], 600);
+        assert_eq!(rules, vec!["DOMAIN-SUFFIX,cn,DIRECT", "MATCH,PROXY"]);
+
+        let custom = GroupTemplate {
+            region_groups: true,
+            region_group_kind: Some(GroupKind::Select),
+            regions: vec![RegionDefinition {
+                name: "Bad".to_string(),
+                pattern: "(".to_string(),
+            }],
+            ..Default::default()
+        };
+        assert!(custom.build(&names).is_err());
+        Ok(())
+    }
+}
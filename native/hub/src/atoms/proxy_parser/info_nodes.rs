@@ -0,0 +1,250 @@
+// 信息节点：机场放在节点列表中的“剩余流量”“套餐到期”“官网”等伪节点。
+// 识别后从配置中移除，并在缺少 subscription-userinfo 头时从中推导流量与到期时间。
+
+use super::pipeline::NodePipeline;
+use super::report::{NodeChange, NodeChangeKind};
+use crate::atoms::ProxyNode;
+use chrono::{Local, NaiveDate, TimeZone};
+use regex::Regex;
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::Value as YamlValue;
+
+// 内置信息节点规则：仅匹配信息节点的典型形态，避免误删“大流量专线”“官方优化”等普通节点
+const BUILTIN_INFO_PATTERNS: &[&str] = &[
+    // 名称以关键字开头并跟冒号，如“剩余流量：120GB”“客服：@xxx”；排除“流量倍率：1.0”
+    r"(?i)^\W*(?:剩余|流量|到期|过期|有效期|重置|官网|网址|官方|客服|频道|群组|公告|traffic|expire|expiry|reset|website|remaining)[^:：倍率]{0,6}[:：]",
+    // 关键字与冒号出现在名称中间时，冒号后须为流量、日期或网址，如“套餐到期：2026-12-01”
+    r"(?i)(?:剩余|流量|到期|过期|有效期|重置|官网|网址|traffic|expire|expiry|reset|website|remaining)[^:：倍率]{0,6}[:：]\s*(?:\d+(?:\.\d+)?\s*[KMGTP]i?B|\d{4}\s*[-/.年]\s*\d{1,2}|(?:https?://)?[a-z0-9-]+\.[a-z]{2,})",
+    // 关键字后跟流量数值，如“剩余 120GB”
+    r"(?i)(?:剩余|流量|已用|总量|套餐|traffic|remaining|used|left)\D{0,8}\d+(?:\.\d+)?\s*[KMGTP]i?B",
+    // 关键字后跟日期，如“套餐到期 2026-12-01”
+    r"(?i)(?:到期|过期|有效期|重置|expire|expiry|reset)\D{0,8}\d{4}\s*[-/.年]\s*\d{1,2}",
+    // 官网后跟域名，如“官网 example.com”
+    r"(?i)(?:官网|网址|官方网站|website)\s*[:：]?\s*[a-z0-9-]+\.[a-z]{2,}",
+];
+
+// 从信息节点推导的订阅用量（字节 / Unix 时间戳）
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubscriptionUsage {
+    pub upload: Option<u64>,
+    pub download: Option<u64>,
+    pub total: Option<u64>,
+    pub expire: Option<i64>,
+}
+
+// 信息节点处理结果
+pub(super) struct InfoNodeOutput {
+    pub(super) config: String,
+    pub(super) changes: Vec<NodeChange>,
+    pub(super) proxies: Vec<ProxyNode>,
+    pub(super) usage: Option<SubscriptionUsage>,
+}
+
+// 移除配置中的信息节点；未发现信息节点时返回 None，配置保持原样
+pub(super) fn strip_info_nodes(
+    config: &str,
+    patterns: &[String],
+) -> Result<Option<InfoNodeOutput>, String> {
+    let patterns: Vec<&str> = if patterns.iter().all(|p| p.trim().is_empty()) {
+        BUILTIN_INFO_PATTERNS.to_vec()
+    } else {
+        patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .collect()
+    };
+    let exclude_pattern = patterns
+        .iter()
+        .map(|p| format!("(?:{})", p))
+        .collect::<Vec<_>>()
+        .join("|");
+    let matcher =
+        Regex::new(&exclude_pattern).map_err(|e| format!("信息节点匹配规则无效：{}", e))?;
+
+    let root: YamlValue =
+        serde_yaml_ng::from_str(config).map_err(|e| format!("YAML 解析失败：{}", e))?;
+    let info_names: Vec<String> = root["proxies"]
+        .as_sequence()
+        .map(|proxies| {
+            proxies
+                .iter()
+                .filter_map(|proxy| proxy["name"].as_str())
+                .filter(|name| matcher.is_match(name))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if info_names.is_empty() {
+        return Ok(None);
+    }
+
+    // 复用流水线的过滤逻辑，保持代理组成员一致
+    let pipeline = NodePipeline {
+        exclude_pattern,
+        ..Default::default()
+    };
+    let output = pipeline.apply(config)?;
+    let changes = output
+        .changes
+        .into_iter()
+        .map(|change| NodeChange {
+            kind: NodeChangeKind::InfoNode,
+            ..change
+        })
+        .collect();
+
+    Ok(Some(InfoNodeOutput {
+        config: output.config,
+        changes,
+        proxies: output.proxies,
+        usage: derive_usage(&info_names),
+    }))
+}
+
+// 从信息节点名称中解析流量与到期时间
+fn derive_usage(names: &[String]) -> Option<SubscriptionUsage> {
+    let amount_regex = Regex::new(r"(?i)(\d+(?:\.\d+)?)\s*([KMGTP]i?B?|B)\b").ok()?;
+    let date_regex = Regex::new(r"(\d{4})\s*[-/.年]\s*(\d{1,2})\s*[-/.月]\s*(\d{1,2})").ok()?;
+
+    let mut used = None;
+    let mut total = None;
+    let mut remaining = None;
+    let mut expire = None;
+
+    for name in names {
+        let amounts: Vec<u64> = amount_regex
+            .captures_iter(name)
+            .filter_map(|caps| parse_bytes(&caps[1], &caps[2]))
+            .collect();
+        let lower = name.to_lowercase();
+
+        match amounts.as_slice() {
+            // “已用/总量”形式，如 10GB / 200GB
+            [first, second, ..] if name.contains('/') || name.contains('|') => {
+                used = used.or(Some(*first));
+                total = total.or(Some(*second));
+            }
+            [amount, ..] => {
+                if name.contains("剩余") || lower.contains("remain") || lower.contains("left") {
+                    remaining = remaining.or(Some(*amount));
+                } else if name.contains("已用") || lower.contains("used") {
+                    used = used.or(Some(*amount));
+                } else if name.contains('总') || name.contains("套餐") || lower.contains("total")
+                {
+                    total = total.or(Some(*amount));
+                } else {
+                    remaining = remaining.or(Some(*amount));
+                }
+            }
+            [] => {}
+        }
+
+        if expire.is_none()
+            && let Some(caps) = date_regex.captures(name)
+        {
+            expire = parse_date(&caps[1], &caps[2], &caps[3]);
+        }
+    }
+
+    // 仅有剩余流量时，以剩余量作为总量、已用为 0，使展示的剩余量一致
+    let (download, total) = match (used, total, remaining) {
+        (Some(used), Some(total), _) => (Some(used), Some(total)),
+        (None, Some(total), Some(remaining)) => {
+            (Some(total.saturating_sub(remaining)), Some(total))
+        }
+        (Some(used), None, Some(remaining)) => (Some(used), Some(used + remaining)),
+        (None, None, Some(remaining)) => (Some(0), Some(remaining)),
+        (used, total, None) => (used, total),
+    };
+
+    if download.is_none() && total.is_none() && expire.is_none() {
+        return None;
+    }
+    Some(SubscriptionUsage {
+        upload: download.map(|_| 0),
+        download,
+        total,
+        expire,
+    })
+}
+
+// 按 1024 进制换算为字节
+fn parse_bytes(value: &str, unit: &str) -> Option<u64> {
+    let value: f64 = value.parse().ok()?;
+    let exponent = match unit.chars().next()?.to_ascii_uppercase() {
+        'B' => 0,
+        'K' => 1,
+        'M' => 2,
+        'G' => 3,
+        'T' => 4,
+        'P' => 5,
+        _ => return None,
+    };
+    Some((value * 1024f64.powi(exponent)) as u64)
+}
+
+// 到期日按本地时区当天 0 点计算
+fn parse_date(year: &str, month: &str, day: &str) -> Option<i64> {
+    let date = NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)?;
+    let datetime = date.and_hms_opt(0, 0, 0)?;
+    Local
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|dt| dt.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{derive_usage, strip_info_nodes};
+
+    #[test]
+    fn strip_info_nodes_and_derive_usage() -> Result<(), String> {
+        let config = r#"
+proxies:
+  - {name: "剩余流量：120GB", type: trojan, server: a.com, port: 443, password: pw}
+  - {name: "套餐到期：2026-12-01", type: trojan, server: a.com, port: 443, password: pw}
+  - {name: "官网 example.com", type: trojan, server: a.com, port: 443, password: pw}
+  - {name: "HK 01", type: trojan, server: b.com, port: 443, password: pw}
+  - {name: "香港 大流量专线", type: trojan, server: c.com, port: 443, password: pw}
+  - {name: "官方优化线路", type: trojan, server: d.com, port: 443, password: pw}
+  - {name: "JP Reset IP 01", type: trojan, server: e.com, port: 443, password: pw}
+  - {name: "🇭🇰 香港 01 | 流量倍率:1.0", type: trojan, server: f.com, port: 443, password: pw}
+  - {name: "日本 01 [流量倍率：0.5]", type: trojan, server: g.com, port: 443, password: pw}
+  - {name: "香港 IPLC 流量:1x", type: trojan, server: h.com, port: 443, password: pw}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: ["剩余流量：120GB", "套餐到期：2026-12-01", "HK 01", "香港 大流量专线"]}
+rules:
+  - MATCH,PROXY
+"#;
+        let output = strip_info_nodes(config, &[])?.ok_or("未识别信息节点")?;
+        assert_eq!(output.changes.len(), 3);
+        assert!(!output.config.contains("剩余流量"));
+        assert!(!output.config.contains("example.com"));
+        // 名称中含关键字的普通节点保留
+        for name in [
+            "HK 01",
+            "香港 大流量专线",
+            "官方优化线路",
+            "JP Reset IP 01",
+            "🇭🇰 香港 01 | 流量倍率:1.0",
+            "日本 01 [流量倍率：0.5]",
+            "香港 IPLC 流量:1x",
+        ] {
+            assert!(output.config.contains(name), "{} 被误删", name);
+        }
+
+        let usage = output.usage.ok_or("未解析出用量")?;
+        assert_eq!(usage.total, Some(120 * 1024 * 1024 * 1024));
+        assert_eq!(usage.download, Some(0));
+        assert!(usage.expire.is_some());
+
+        let usage = derive_usage(&["已用 10GB / 总 200GB".to_string()]).ok_or("未解析出用量")?;
+        assert_eq!(usage.download, Some(10 * 1024 * 1024 * 1024));
+        assert_eq!(usage.total, Some(200 * 1024 * 1024 * 1024));
+
+        assert!(strip_info_nodes("proxies:\n  - {name: HK, type: direct}\n", &[])?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,1118 @@
+// sing-box / Xray JSON 配置转换器：将 outbounds 映射为 mihomo 代理节点。
+// sing-box 的 selector/urltest 与 Xray 的 balancers 映射为代理组。
+
+use super::ProxyParser;
+use super::report::LineOutcome;
+use super::shadowsocks::parse_plugin;
+use crate::atoms::ProxyNode;
+use crate::atoms::proxy_node::ParseError;
+use serde_json::{Value as JsonValue, json};
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_TEST_URL: &str = "https://www.gstatic.com/generate_204";
+const DEFAULT_TEST_INTERVAL: u64 = 300;
+
+// JSON 配置来源
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum JsonConfigKind {
+    SingBox,
+    Xray,
+}
+
+// JSON 配置转换结果
+pub(super) struct JsonConfigConversion {
+    pub proxies: Vec<ProxyNode>,
+    pub groups: Vec<JsonValue>,
+    // 每个代理出站的转换结果
+    pub outcomes: Vec<LineOutcome>,
+}
+
+impl ProxyParser {
+    // 识别 sing-box / Xray JSON 配置。
+    // 支持完整配置（含 outbounds）与单独的 outbounds 数组。
+    pub(super) fn detect_json_config(content: &str) -> Option<(JsonConfigKind, JsonValue)> {
+        let trimmed = content.trim_start();
+        if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+            return None;
+        }
+
+        let value: JsonValue = serde_json::from_str(content).ok()?;
+        let outbounds = match &value {
+            JsonValue::Array(items) => items,
+            JsonValue::Object(map) => map.get("outbounds")?.as_array()?,
+            _ => return None,
+        };
+
+        let kind = if outbounds.iter().any(|o| o.get("protocol").is_some()) {
+            JsonConfigKind::Xray
+        } else if outbounds.iter().any(|o| o.get("type").is_some()) {
+            JsonConfigKind::SingBox
+        } else {
+            return None;
+        };
+
+        Some((kind, value))
+    }
+
+    // 将 JSON 配置转换为代理节点与代理组
+    pub(super) fn convert_json_config(
+        kind: JsonConfigKind,
+        config: &JsonValue,
+    ) -> JsonConfigConversion {
+        match kind {
+            JsonConfigKind::SingBox => convert_singbox(config),
+            JsonConfigKind::Xray => convert_xray(config),
+        }
+    }
+}
+
+// sing-box 转换
+
+fn convert_singbox(config: &JsonValue) -> JsonConfigConversion {
+    let empty = Vec::new();
+    let outbounds = match config {
+        JsonValue::Array(items) => items,
+        _ => config["outbounds"].as_array().unwrap_or(&empty),
+    };
+    // sing-box 1.11+ 将 WireGuard 移至 endpoints
+    let endpoints = config["endpoints"].as_array().unwrap_or(&empty);
+
+    let mut proxies = Vec::new();
+    let mut outcomes = Vec::new();
+    let mut group_outbounds = Vec::new();
+    let mut builtin_targets = HashMap::new();
+
+    for (index, outbound) in outbounds.iter().chain(endpoints).enumerate() {
+        let outbound_type = outbound["type"].as_str().unwrap_or_default();
+        let tag = outbound["tag"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}-{}", outbound_type, index + 1));
+
+        match outbound_type {
+            "selector" | "urltest" => group_outbounds.push(outbound),
+            "direct" => {
+                builtin_targets.insert(tag, "DIRECT");
+            }
+            "block" => {
+                builtin_targets.insert(tag, "REJECT");
+            }
+            "dns" => {}
+            _ => match singbox_outbound_to_proxy(outbound, &tag).and_then(ProxyNode::from_json) {
+                Ok(proxy) => {
+                    outcomes.push(LineOutcome::ok(index + 1, &tag, &proxy));
+                    proxies.push(proxy);
+                }
+                Err(e) => {
+                    log::warn!("跳过无法转换的 sing-box 出站：{} - {}", tag, e);
+                    outcomes.push(LineOutcome::failed(index + 1, &tag, &e));
+                }
+            },
+        }
+    }
+
+    map_builtin_dialers(&mut proxies, &builtin_targets);
+    let groups = build_singbox_groups(&group_outbounds, &proxies, &builtin_targets);
+
+    JsonConfigConversion {
+        proxies,
+        groups,
+        outcomes,
+    }
+}
+
+fn singbox_outbound_to_proxy(outbound: &JsonValue, tag: &str) -> Result<JsonValue, ParseError> {
+    let outbound_type = outbound["type"].as_str().unwrap_or_default();
+
+    if outbound_type == "wireguard" {
+        return singbox_wireguard_to_proxy(outbound, tag);
+    }
+
+    let server = text(&outbound["server"]).ok_or_else(|| ParseError::missing("缺少 server"))?;
+    let port = outbound["server_port"]
+        .as_i64()
+        .ok_or_else(|| ParseError::missing("缺少 server_port"))?;
+
+    let mut proxy = json!({
+        "name": tag,
+        "server": server,
+        "port": port,
+        "udp": text(&outbound["network"]).as_deref() != Some("tcp"),
+    });
+
+    match outbound_type {
+        "shadowsocks" => {
+            proxy["type"] = json!("ss");
+            proxy["cipher"] =
+                json!(text(&outbound["method"]).ok_or_else(|| ParseError::missing("缺少 method"))?);
+            proxy["password"] = json!(text(&outbound["password"]).unwrap_or_default());
+            if let Some(plugin) = text(&outbound["plugin"]) {
+                let plugin = match text(&outbound["plugin_opts"]) {
+                    Some(opts) => format!("{};{}", plugin, opts),
+                    None => plugin,
+                };
+                let (plugin_name, plugin_opts) = parse_plugin(&plugin)?;
+                proxy["plugin"] = json!(plugin_name);
+                proxy["plugin-opts"] = plugin_opts;
+            }
+        }
+        "vmess" | "vless" | "trojan" => {
+            proxy["type"] = json!(outbound_type);
+            match outbound_type {
+                "vmess" => {
+                    proxy["uuid"] = json!(
+                        text(&outbound["uuid"]).ok_or_else(|| ParseError::missing("缺少 uuid"))?
+                    );
+                    proxy["alterId"] = json!(outbound["alter_id"].as_i64().unwrap_or(0));
+                    proxy["cipher"] =
+                        json!(text(&outbound["security"]).unwrap_or_else(|| "auto".to_string()));
+                }
+                "vless" => {
+                    proxy["uuid"] = json!(
+                        text(&outbound["uuid"]).ok_or_else(|| ParseError::missing("缺少 uuid"))?
+                    );
+                    if let Some(flow) = text(&outbound["flow"]) {
+                        proxy["flow"] = json!(flow);
+                    }
+                }
+                _ => {
+                    proxy["password"] = json!(
+                        text(&outbound["password"])
+                            .ok_or_else(|| ParseError::missing("缺少 password"))?
+                    );
+                }
+            }
+            if let Some(packet_encoding) = text(&outbound["packet_encoding"]) {
+                proxy["packet-encoding"] = json!(packet_encoding);
+            }
+
+            let mut params = singbox_tls_params(&outbound["tls"]);
+            let is_tls = params.contains_key("security");
+            let (transport_params, early_data_header) =
+                singbox_transport_params(&outbound["transport"], is_tls);
+            params.extend(transport_params);
+
+            ProxyParser::apply_tls_params(&mut proxy, &params, outbound_type == "trojan");
+            ProxyParser::apply_transport_params(&mut proxy, &params)?;
+            if let Some(header) = early_data_header
+                && proxy["ws-opts"].get("max-early-data").is_some()
+            {
+                proxy["ws-opts"]["early-data-header-name"] = json!(header);
+            }
+        }
+        "hysteria2" => {
+            proxy["type"] = json!("hysteria2");
+            proxy["password"] = json!(text(&outbound["password"]).unwrap_or_default());
+            if let Some(ports) = outbound["server_ports"].as_array() {
+                let ports = ports
+                    .iter()
+                    .filter_map(text)
+                    .map(|range| range.replace(':', "-"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                proxy["ports"] = json!(ports);
+            }
+            if let Some(up) = outbound["up_mbps"].as_i64() {
+                proxy["up"] = json!(up);
+            }
+            if let Some(down) = outbound["down_mbps"].as_i64() {
+                proxy["down"] = json!(down);
+            }
+            if let Some(obfs) = outbound.get("obfs").and_then(|o| text(&o["type"])) {
+                proxy["obfs"] = json!(obfs);
+                if let Some(password) = text(&outbound["obfs"]["password"]) {
+                    proxy["obfs-password"] = json!(password);
+                }
+            }
+            apply_singbox_quic_tls(&mut proxy, &outbound["tls"]);
+        }
+        "hysteria" => {
+            proxy["type"] = json!("hysteria");
+            proxy["auth-str"] = json!(text(&outbound["auth_str"]).unwrap_or_default());
+            proxy["up"] = json!(outbound["up_mbps"].as_i64().unwrap_or(10));
+            proxy["down"] = json!(outbound["down_mbps"].as_i64().unwrap_or(50));
+            if let Some(obfs) = text(&outbound["obfs"]) {
+                proxy["obfs"] = json!(obfs);
+            }
+            apply_singbox_quic_tls(&mut proxy, &outbound["tls"]);
+        }
+        "tuic" => {
+            proxy["type"] = json!("tuic");
+            proxy["uuid"] =
+                json!(text(&outbound["uuid"]).ok_or_else(|| ParseError::missing("缺少 uuid"))?);
+            proxy["password"] = json!(text(&outbound["password"]).unwrap_or_default());
+            if let Some(congestion) = text(&outbound["congestion_control"]) {
+                proxy["congestion-control"] = json!(congestion);
+            }
+            if let Some(relay_mode) = text(&outbound["udp_relay_mode"]) {
+                proxy["udp-relay-mode"] = json!(relay_mode);
+            }
+            if outbound["zero_rtt_handshake"].as_bool() == Some(true) {
+                proxy["reduce-rtt"] = json!(true);
+            }
+            apply_singbox_quic_tls(&mut proxy, &outbound["tls"]);
+        }
+        "anytls" => {
+            proxy["type"] = json!("anytls");
+            proxy["password"] = json!(
+                text(&outbound["password"]).ok_or_else(|| ParseError::missing("缺少 password"))?
+            );
+            apply_singbox_quic_tls(&mut proxy, &outbound["tls"]);
+        }
+        "socks" => {
+            proxy["type"] = json!("socks5");
+            apply_credentials(&mut proxy, outbound, "username", "password");
+        }
+        "http" => {
+            proxy["type"] = json!("http");
+            apply_credentials(&mut proxy, outbound, "username", "password");
+            if outbound["tls"]["enabled"].as_bool() == Some(true) {
+                proxy["tls"] = json!(true);
+            }
+        }
+        "ssh" => {
+            proxy["type"] = json!("ssh");
+            proxy["username"] =
+                json!(text(&outbound["user"]).unwrap_or_else(|| "root".to_string()));
+            if let Some(password) = text(&outbound["password"]) {
+                proxy["password"] = json!(password);
+            }
+            if let Some(private_key) =
+                text(&outbound["private_key"]).or_else(|| text(&outbound["private_key_path"]))
+            {
+                proxy["private-key"] = json!(private_key);
+            }
+            if let Some(host_key) = outbound["host_key"].as_array() {
+                proxy["host-key"] = json!(host_key.iter().filter_map(text).collect::<Vec<_>>());
+            }
+        }
+        other => {
+            return Err(ParseError::unsupported(format!(
+                "不支持的出站类型：{}",
+                other
+            )));
+        }
+    }
+
+    if let Some(detour) = text(&outbound["detour"]) {
+        proxy["dialer-proxy"] = json!(detour);
+    }
+
+    Ok(proxy)
+}
+
+// sing-box TLS 字段转换为分享链接参数
+fn singbox_tls_params(tls: &JsonValue) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    if tls["enabled"].as_bool() != Some(true) {
+        return params;
+    }
+
+    let reality = &tls["reality"];
+    let is_reality = reality["enabled"].as_bool() == Some(true);
+    params.insert(
+        "security".to_string(),
+        if is_reality { "reality" } else { "tls" }.to_string(),
+    );
+
+    insert_text(&mut params, "sni", &tls["server_name"]);
+    insert_text(&mut params, "alpn", &tls["alpn"]);
+    if tls["insecure"].as_bool() == Some(true) {
+        params.insert("allowInsecure".to_string(), "1".to_string());
+    }
+    if tls["utls"]["enabled"].as_bool() == Some(true) {
+        insert_text(&mut params, "fp", &tls["utls"]["fingerprint"]);
+    }
+    if is_reality {
+        insert_text(&mut params, "pbk", &reality["public_key"]);
+        insert_text(&mut params, "sid", &reality["short_id"]);
+    }
+
+    params
+}
+
+// sing-box 传输字段转换为分享链接参数，同时返回早期数据请求头名称
+fn singbox_transport_params(
+    transport: &JsonValue,
+    is_tls: bool,
+) -> (HashMap<String, String>, Option<String>) {
+    let mut params = HashMap::new();
+    let mut early_data_header = None;
+
+    match transport["type"].as_str() {
+        Some("ws") => {
+            params.insert("type".to_string(), "ws".to_string());
+            let path = text(&transport["path"]).unwrap_or_else(|| "/".to_string());
+            let path = match transport["max_early_data"].as_i64() {
+                Some(early_data) if early_data > 0 => {
+                    early_data_header = text(&transport["early_data_header_name"]);
+                    format!("{}?ed={}", path, early_data)
+                }
+                _ => path,
+            };
+            params.insert("path".to_string(), path);
+            insert_text(&mut params, "host", &transport["headers"]["Host"]);
+        }
+        Some("http") => {
+            // 无 TLS 的 HTTP 传输对应 mihomo 的 HTTP 伪装
+            if is_tls {
+                params.insert("type".to_string(), "http".to_string());
+            } else {
+                params.insert("type".to_string(), "tcp".to_string());
+                params.insert("headerType".to_string(), "http".to_string());
+            }
+            insert_text(&mut params, "path", &transport["path"]);
+            insert_text(&mut params, "host", &transport["host"]);
+        }
+        Some("grpc") => {
+            params.insert("type".to_string(), "grpc".to_string());
+            insert_text(&mut params, "serviceName", &transport["service_name"]);
+        }
+        Some("httpupgrade") => {
+            params.insert("type".to_string(), "httpupgrade".to_string());
+            insert_text(&mut params, "path", &transport["path"]);
+            insert_text(&mut params, "host", &transport["host"]);
+        }
+        Some(other) => {
+            params.insert("type".to_string(), other.to_string());
+        }
+        None => {}
+    }
+
+    (params, early_data_header)
+}
+
+// QUIC 系协议（Hysteria/TUIC/AnyTLS）的 TLS 字段
+fn apply_singbox_quic_tls(proxy: &mut JsonValue, tls: &JsonValue) {
+    if let Some(sni) = text(&tls["server_name"]) {
+        proxy["sni"] = json!(sni);
+    }
+    if let Some(alpn) = tls["alpn"].as_array() {
+        proxy["alpn"] = json!(alpn.iter().filter_map(text).collect::<Vec<_>>());
+    }
+    proxy["skip-cert-verify"] = json!(tls["insecure"].as_bool() == Some(true));
+}
+
+// sing-box WireGuard 出站/端点，转换为 wg-quick 配置后复用解析逻辑
+fn singbox_wireguard_to_proxy(outbound: &JsonValue, tag: &str) -> Result<JsonValue, ParseError> {
+    let private_key =
+        text(&outbound["private_key"]).ok_or_else(|| ParseError::missing("缺少 private_key"))?;
+    let addresses = outbound
+        .get("address")
+        .or_else(|| outbound.get("local_address"))
+        .map(list_text)
+        .unwrap_or_default();
+
+    let mut conf = format!(
+        "# Name = {}\n[Interface]\nPrivateKey = {}\n",
+        tag, private_key
+    );
+    if !addresses.is_empty() {
+        conf.push_str(&format!("Address = {}\n", addresses));
+    }
+    if let Some(mtu) = outbound["mtu"].as_i64() {
+        conf.push_str(&format!("MTU = {}\n", mtu));
+    }
+
+    match outbound["peers"].as_array() {
+        Some(peers) => {
+            for peer in peers {
+                let endpoint = format!(
+                    "{}:{}",
+                    text(&peer["address"])
+                        .ok_or_else(|| ParseError::missing("Peer 缺少 address"))?,
+                    peer["port"]
+                        .as_i64()
+                        .ok_or_else(|| ParseError::missing("Peer 缺少 port"))?
+                );
+                push_wireguard_peer(
+                    &mut conf,
+                    &peer["public_key"],
+                    &peer["pre_shared_key"],
+                    &endpoint,
+                    &peer["allowed_ips"],
+                    &peer["reserved"],
+                );
+            }
+        }
+        None => {
+            let endpoint = format!(
+                "{}:{}",
+                text(&outbound["server"]).ok_or_else(|| ParseError::missing("缺少 server"))?,
+                outbound["server_port"]
+                    .as_i64()
+                    .ok_or_else(|| ParseError::missing("缺少 server_port"))?
+            );
+            push_wireguard_peer(
+                &mut conf,
+                &outbound["peer_public_key"],
+                &outbound["pre_shared_key"],
+                &endpoint,
+                &JsonValue::Null,
+                &outbound["reserved"],
+            );
+        }
+    }
+
+    ProxyParser::parse_wireguard_conf(&conf)
+}
+
+// 将 selector/urltest 映射为 select/url-test 代理组
+fn build_singbox_groups(
+    group_outbounds: &[&JsonValue],
+    proxies: &[ProxyNode],
+    builtin_targets: &HashMap<String, &str>,
+) -> Vec<JsonValue> {
+    let mut known_names: HashSet<String> = proxies.iter().map(|p| p.name().to_string()).collect();
+    known_names.extend(
+        group_outbounds
+            .iter()
+            .filter_map(|g| g["tag"].as_str().map(str::to_string)),
+    );
+
+    group_outbounds
+        .iter()
+        .filter_map(|outbound| {
+            let name = outbound["tag"].as_str()?;
+            let members = outbound["outbounds"]
+                .as_array()
+                .map(|items| items.iter().filter_map(text).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let group_type = if outbound["type"].as_str() == Some("urltest") {
+                "url-test"
+            } else {
+                "select"
+            };
+
+            let mut group = json!({
+                "name": name,
+                "type": group_type,
+                "proxies": resolve_members(&members, &known_names, builtin_targets),
+            });
+
+            if group_type == "url-test" {
+                group["url"] =
+                    json!(text(&outbound["url"]).unwrap_or_else(|| DEFAULT_TEST_URL.to_string()));
+                group["interval"] = json!(
+                    outbound["interval"]
+                        .as_str()
+                        .and_then(parse_duration_secs)
+                        .unwrap_or(DEFAULT_TEST_INTERVAL)
+                );
+                if let Some(tolerance) = outbound["tolerance"].as_i64() {
+                    group["tolerance"] = json!(tolerance);
+                }
+            }
+
+            Some(group)
+        })
+        .collect()
+}
+
+// Xray 转换
+
+fn convert_xray(config: &JsonValue) -> JsonConfigConversion {
+    let empty = Vec::new();
+    let outbounds = match config {
+        JsonValue::Array(items) => items,
+        _ => config["outbounds"].as_array().unwrap_or(&empty),
+    };
+
+    let mut proxies = Vec::new();
+    let mut outcomes = Vec::new();
+    let mut builtin_targets = HashMap::new();
+
+    for (index, outbound) in outbounds.iter().enumerate() {
+        let protocol = outbound["protocol"].as_str().unwrap_or_default();
+        if matches!(protocol, "freedom" | "blackhole" | "dns" | "loopback") {
+            if let Some(tag) = outbound["tag"].as_str() {
+                match protocol {
+                    "freedom" => builtin_targets.insert(tag.to_string(), "DIRECT"),
+                    "blackhole" => builtin_targets.insert(tag.to_string(), "REJECT"),
+                    _ => None,
+                };
+            }
+            continue;
+        }
+
+        let tag = outbound["tag"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}-{}", protocol, index + 1));
+
+        match xray_outbound_to_proxy(outbound, &tag).and_then(ProxyNode::from_json) {
+            Ok(proxy) => {
+                outcomes.push(LineOutcome::ok(index + 1, &tag, &proxy));
+                proxies.push(proxy);
+            }
+            Err(e) => {
+                log::warn!("跳过无法转换的 Xray 出站：{} - {}", tag, e);
+                outcomes.push(LineOutcome::failed(index + 1, &tag, &e));
+            }
+        }
+    }
+
+    map_builtin_dialers(&mut proxies, &builtin_targets);
+    let groups = build_xray_balancer_groups(&config["routing"]["balancers"], &proxies);
+
+    JsonConfigConversion {
+        proxies,
+        groups,
+        outcomes,
+    }
+}
+
+fn xray_outbound_to_proxy(outbound: &JsonValue, tag: &str) -> Result<JsonValue, ParseError> {
+    let protocol = outbound["protocol"].as_str().unwrap_or_default();
+    let settings = &outbound["settings"];
+
+    if protocol == "wireguard" {
+        return xray_wireguard_to_proxy(settings, tag);
+    }
+
+    // vnext（VLESS/VMess）或 servers（其他协议）的第一个服务器
+    let server = settings["vnext"]
+        .get(0)
+        .or_else(|| settings["servers"].get(0))
+        .unwrap_or(settings);
+    let address = text(&server["address"]).ok_or_else(|| ParseError::missing("缺少 address"))?;
+    let port = server["port"]
+        .as_i64()
+        .ok_or_else(|| ParseError::missing("缺少 port"))?;
+    let user = server["users"].get(0).unwrap_or(server);
+
+    let mut proxy = json!({
+        "name": tag,
+        "server": address,
+        "port": port,
+        "udp": true,
+    });
+
+    match protocol {
+        "vless" | "vmess" | "trojan" => {
+            proxy["type"] = json!(protocol);
+            match protocol {
+                "vless" => {
+                    proxy["uuid"] =
+                        json!(text(&user["id"]).ok_or_else(|| ParseError::missing("缺少 id"))?);
+                    if let Some(flow) = text(&user["flow"]) {
+                        proxy["flow"] = json!(flow);
+                    }
+                    if let Some(encryption) =
+                        text(&user["encryption"]).filter(|e| e.as_str() != "none")
+                    {
+                        proxy["encryption"] = json!(encryption);
+                    }
+                }
+                "vmess" => {
+                    proxy["uuid"] =
+                        json!(text(&user["id"]).ok_or_else(|| ParseError::missing("缺少 id"))?);
+                    proxy["alterId"] = json!(user["alterId"].as_i64().unwrap_or(0));
+                    proxy["cipher"] =
+                        json!(text(&user["security"]).unwrap_or_else(|| "auto".to_string()));
+                }
+                _ => {
+                    proxy["password"] = json!(
+                        text(&server["password"])
+                            .ok_or_else(|| ParseError::missing("缺少 password"))?
+                    );
+                }
+            }
+
+            let params = xray_stream_params(&outbound["streamSettings"]);
+            ProxyParser::apply_tls_params(&mut proxy, &params, protocol == "trojan");
+            ProxyParser::apply_transport_params(&mut proxy, &params)?;
+        }
+        "shadowsocks" => {
+            proxy["type"] = json!("ss");
+            proxy["cipher"] =
+                json!(text(&server["method"]).ok_or_else(|| ParseError::missing("缺少 method"))?);
+            proxy["password"] = json!(text(&server["password"]).unwrap_or_default());
+        }
+        "socks" | "http" => {
+            proxy["type"] = json!(if protocol == "socks" {
+                "socks5"
+            } else {
+                "http"
+            });
+            apply_credentials(&mut proxy, user, "user", "pass");
+        }
+        other => {
+            return Err(ParseError::unsupported(format!(
+                "不支持的出站协议：{}",
+                other
+            )));
+        }
+    }
+
+    if let Some(dialer_proxy) = text(&outbound["proxySettings"]["tag"]) {
+        proxy["dialer-proxy"] = json!(dialer_proxy);
+    }
+
+    Ok(proxy)
+}
+
+// Xray streamSettings 转换为分享链接参数
+fn xray_stream_params(stream: &JsonValue) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+
+    let network = match stream["network"].as_str().unwrap_or("tcp") {
+        "raw" => "tcp",
+        "h2" => "http",
+        "splithttp" => "xhttp",
+        other => other,
+    };
+    params.insert("type".to_string(), network.to_string());
+
+    let security = stream["security"].as_str().unwrap_or("none");
+    params.insert("security".to_string(), security.to_string());
+
+    let tls = match security {
+        "reality" => &stream["realitySettings"],
+        _ => &stream["tlsSettings"],
+    };
+    insert_text(&mut params, "sni", &tls["serverName"]);
+    insert_text(&mut params, "fp", &tls["fingerprint"]);
+    insert_text(&mut params, "alpn", &tls["alpn"]);
+    insert_text(&mut params, "pbk", &tls["publicKey"]);
+    insert_text(&mut params, "sid", &tls["shortId"]);
+    if tls["allowInsecure"].as_bool() == Some(true) {
+        params.insert("allowInsecure".to_string(), "1".to_string());
+    }
+
+    match network {
+        "ws" => {
+            let ws = &stream["wsSettings"];
+            insert_text(&mut params, "path", &ws["path"]);
+            insert_text(&mut params, "host", &ws["host"]);
+            insert_text(&mut params, "host", &ws["headers"]["Host"]);
+        }
+        "grpc" => {
+            insert_text(
+                &mut params,
+                "serviceName",
+                &stream["grpcSettings"]["serviceName"],
+            );
+        }
+        "http" => {
+            let http = &stream["httpSettings"];
+            insert_text(&mut params, "path", &http["path"]);
+            insert_text(&mut params, "host", &http["host"]);
+        }
+        "httpupgrade" => {
+            let upgrade = &stream["httpupgradeSettings"];
+            insert_text(&mut params, "path", &upgrade["path"]);
+            insert_text(&mut params, "host", &upgrade["host"]);
+        }
+        "xhttp" => {
+            let xhttp = stream
+                .get("xhttpSettings")
+                .unwrap_or(&stream["splithttpSettings"]);
+            insert_text(&mut params, "path", &xhttp["path"]);
+            insert_text(&mut params, "host", &xhttp["host"]);
+            insert_text(&mut params, "mode", &xhttp["mode"]);
+        }
+        "tcp" => {
+            let header = stream
+                .get("tcpSettings")
+                .unwrap_or(&stream["rawSettings"])
+                .get("header")
+                .unwrap_or(&JsonValue::Null);
+            if header["type"].as_str() == Some("http") {
+                params.insert("headerType".to_string(), "http".to_string());
+                insert_text(&mut params, "path", &header["request"]["path"]);
+                insert_text(&mut params, "host", &header["request"]["headers"]["Host"]);
+            }
+        }
+        _ => {}
+    }
+
+    params
+}
+
+// Xray WireGuard 出站，转换为 wg-quick 配置后复用解析逻辑
+fn xray_wireguard_to_proxy(settings: &JsonValue, tag: &str) -> Result<JsonValue, ParseError> {
+    let private_key =
+        text(&settings["secretKey"]).ok_or_else(|| ParseError::missing("缺少 secretKey"))?;
+
+    let mut conf = format!(
+        "# Name = {}\n[Interface]\nPrivateKey = {}\n",
+        tag, private_key
+    );
+    let addresses = list_text(&settings["address"]);
+    if !addresses.is_empty() {
+        conf.push_str(&format!("Address = {}\n", addresses));
+    }
+    if let Some(mtu) = settings["mtu"].as_i64() {
+        conf.push_str(&format!("MTU = {}\n", mtu));
+    }
+
+    for peer in settings["peers"]
+        .as_array()
+        .ok_or_else(|| ParseError::missing("缺少 peers"))?
+    {
+        push_wireguard_peer(
+            &mut conf,
+            &peer["publicKey"],
+            &peer["preSharedKey"],
+            &text(&peer["endpoint"]).ok_or_else(|| ParseError::missing("Peer 缺少 endpoint"))?,
+            &peer["allowedIPs"],
+            &settings["reserved"],
+        );
+    }
+
+    ProxyParser::parse_wireguard_conf(&conf)
+}
+
+// 将 routing.balancers 映射为代理组（leastPing/leastLoad → url-test，其余 → load-balance）
+fn build_xray_balancer_groups(balancers: &JsonValue, proxies: &[ProxyNode]) -> Vec<JsonValue> {
+    let Some(balancers) = balancers.as_array() else {
+        return Vec::new();
+    };
+
+    balancers
+        .iter()
+        .filter_map(|balancer| {
+            let name = balancer["tag"].as_str()?;
+            let prefixes = balancer["selector"]
+                .as_array()
+                .map(|items| items.iter().filter_map(text).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let members = proxies
+                .iter()
+                .map(ProxyNode::name)
+                .filter(|proxy_name| prefixes.iter().any(|p| proxy_name.starts_with(p.as_str())))
+                .collect::<Vec<_>>();
+            if members.is_empty() {
+                return None;
+            }
+
+            let strategy = balancer["strategy"]["type"].as_str().unwrap_or("random");
+            let mut group = json!({
+                "name": name,
+                "proxies": members,
+                "url": DEFAULT_TEST_URL,
+                "interval": DEFAULT_TEST_INTERVAL,
+            });
+            if matches!(strategy, "leastPing" | "leastLoad") {
+                group["type"] = json!("url-test");
+            } else {
+                group["type"] = json!("load-balance");
+                group["strategy"] = json!("round-robin");
+            }
+
+            Some(group)
+        })
+        .collect()
+}
+
+// 公共工具
+
+// 前置出站为 direct/freedom 时等同直连，移除 dialer-proxy；block/blackhole 映射为 REJECT。
+// 其余引用在节点改名后统一校验
+fn map_builtin_dialers(proxies: &mut [ProxyNode], builtin_targets: &HashMap<String, &str>) {
+    for proxy in proxies {
+        let common = proxy.common_mut();
+        let Some(target) = common
+            .dialer_proxy
+            .as_ref()
+            .and_then(|dialer| builtin_targets.get(dialer))
+        else {
+            continue;
+        };
+        common.dialer_proxy = (*target != "DIRECT").then(|| target.to_string());
+    }
+}
+
+// 过滤代理组成员：保留已知节点/代理组，direct/block 映射为内置策略
+fn resolve_members(
+    members: &[String],
+    known_names: &HashSet<String>,
+    builtin_targets: &HashMap<String, &str>,
+) -> Vec<String> {
+    let mut resolved: Vec<String> = members
+        .iter()
+        .filter_map(|member| {
+            if known_names.contains(member) {
+                Some(member.clone())
+            } else {
+                builtin_targets.get(member).map(|b| b.to_string())
+            }
+        })
+        .collect();
+
+    // mihomo 不允许空代理组
+    if resolved.is_empty() {
+        resolved.push("DIRECT".to_string());
+    }
+
+    resolved
+}
+
+fn push_wireguard_peer(
+    conf: &mut String,
+    public_key: &JsonValue,
+    pre_shared_key: &JsonValue,
+    endpoint: &str,
+    allowed_ips: &JsonValue,
+    reserved: &JsonValue,
+) {
+    conf.push_str("\n[Peer]\n");
+    if let Some(public_key) = text(public_key) {
+        conf.push_str(&format!("PublicKey = {}\n", public_key));
+    }
+    if let Some(psk) = text(pre_shared_key) {
+        conf.push_str(&format!("PresharedKey = {}\n", psk));
+    }
+    conf.push_str(&format!("Endpoint = {}\n", endpoint));
+    let allowed_ips = list_text(allowed_ips);
+    if !allowed_ips.is_empty() {
+        conf.push_str(&format!("AllowedIPs = {}\n", allowed_ips));
+    }
+    let reserved = list_text(reserved);
+    if !reserved.is_empty() {
+        conf.push_str(&format!("Reserved = {}\n", reserved));
+    }
+}
+
+fn apply_credentials(proxy: &mut JsonValue, source: &JsonValue, user_key: &str, pass_key: &str) {
+    if let Some(username) = text(&source[user_key]) {
+        proxy["username"] = json!(username);
+    }
+    if let Some(password) = text(&source[pass_key]) {
+        proxy["password"] = json!(password);
+    }
+}
+
+// 解析 Go 风格时长（如 3m、1m30s、1h）为秒
+fn parse_duration_secs(value: &str) -> Option<u64> {
+    let mut total = 0u64;
+    let mut number = String::new();
+
+    for c in value.trim().chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let amount = number.parse::<u64>().ok()?;
+        number.clear();
+        total += match c {
+            'h' => amount * 3600,
+            'm' => amount * 60,
+            's' => amount,
+            _ => return None,
+        };
+    }
+
+    if !number.is_empty() {
+        total += number.parse::<u64>().ok()?;
+    }
+
+    Some(total)
+}
+
+// 读取字符串或数字字段，空字符串视为缺失
+fn text(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) if !s.is_empty() => Some(s.clone()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+// 将数组或字符串字段拼接为逗号分隔字符串
+fn list_text(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Array(items) => items.iter().filter_map(text).collect::<Vec<_>>().join(","),
+        other => text(other).unwrap_or_default(),
+    }
+}
+
+fn insert_text(params: &mut HashMap<String, String>, key: &str, value: &JsonValue) {
+    let value = list_text(value);
+    if !value.is_empty() {
+        params.insert(key.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonConfigKind, ProxyParser, parse_duration_secs};
+    use crate::atoms::ProxyNode;
+    use crate::atoms::proxy_parser::ParseOptions;
+    use crate::atoms::proxy_parser::report::{LineStatus, NodeChangeKind};
+    use serde_json::{Value as JsonValue, json};
+
+    const UUID: &str = "b831381d-6324-4d53-ad4f-8cda48b30811";
+
+    fn to_json(proxies: &[ProxyNode]) -> Result<Vec<JsonValue>, String> {
+        proxies.iter().map(ProxyNode::to_json).collect()
+    }
+
+    #[test]
+    fn convert_singbox_outbounds_and_groups() -> Result<(), String> {
+        let config = json!({
+            "outbounds": [
+                {"type": "selector", "tag": "select", "outbounds": ["auto", "vless-out", "direct"]},
+                {"type": "urltest", "tag": "auto", "outbounds": ["vless-out", "hy2-out", "missing"], "interval": "1m30s", "tolerance": 50},
+                {
+                    "type": "vless", "tag": "vless-out", "server": "example.com", "server_port": 443,
+                    "uuid": UUID, "flow": "xtls-rprx-vision",
+                    "tls": {
+                        "enabled": true, "server_name": "www.apple.com",
+                        "utls": {"enabled": true, "fingerprint": "chrome"},
+                        "reality": {"enabled": true, "public_key": "key", "short_id": "ab"}
+                    },
+                    "transport": {"type": "ws", "path": "/ws", "headers": {"Host": "cdn.com"}}
+                },
+                {"type": "hysteria2", "tag": "hy2-out", "server": "1.2.3.4", "server_port": 443, "password": "pw", "server_ports": ["20000:30000"]},
+                {"type": "shadowsocks", "tag": "ss-out", "server": "1.2.3.4", "server_port": 8388, "method": "aes-128-gcm", "password": "pw", "plugin": "obfs-local", "plugin_opts": "obfs=http;obfs-host=a.com"},
+                {"type": "vmess", "tag": "bad", "server": "1.2.3.4", "server_port": 443, "uuid": UUID, "transport": {"type": "quic"}},
+                {"type": "direct", "tag": "direct"}
+            ]
+        });
+
+        let (kind, config) =
+            ProxyParser::detect_json_config(&config.to_string()).ok_or("未识别 sing-box 配置")?;
+        assert_eq!(kind, JsonConfigKind::SingBox);
+
+        let conversion = ProxyParser::convert_json_config(kind, &config);
+        assert_eq!(conversion.proxies.len(), 3);
+        let rejected: Vec<_> = conversion
+            .outcomes
+            .iter()
+            .filter(|o| o.status != LineStatus::Ok)
+            .collect();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].preview, "bad");
+        assert_eq!(rejected[0].status, LineStatus::UnsupportedScheme);
+
+        let proxies = to_json(&conversion.proxies)?;
+        let vless = &proxies[0];
+        assert_eq!(vless["servername"], "www.apple.com");
+        assert_eq!(vless["reality-opts"]["short-id"], "ab");
+        assert_eq!(vless["ws-opts"]["headers"]["Host"], "cdn.com");
+        assert_eq!(proxies[1]["ports"], "20000-30000");
+        assert_eq!(proxies[2]["plugin-opts"]["host"], "a.com");
+
+        assert_eq!(conversion.groups[0]["type"], "select");
+        assert_eq!(
+            conversion.groups[0]["proxies"],
+            json!(["auto", "vless-out", "DIRECT"])
+        );
+        assert_eq!(conversion.groups[1]["type"], "url-test");
+        assert_eq!(conversion.groups[1]["interval"], 90);
+        assert_eq!(
+            conversion.groups[1]["proxies"],
+            json!(["vless-out", "hy2-out"])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn convert_xray_outbounds_and_balancers() -> Result<(), String> {
+        let config = json!({
+            "outbounds": [
+                {
+                    "tag": "proxy-hk", "protocol": "vless",
+                    "settings": {"vnext": [{"address": "hk.example.com", "port": 443, "users": [{"id": UUID, "encryption": "none"}]}]},
+                    "streamSettings": {
+                        "network": "grpc", "security": "tls",
+                        "tlsSettings": {"serverName": "hk.example.com", "fingerprint": "chrome"},
+                        "grpcSettings": {"serviceName": "svc"}
+                    }
+                },
+                {
+                    "tag": "proxy-jp", "protocol": "trojan",
+                    "settings": {"servers": [{"address": "jp.example.com", "port": 443, "password": "pw"}]}
+                },
+                {"tag": "direct", "protocol": "freedom"}
+            ],
+            "routing": {"balancers": [{"tag": "balance", "selector": ["proxy-"], "strategy": {"type": "leastPing"}}]}
+        });
+
+        let (kind, config) =
+            ProxyParser::detect_json_config(&config.to_string()).ok_or("未识别 Xray 配置")?;
+        assert_eq!(kind, JsonConfigKind::Xray);
+
+        let conversion = ProxyParser::convert_json_config(kind, &config);
+        let proxies = to_json(&conversion.proxies)?;
+        assert_eq!(proxies.len(), 2);
+        assert_eq!(proxies[0]["grpc-opts"]["grpc-service-name"], "svc");
+        assert_eq!(proxies[0]["tls"], true);
+        assert_eq!(proxies[1]["password"], "pw");
+        assert_eq!(conversion.groups[0]["type"], "url-test");
+        assert_eq!(
+            conversion.groups[0]["proxies"],
+            json!(["proxy-hk", "proxy-jp"])
+        );
+
+        let yaml = ProxyParser::parse_subscription(&config.to_string())?;
+        assert!(yaml.contains("MATCH,balance"));
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_detour_after_renaming() -> Result<(), String> {
+        let trojan = |tag: &str, detour: &str| json!({"type": "trojan", "tag": tag, "server": "a.com", "server_port": 443, "password": tag, "detour": detour});
+        let config = json!({
+            "outbounds": [
+                {"type": "selector", "tag": "select", "outbounds": ["AUTO", "chained", "broken", "broken-child"]},
+                trojan("AUTO", "direct"),
+                trojan("chained", "AUTO"),
+                trojan("via-group", "select"),
+                trojan("broken", "missing"),
+                trojan("broken-child", "broken"),
+                {"type": "direct", "tag": "direct"}
+            ]
+        });
+
+        let (result, report) = ProxyParser::parse_subscription_with_report(
+            &config.to_string(),
+            &ParseOptions::default(),
+        );
+        let root: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(&result?).map_err(|e| e.to_string())?;
+
+        // 与默认代理组重名的节点改名后，引用同步更新
+        let proxies = root["proxies"].as_sequence().ok_or("缺少 proxies")?;
+        let dialers: Vec<_> = proxies
+            .iter()
+            .map(|p| (p["name"].as_str(), p["dialer-proxy"].as_str()))
+            .collect();
+        assert_eq!(
+            dialers,
+            vec![
+                (Some("AUTO 2"), None),
+                (Some("chained"), Some("AUTO 2")),
+                (Some("via-group"), Some("select")),
+            ]
+        );
+        assert_eq!(
+            root["proxy-groups"][0]["proxies"],
+            serde_yaml_ng::to_value(["AUTO 2", "chained"]).map_err(|e| e.to_string())?
+        );
+
+        let dangling: Vec<_> = report
+            .node_changes
+            .iter()
+            .filter(|c| c.kind == NodeChangeKind::DanglingDialer)
+            .map(|c| (c.original_name.as_str(), c.new_name.as_str()))
+            .collect();
+        assert_eq!(
+            dangling,
+            vec![("broken", "missing"), ("broken-child", "broken")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_go_durations() {
+        assert_eq!(parse_duration_secs("3m"), Some(180));
+        assert_eq!(parse_duration_secs("1h30s"), Some(3630));
+        assert_eq!(parse_duration_secs("1d"), None);
+    }
+}
@@ -0,0 +1,783 @@
+// iOS 客户端代理行解析器：支持 Surge、Loon 与 Quantumult X 的节点行格式。
+// Surge/Loon：名称 = 类型, 服务器, 端口, 位置参数…, 键=值…
+// Quantumult X：类型=服务器:端口, 键=值…, tag=名称
+
+use super::ProxyParser;
+use super::shadowsocks::{parse_plugin, validate_ss2022_key};
+use crate::atoms::proxy_node::ParseError;
+use serde_json::{Value as JsonValue, json};
+use std::collections::HashMap;
+
+// Surge/Loon 节点类型（小写）
+const SURGE_LOON_TYPES: &[&str] = &[
+    "ss",
+    "shadowsocks",
+    "ssr",
+    "shadowsocksr",
+    "vmess",
+    "vless",
+    "trojan",
+    "http",
+    "https",
+    "socks5",
+    "socks5-tls",
+    "snell",
+    "tuic",
+    "tuic-v5",
+    "hysteria2",
+    "anytls",
+    // 以下类型可识别但无法映射为独立节点
+    "direct",
+    "reject",
+    "reject-tinygif",
+    "wireguard",
+    "external",
+];
+
+// Quantumult X 节点类型
+const QUANTUMULT_X_TYPES: &[&str] = &["shadowsocks", "vmess", "vless", "trojan", "http", "socks5"];
+
+// 节点行格式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum LineFormat {
+    SurgeLoon,
+    QuantumultX,
+}
+
+// 行内参数：位置参数与键值参数
+struct LineFields {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl LineFields {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.options
+            .get(key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+
+    fn is_true(&self, key: &str) -> bool {
+        self.get(key)
+            .is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+    }
+
+    fn positional(&self, index: usize) -> Option<&str> {
+        self.positional
+            .get(index)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+}
+
+impl ProxyParser {
+    // 识别节点行格式（无法识别时返回 None）
+    pub(super) fn detect_line_format(line: &str) -> Option<LineFormat> {
+        let (left, right) = line.split_once('=')?;
+        let first_field = right.split(',').next().unwrap_or_default().trim();
+
+        if SURGE_LOON_TYPES.contains(&first_field.to_ascii_lowercase().as_str()) {
+            return Some(LineFormat::SurgeLoon);
+        }
+
+        let left = left.trim().to_ascii_lowercase();
+        if QUANTUMULT_X_TYPES.contains(&left.as_str()) && first_field.contains(':') {
+            return Some(LineFormat::QuantumultX);
+        }
+
+        None
+    }
+
+    // 解析 Surge/Loon/Quantumult X 节点行
+    pub(super) fn parse_line_proxy(
+        line: &str,
+        format: LineFormat,
+    ) -> Result<JsonValue, ParseError> {
+        match format {
+            LineFormat::SurgeLoon => parse_surge_loon(line),
+            LineFormat::QuantumultX => parse_quantumult_x(line),
+        }
+    }
+}
+
+// Surge / Loon
+
+fn parse_surge_loon(line: &str) -> Result<JsonValue, ParseError> {
+    let (name, body) = line
+        .split_once('=')
+        .ok_or_else(|| ParseError::missing("节点行缺少 ="))?;
+    let name = unquote(name.trim());
+
+    let mut items = split_fields(body).into_iter();
+    let proxy_type = items.next().unwrap_or_default().to_ascii_lowercase();
+
+    match proxy_type.as_str() {
+        "direct" | "reject" | "reject-tinygif" => {
+            return Err(ParseError::unsupported(format!(
+                "{} 为内置策略，无法转换为代理节点",
+                proxy_type
+            )));
+        }
+        "wireguard" => {
+            return Err(ParseError::unsupported(
+                "Surge WireGuard 节点依赖 [WireGuard] 段，无法单行转换",
+            ));
+        }
+        "external" => return Err(ParseError::unsupported("不支持 external 外部代理程序")),
+        _ => {}
+    }
+
+    let server = items
+        .next()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| ParseError::missing("缺少服务器地址"))?;
+    let port = items
+        .next()
+        .ok_or_else(|| ParseError::missing("缺少端口"))?
+        .parse::<i64>()
+        .map_err(|_| "端口解析失败")?;
+    let fields = collect_fields(items);
+
+    let mut proxy = json!({
+        "name": name,
+        "server": server.trim_start_matches('[').trim_end_matches(']'),
+        "port": port,
+    });
+
+    match proxy_type.as_str() {
+        "ss" | "shadowsocks" => {
+            // Surge 使用 encrypt-method/password，Loon 使用位置参数
+            let method = fields
+                .get("encrypt-method")
+                .or_else(|| fields.positional(0))
+                .ok_or_else(|| ParseError::missing("缺少加密方式"))?;
+            let password = fields
+                .get("password")
+                .or_else(|| fields.positional(1))
+                .ok_or_else(|| ParseError::missing("缺少密码"))?;
+            validate_ss2022_key(method, password)?;
+
+            proxy["type"] = json!("ss");
+            proxy["cipher"] = json!(method);
+            proxy["password"] = json!(password);
+            apply_ss_obfs(&mut proxy, &fields)?;
+        }
+        "ssr" | "shadowsocksr" => {
+            proxy["type"] = json!("ssr");
+            proxy["cipher"] = json!(
+                fields
+                    .positional(0)
+                    .ok_or_else(|| ParseError::missing("缺少加密方式"))?
+            );
+            proxy["password"] = json!(
+                fields
+                    .positional(1)
+                    .ok_or_else(|| ParseError::missing("缺少密码"))?
+            );
+            proxy["protocol"] = json!(fields.get("protocol").unwrap_or("origin"));
+            proxy["obfs"] = json!(fields.get("obfs").unwrap_or("plain"));
+            if let Some(param) = fields.get("protocol-param") {
+                proxy["protocol-param"] = json!(param);
+            }
+            if let Some(param) = fields.get("obfs-param") {
+                proxy["obfs-param"] = json!(param);
+            }
+        }
+        "vmess" => {
+            // Surge：username=uuid；Loon：加密方式, "uuid"
+            let (cipher, uuid) = match fields.get("username") {
+                Some(uuid) => (fields.get("encrypt-method").unwrap_or("auto"), uuid),
+                None => (
+                    fields.positional(0).unwrap_or("auto"),
+                    fields
+                        .positional(1)
+                        .ok_or_else(|| ParseError::missing("缺少 UUID"))?,
+                ),
+            };
+            proxy["type"] = json!("vmess");
+            proxy["uuid"] = json!(uuid);
+            proxy["alterId"] = json!(
+                fields
+                    .get("alterId")
+                    .unwrap_or("0")
+                    .parse::<i64>()
+                    .unwrap_or(0)
+            );
+            proxy["cipher"] = json!(cipher);
+            apply_surge_loon_stream(&mut proxy, &fields, false)?;
+        }
+        "vless" => {
+            proxy["type"] = json!("vless");
+            proxy["uuid"] = json!(
+                fields
+                    .positional(0)
+                    .ok_or_else(|| ParseError::missing("缺少 UUID"))?
+            );
+            if let Some(flow) = fields.get("flow") {
+                proxy["flow"] = json!(flow);
+            }
+            apply_surge_loon_stream(&mut proxy, &fields, false)?;
+        }
+        "trojan" => {
+            let password = fields
+                .get("password")
+                .or_else(|| fields.positional(0))
+                .ok_or_else(|| ParseError::missing("缺少密码"))?;
+            proxy["type"] = json!("trojan");
+            proxy["password"] = json!(password);
+            apply_surge_loon_stream(&mut proxy, &fields, true)?;
+        }
+        "http" | "https" | "socks5" | "socks5-tls" => {
+            let is_socks = proxy_type.starts_with("socks5");
+            proxy["type"] = json!(if is_socks { "socks5" } else { "http" });
+            let username = fields.get("username").or_else(|| fields.positional(0));
+            let password = fields.get("password").or_else(|| fields.positional(1));
+            if let Some(username) = username {
+                proxy["username"] = json!(username);
+            }
+            if let Some(password) = password {
+                proxy["password"] = json!(password);
+            }
+            if proxy_type == "https" || proxy_type == "socks5-tls" || fields.is_true("over-tls") {
+                proxy["tls"] = json!(true);
+                apply_sni(&mut proxy, &fields, "sni");
+            }
+            proxy["skip-cert-verify"] = json!(fields.is_true("skip-cert-verify"));
+        }
+        "snell" => {
+            let version = fields
+                .get("version")
+                .map(|v| v.parse::<i64>().map_err(|_| "version 解析失败"))
+                .transpose()?
+                .unwrap_or(1);
+            proxy["type"] = json!("snell");
+            proxy["psk"] = json!(
+                fields
+                    .get("psk")
+                    .ok_or_else(|| ParseError::missing("缺少 psk"))?
+            );
+            proxy["version"] = json!(version);
+            if version >= 3 {
+                proxy["udp"] = json!(true);
+            }
+            if let Some(obfs) = fields.get("obfs").filter(|o| *o != "none") {
+                let mut obfs_opts = json!({ "mode": obfs });
+                if let Some(host) = fields.get("obfs-host") {
+                    obfs_opts["host"] = json!(host);
+                }
+                proxy["obfs-opts"] = obfs_opts;
+            }
+        }
+        "tuic" | "tuic-v5" => {
+            proxy["type"] = json!("tuic");
+            match fields.get("uuid") {
+                Some(uuid) => {
+                    proxy["uuid"] = json!(uuid);
+                    proxy["password"] = json!(fields.get("password").unwrap_or_default());
+                }
+                None => {
+                    proxy["token"] = json!(
+                        fields
+                            .get("token")
+                            .ok_or_else(|| ParseError::missing("缺少 token 或 uuid"))?
+                    )
+                }
+            }
+            apply_sni(&mut proxy, &fields, "sni");
+            apply_alpn(&mut proxy, &fields);
+            proxy["skip-cert-verify"] = json!(fields.is_true("skip-cert-verify"));
+        }
+        "hysteria2" => {
+            let password = fields
+                .get("password")
+                .or_else(|| fields.positional(0))
+                .ok_or_else(|| ParseError::missing("缺少密码"))?;
+            proxy["type"] = json!("hysteria2");
+            proxy["password"] = json!(password);
+            if let Some(down) = fields.get("download-bandwidth") {
+                proxy["down"] = json!(down);
+            }
+            apply_sni(&mut proxy, &fields, "sni");
+            proxy["skip-cert-verify"] = json!(fields.is_true("skip-cert-verify"));
+        }
+        "anytls" => {
+            let password = fields
+                .get("password")
+                .or_else(|| fields.positional(0))
+                .ok_or_else(|| ParseError::missing("缺少密码"))?;
+            proxy["type"] = json!("anytls");
+            proxy["password"] = json!(password);
+            apply_sni(&mut proxy, &fields, "sni");
+            proxy["skip-cert-verify"] = json!(fields.is_true("skip-cert-verify"));
+        }
+        other => {
+            return Err(ParseError::unsupported(format!(
+                "不支持的节点类型：{}",
+                other
+            )));
+        }
+    }
+
+    // Surge 使用 udp-relay，Loon 使用 udp
+    if fields.is_true("udp-relay") || fields.is_true("udp") {
+        proxy["udp"] = json!(true);
+    }
+
+    if let Some(underlying) = fields.get("underlying-proxy") {
+        proxy["dialer-proxy"] = json!(underlying);
+    }
+
+    Ok(proxy)
+}
+
+// Surge（ws/ws-path/ws-headers/tls/sni）与 Loon（transport/path/host/over-tls/tls-name）的传输参数
+fn apply_surge_loon_stream(
+    proxy: &mut JsonValue,
+    fields: &LineFields,
+    implicit_tls: bool,
+) -> Result<(), ParseError> {
+    let mut params = HashMap::new();
+
+    let network = match fields.get("transport") {
+        Some(transport) => transport.to_string(),
+        None if fields.is_true("ws") => "ws".to_string(),
+        None => "tcp".to_string(),
+    };
+    params.insert("type".to_string(), network);
+
+    if let Some(path) = fields.get("path").or_else(|| fields.get("ws-path")) {
+        params.insert("path".to_string(), path.to_string());
+    }
+    let host = fields
+        .get("host")
+        .map(str::to_string)
+        .or_else(|| fields.get("ws-headers").and_then(surge_header_host));
+    if let Some(host) = host {
+        params.insert("host".to_string(), host);
+    }
+    if let Some(service_name) = fields.get("grpc-service-name") {
+        params.insert("serviceName".to_string(), service_name.to_string());
+    }
+
+    if fields.is_true("tls") || fields.is_true("over-tls") {
+        params.insert("security".to_string(), "tls".to_string());
+    }
+    if let Some(public_key) = fields.get("public-key") {
+        params.insert("security".to_string(), "reality".to_string());
+        params.insert("pbk".to_string(), public_key.to_string());
+        if let Some(short_id) = fields.get("short-id") {
+            params.insert("sid".to_string(), short_id.to_string());
+        }
+    }
+    if let Some(sni) = fields.get("sni").or_else(|| fields.get("tls-name")) {
+        params.insert("sni".to_string(), sni.to_string());
+    }
+    if let Some(alpn) = fields.get("alpn") {
+        params.insert("alpn".to_string(), alpn.to_string());
+    }
+    if fields.is_true("skip-cert-verify") {
+        params.insert("allowInsecure".to_string(), "1".to_string());
+    }
+
+    ProxyParser::apply_tls_params(proxy, &params, implicit_tls);
+    ProxyParser::apply_transport_params(proxy, &params)
+}
+
+// 从 Surge ws-headers（Host:a.com|User-Agent:x）中提取 Host
+fn surge_header_host(headers: &str) -> Option<String> {
+    headers.split('|').find_map(|header| {
+        let (key, value) = header.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case("host")
+            .then(|| unquote(value.trim()))
+    })
+}
+
+// Surge/Loon 的 obfs（simple-obfs）参数转换为 SS 插件
+fn apply_ss_obfs(proxy: &mut JsonValue, fields: &LineFields) -> Result<(), ParseError> {
+    let Some(obfs) = fields.get("obfs").or_else(|| fields.get("obfs-name")) else {
+        return Ok(());
+    };
+
+    let mut plugin = format!("obfs-local;obfs={}", obfs);
+    if let Some(host) = fields.get("obfs-host") {
+        plugin.push_str(&format!(";obfs-host={}", host));
+    }
+    let (plugin_name, plugin_opts) = parse_plugin(&plugin)?;
+    proxy["plugin"] = json!(plugin_name);
+    proxy["plugin-opts"] = plugin_opts;
+    Ok(())
+}
+
+// Quantumult X
+
+fn parse_quantumult_x(line: &str) -> Result<JsonValue, ParseError> {
+    let (proxy_type, body) = line
+        .split_once('=')
+        .ok_or_else(|| ParseError::missing("节点行缺少 ="))?;
+    let proxy_type = proxy_type.trim().to_ascii_lowercase();
+
+    let mut items = split_fields(body).into_iter();
+    let endpoint = items
+        .next()
+        .ok_or_else(|| ParseError::missing("缺少服务器地址"))?;
+    let (server, port) = endpoint
+        .rsplit_once(':')
+        .ok_or_else(|| ParseError::missing("缺少端口"))?;
+    let port = port.parse::<i64>().map_err(|_| "端口解析失败")?;
+    let fields = collect_fields(items);
+
+    let name = fields
+        .get("tag")
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}:{}", server, port));
+
+    let mut proxy = json!({
+        "name": name,
+        "server": server.trim_start_matches('[').trim_end_matches(']'),
+        "port": port,
+        "udp": fields.is_true("udp-relay"),
+    });
+
+    match proxy_type.as_str() {
+        "shadowsocks" => {
+            let method = fields
+                .get("method")
+                .ok_or_else(|| ParseError::missing("缺少 method"))?;
+            let password = fields
+                .get("password")
+                .ok_or_else(|| ParseError::missing("缺少 password"))?;
+
+            // 含 ssr-protocol 时为 ShadowsocksR
+            if let Some(protocol) = fields.get("ssr-protocol") {
+                proxy["type"] = json!("ssr");
+                proxy["cipher"] = json!(method);
+                proxy["password"] = json!(password);
+                proxy["protocol"] = json!(protocol);
+                proxy["obfs"] = json!(fields.get("obfs").unwrap_or("plain"));
+                if let Some(param) = fields.get("ssr-protocol-param") {
+                    proxy["protocol-param"] = json!(param);
+                }
+                if let Some(host) = fields.get("obfs-host") {
+                    proxy["obfs-param"] = json!(host);
+                }
+                return Ok(proxy);
+            }
+
+            validate_ss2022_key(method, password)?;
+            proxy["type"] = json!("ss");
+            proxy["cipher"] = json!(method);
+            proxy["password"] = json!(password);
+
+            match fields.get("obfs") {
+                Some("http") | Some("tls") => apply_ss_obfs(&mut proxy, &fields)?,
+                Some(obfs @ ("ws" | "wss")) => {
+                    let mut plugin_opts = json!({
+                        "mode": "websocket",
+                        "path": fields.get("obfs-uri").unwrap_or("/"),
+                    });
+                    if let Some(host) = fields.get("obfs-host") {
+                        plugin_opts["host"] = json!(host);
+                    }
+                    if obfs == "wss" {
+                        plugin_opts["tls"] = json!(true);
+                    }
+                    proxy["plugin"] = json!("v2ray-plugin");
+                    proxy["plugin-opts"] = plugin_opts;
+                }
+                Some(other) => {
+                    return Err(ParseError::unsupported(format!(
+                        "不支持的 obfs 类型：{}",
+                        other
+                    )));
+                }
+                None => {}
+            }
+        }
+        "vmess" | "vless" | "trojan" => {
+            let password = fields
+                .get("password")
+                .ok_or_else(|| ParseError::missing("缺少 password"))?;
+            proxy["type"] = json!(proxy_type);
+            match proxy_type.as_str() {
+                "vmess" => {
+                    proxy["uuid"] = json!(password);
+                    proxy["alterId"] = json!(0);
+                    proxy["cipher"] = json!(fields.get("method").unwrap_or("auto"));
+                }
+                "vless" => {
+                    proxy["uuid"] = json!(password);
+                    if let Some(flow) = fields.get("vless-flow") {
+                        proxy["flow"] = json!(flow);
+                    }
+                }
+                _ => proxy["password"] = json!(password),
+            }
+            apply_quantumult_x_stream(&mut proxy, &fields, proxy_type == "trojan")?;
+        }
+        "http" | "socks5" => {
+            proxy["type"] = json!(proxy_type);
+            if let Some(username) = fields.get("username") {
+                proxy["username"] = json!(username);
+            }
+            if let Some(password) = fields.get("password") {
+                proxy["password"] = json!(password);
+            }
+            if fields.is_true("over-tls") {
+                proxy["tls"] = json!(true);
+                apply_sni(&mut proxy, &fields, "tls-host");
+            }
+            proxy["skip-cert-verify"] = json!(fields.get("tls-verification") == Some("false"));
+        }
+        other => {
+            return Err(ParseError::unsupported(format!(
+                "不支持的节点类型：{}",
+                other
+            )));
+        }
+    }
+
+    Ok(proxy)
+}
+
+// Quantumult X 的 obfs（over-tls/ws/wss/http）与 TLS 参数
+fn apply_quantumult_x_stream(
+    proxy: &mut JsonValue,
+    fields: &LineFields,
+    implicit_tls: bool,
+) -> Result<(), ParseError> {
+    let mut params = HashMap::new();
+    let obfs = fields.get("obfs").unwrap_or("none");
+
+    let network = match obfs {
+        "ws" | "wss" => "ws",
+        "http" => {
+            params.insert("headerType".to_string(), "http".to_string());
+            "tcp"
+        }
+        _ => "tcp",
+    };
+    params.insert("type".to_string(), network.to_string());
+
+    if matches!(obfs, "wss" | "over-tls") || fields.is_true("over-tls") {
+        params.insert("security".to_string(), "tls".to_string());
+    }
+    if let Some(public_key) = fields.get("reality-base64-pubkey") {
+        params.insert("security".to_string(), "reality".to_string());
+        params.insert("pbk".to_string(), public_key.to_string());
+        if let Some(short_id) = fields.get("reality-hex-shortid") {
+            params.insert("sid".to_string(), short_id.to_string());
+        }
+    }
+
+    if let Some(host) = fields.get("obfs-host") {
+        params.insert("host".to_string(), host.to_string());
+    }
+    if let Some(path) = fields.get("obfs-uri") {
+        params.insert("path".to_string(), path.to_string());
+    }
+    // tls-host 优先，其次 obfs-host
+    if let Some(sni) = fields.get("tls-host").or_else(|| fields.get("obfs-host")) {
+        params.insert("sni".to_string(), sni.to_string());
+    }
+    if fields.get("tls-verification") == Some("false") {
+        params.insert("allowInsecure".to_string(), "1".to_string());
+    }
+
+    ProxyParser::apply_tls_params(proxy, &params, implicit_tls);
+    ProxyParser::apply_transport_params(proxy, &params)
+}
+
+// 公共工具
+
+// 按逗号拆分字段，忽略双引号内的逗号并去除首尾空白
+fn split_fields(body: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in body.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            ',' if !in_quotes => {
+                fields.push(unquote(current.trim()));
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    fields.push(unquote(current.trim()));
+
+    fields
+}
+
+// 区分位置参数与键值参数
+fn collect_fields(items: impl Iterator<Item = String>) -> LineFields {
+    let mut positional = Vec::new();
+    let mut options = HashMap::new();
+
+    for item in items {
+        match item.split_once('=') {
+            // 值本身可能含 =（如 Base64 密码），因此只在键名合法时视为键值对
+            Some((key, value)) if is_option_key(key) => {
+                options.insert(key.trim().to_string(), unquote(value.trim()));
+            }
+            _ => positional.push(item),
+        }
+    }
+
+    LineFields {
+        positional,
+        options,
+    }
+}
+
+fn is_option_key(key: &str) -> bool {
+    let key = key.trim();
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn apply_sni(proxy: &mut JsonValue, fields: &LineFields, key: &str) {
+    if let Some(sni) = fields.get(key).or_else(|| fields.get("sni")) {
+        proxy["sni"] = json!(sni);
+    }
+}
+
+fn apply_alpn(proxy: &mut JsonValue, fields: &LineFields) {
+    if let Some(alpn) = fields.get("alpn") {
+        proxy["alpn"] = json!(alpn.split(',').map(str::trim).collect::<Vec<_>>());
+    }
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyParser;
+    use crate::atoms::proxy_node::{ParseError, ParseErrorKind};
+    use crate::atoms::proxy_parser::ParseOptions;
+    use crate::atoms::proxy_parser::report::NodeChangeKind;
+    use serde_json::json;
+
+    const UUID: &str = "b831381d-6324-4d53-ad4f-8cda48b30811";
+
+    #[test]
+    fn parse_surge_and_loon_lines() -> Result<(), String> {
+        let ss = ProxyParser::parse_single_proxy(
+            "香港 01 = ss, hk.example.com, 8388, encrypt-method=aes-128-gcm, password=pw, obfs=http, obfs-host=a.com, udp-relay=true",
+        )?;
+        assert_eq!(ss["name"], "香港 01");
+        assert_eq!(ss["cipher"], "aes-128-gcm");
+        assert_eq!(ss["plugin"], "obfs");
+        assert_eq!(ss["plugin-opts"]["host"], "a.com");
+        assert_eq!(ss["udp"], true);
+
+        let vmess = ProxyParser::parse_single_proxy(&format!(
+            "JP = vmess, jp.example.com, 443, username={}, ws=true, ws-path=/ws, ws-headers=Host:cdn.com, tls=true, sni=jp.example.com",
+            UUID
+        ))?;
+        assert_eq!(vmess["uuid"], UUID);
+        assert_eq!(vmess["ws-opts"]["headers"]["Host"], "cdn.com");
+        assert_eq!(vmess["servername"], "jp.example.com");
+
+        let loon = ProxyParser::parse_single_proxy(&format!(
+            "US = vmess, us.example.com, 443, aes-128-gcm, \"{}\", transport=ws, path=/v, host=us.com, over-tls=true, tls-name=us.com",
+            UUID
+        ))?;
+        assert_eq!(loon["cipher"], "aes-128-gcm");
+        assert_eq!(loon["ws-opts"]["path"], "/v");
+        assert_eq!(loon["tls"], true);
+
+        let trojan = ProxyParser::parse_single_proxy(
+            "SG = trojan, sg.example.com, 443, password=pw, sni=sg.com, skip-cert-verify=true",
+        )?;
+        assert_eq!(trojan["sni"], "sg.com");
+        assert_eq!(trojan["skip-cert-verify"], true);
+        Ok(())
+    }
+
+    #[test]
+    fn validate_underlying_proxy_references() -> Result<(), String> {
+        let content = [
+            "PROXY = trojan, a.example.com, 443, password=pw",
+            "Chained = trojan, b.example.com, 443, password=pw, underlying-proxy=PROXY",
+            "Broken = trojan, c.example.com, 443, password=pw, underlying-proxy=Surge Group",
+        ]
+        .join("\n");
+
+        let (result, report) =
+            ProxyParser::parse_subscription_with_report(&content, &ParseOptions::default());
+        let root: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(&result?).map_err(|e| e.to_string())?;
+        let proxies = root["proxies"].as_sequence().ok_or("缺少 proxies")?;
+        assert_eq!(proxies.len(), 2);
+        assert_eq!(proxies[0]["name"], "PROXY 2");
+        assert_eq!(proxies[1]["dialer-proxy"], "PROXY 2");
+
+        let dangling = report
+            .node_changes
+            .iter()
+            .find(|c| c.kind == NodeChangeKind::DanglingDialer)
+            .ok_or("未记录悬空引用")?;
+        assert_eq!(dangling.original_name, "Broken");
+        assert_eq!(dangling.new_name, "Surge Group");
+        Ok(())
+    }
+
+    #[test]
+    fn parse_quantumult_x_lines() -> Result<(), String> {
+        let ss = ProxyParser::parse_single_proxy(
+            "shadowsocks=hk.example.com:443, method=chacha20-ietf-poly1305, password=pw, obfs=wss, obfs-host=cdn.com, obfs-uri=/ws, tag=香港",
+        )?;
+        assert_eq!(ss["name"], "香港");
+        assert_eq!(ss["plugin"], "v2ray-plugin");
+        assert_eq!(
+            ss["plugin-opts"],
+            json!({"mode": "websocket", "path": "/ws", "host": "cdn.com", "tls": true})
+        );
+
+        let vless = ProxyParser::parse_single_proxy(&format!(
+            "vless=jp.example.com:443, method=none, password={}, obfs=over-tls, obfs-host=www.apple.com, reality-base64-pubkey=key, reality-hex-shortid=ab, vless-flow=xtls-rprx-vision, tag=JP",
+            UUID
+        ))?;
+        assert_eq!(vless["flow"], "xtls-rprx-vision");
+        assert_eq!(vless["servername"], "www.apple.com");
+        assert_eq!(vless["reality-opts"]["public-key"], "key");
+        Ok(())
+    }
+
+    #[test]
+    fn report_unmappable_lines() {
+        assert_eq!(
+            ProxyParser::parse_single_proxy("Direct = direct").err(),
+            Some(ParseError::unsupported(
+                "direct 为内置策略，无法转换为代理节点"
+            ))
+        );
+        assert!(
+            ProxyParser::parse_single_proxy("WG = wireguard, section-name=home")
+                .is_err_and(
+                    |e| e.kind == ParseErrorKind::Unsupported && e.message.contains("[WireGuard]")
+                )
+        );
+        assert_eq!(
+            ProxyParser::parse_single_proxy("SS = ss, example.com, 8388")
+                .err()
+                .map(|e| e.kind),
+            Some(ParseErrorKind::MissingField)
+        );
+    }
+}
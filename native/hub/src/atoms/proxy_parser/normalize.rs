@@ -0,0 +1,491 @@
+// 节点规范化：重名节点追加稳定序号，可选移除端点完全相同的重复节点。
+// mihomo 遇到重名节点会拒绝启动，因此生成配置前必须执行。
+
+use super::groups::DEFAULT_GROUP_NAMES;
+use super::options::ParseOptions;
+use super::pipeline::BUILTIN_POLICY_NAMES;
+use super::report::{NodeChange, NodeChangeKind};
+use crate::atoms::ProxyNode;
+use serde_json::{Value as JsonValue, json};
+use serde_yaml_ng::Value as YamlValue;
+use std::collections::{HashMap, HashSet};
+
+// 规范化节点列表，返回处理后的节点与变更记录
+pub(super) fn normalize_proxies(
+    proxies: Vec<ProxyNode>,
+    options: &ParseOptions,
+) -> (Vec<ProxyNode>, Vec<NodeChange>) {
+    let mut changes = Vec::new();
+
+    // 移除重复端点（保留首次出现的节点）
+    let proxies = if options.drop_duplicate_endpoints {
+        let mut seen: HashMap<String, String> = HashMap::new();
+        proxies
+            .into_iter()
+            .filter(|proxy| {
+                let Some(key) = endpoint_key(proxy) else {
+                    return true;
+                };
+                match seen.get(&key) {
+                    Some(kept_name) => {
+                        changes.push(NodeChange {
+                            kind: NodeChangeKind::DroppedDuplicate,
+                            original_name: proxy.name().to_string(),
+                            new_name: kept_name.clone(),
+                        });
+                        false
+                    }
+                    None => {
+                        seen.insert(key, proxy.name().to_string());
+                        true
+                    }
+                }
+            })
+            .collect()
+    } else {
+        proxies
+    };
+
+    // 重名节点追加稳定序号；将要生成的代理组名与内置策略名优先保留，避免节点与其重名
+    let names: Vec<String> = proxies.iter().map(|p| p.name().to_string()).collect();
+    let mut reserved_names: Vec<String> = BUILTIN_POLICY_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    reserved_names.extend(generated_group_names(options));
+    let reserved_count = reserved_names.len();
+    reserved_names.extend(names.iter().cloned());
+    let unique_names = deduplicate_names(&reserved_names).split_off(reserved_count);
+
+    let mut rename_map: HashMap<String, String> = HashMap::new();
+    let mut proxies: Vec<ProxyNode> = proxies
+        .into_iter()
+        .zip(names.into_iter().zip(unique_names))
+        .map(|(mut proxy, (name, new_name))| {
+            if name != new_name {
+                proxy.common_mut().name = new_name.clone();
+                rename_map
+                    .entry(name.clone())
+                    .or_insert_with(|| new_name.clone());
+                changes.push(NodeChange {
+                    kind: NodeChangeKind::Renamed,
+                    original_name: name,
+                    new_name,
+                });
+            }
+            proxy
+        })
+        .collect();
+
+    // 原名已不存在的节点（与保留名称冲突而改名）同步 dialer-proxy 引用
+    let final_names: HashSet<String> = proxies.iter().map(|p| p.name().to_string()).collect();
+    for proxy in &mut proxies {
+        let common = proxy.common_mut();
+        if let Some(dialer) = &common.dialer_proxy
+            && !final_names.contains(dialer)
+            && let Some(new_name) = rename_map.get(dialer)
+        {
+            common.dialer_proxy = Some(new_name.clone());
+        }
+    }
+
+    (proxies, changes)
+}
+
+// 生成配置时将创建的代理组名称（模板或默认 PROXY/AUTO 组）
+pub(super) fn generated_group_names(options: &ParseOptions) -> Vec<String> {
+    match &options.group_template {
+        Some(template) => template.group_names(),
+        None => DEFAULT_GROUP_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+    }
+}
+
+// 重名项追加序号：首个保留原名，后续依次为 "名称 2"、"名称 3"…
+// 跳过与其他原始名称冲突的序号，保证结果与输入顺序一一对应
+pub(super) fn deduplicate_names(names: &[String]) -> Vec<String> {
+    let original_names: HashSet<&str> = names.iter().map(String::as_str).collect();
+    let mut used_names = HashSet::new();
+    names
+        .iter()
+        .map(|name| {
+            if used_names.insert(name.clone()) {
+                return name.clone();
+            }
+            let new_name = (2..)
+                .map(|index| format!("{} {}", name, index))
+                .find(|candidate| {
+                    !used_names.contains(candidate) && !original_names.contains(candidate.as_str())
+                })
+                .unwrap_or_default();
+            used_names.insert(new_name.clone());
+            new_name
+        })
+        .collect()
+}
+
+// 完整 Clash 配置的重名节点追加序号；无重名时返回 None，配置保持原样。
+// 代理组中引用重名节点的成员展开为全部同名节点，保证改名后的节点仍可选用
+pub(super) fn deduplicate_yaml_names(
+    config: &str,
+) -> Result<Option<(String, Vec<NodeChange>)>, String> {
+    let mut root: YamlValue =
+        serde_yaml_ng::from_str(config).map_err(|e| format!("YAML 解析失败：{}", e))?;
+    let Some(proxies) = root.get_mut("proxies").and_then(YamlValue::as_sequence_mut) else {
+        return Ok(None);
+    };
+
+    let names: Vec<String> = proxies
+        .iter()
+        .map(|proxy| {
+            proxy
+                .get("name")
+                .and_then(YamlValue::as_str)
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect();
+    let unique_names = deduplicate_names(&names);
+    if names == unique_names {
+        return Ok(None);
+    }
+
+    let mut changes = Vec::new();
+    let mut variants: HashMap<String, Vec<String>> = HashMap::new();
+    for ((proxy, name), new_name) in proxies.iter_mut().zip(&names).zip(&unique_names) {
+        variants
+            .entry(name.clone())
+            .or_default()
+            .push(new_name.clone());
+        if name == new_name {
+            continue;
+        }
+        if let Some(map) = proxy.as_mapping_mut() {
+            map.insert("name".into(), YamlValue::String(new_name.clone()));
+        }
+        changes.push(NodeChange {
+            kind: NodeChangeKind::Renamed,
+            original_name: name.clone(),
+            new_name: new_name.clone(),
+        });
+    }
+
+    if let Some(groups) = root
+        .get_mut("proxy-groups")
+        .and_then(YamlValue::as_sequence_mut)
+    {
+        for members in groups
+            .iter_mut()
+            .filter_map(|group| group.get_mut("proxies"))
+            .filter_map(YamlValue::as_sequence_mut)
+        {
+            let mut seen = HashSet::new();
+            let expanded: Vec<YamlValue> = members
+                .iter()
+                .flat_map(
+                    |member| match member.as_str().and_then(|name| variants.get(name)) {
+                        Some(names) => names
+                            .iter()
+                            .map(|name| YamlValue::String(name.clone()))
+                            .collect(),
+                        None => vec![member.clone()],
+                    },
+                )
+                .filter(|member| {
+                    member
+                        .as_str()
+                        .is_none_or(|name| seen.insert(name.to_string()))
+                })
+                .collect();
+            *members = expanded;
+        }
+    }
+
+    log::info!("重名节点已追加序号：{}个", changes.len());
+    let config = serde_yaml_ng::to_string(&root).map_err(|e| format!("YAML 序列化失败：{}", e))?;
+    Ok(Some((config, changes)))
+}
+
+// 将代理组中被移除的重复节点替换为保留的节点，并去除重复成员
+pub(super) fn remap_group_members(
+    groups: &mut [JsonValue],
+    proxies: &[ProxyNode],
+    changes: &[NodeChange],
+) {
+    // 被移除的重复节点指向保留节点；原名已不存在的改名节点指向新名称
+    let final_names: HashSet<&str> = proxies.iter().map(ProxyNode::name).collect();
+    let mut replacements: HashMap<&str, &str> = HashMap::new();
+    for change in changes {
+        let is_replaced = match change.kind {
+            NodeChangeKind::DroppedDuplicate => true,
+            NodeChangeKind::Renamed => !final_names.contains(change.original_name.as_str()),
+            _ => false,
+        };
+        if is_replaced {
+            replacements
+                .entry(change.original_name.as_str())
+                .or_insert(change.new_name.as_str());
+        }
+    }
+    let removed: HashSet<&str> = changes
+        .iter()
+        .filter(|change| change.kind == NodeChangeKind::DanglingDialer)
+        .map(|change| change.original_name.as_str())
+        .collect();
+    if replacements.is_empty() && removed.is_empty() {
+        return;
+    }
+
+    for group in groups {
+        let Some(members) = group["proxies"].as_array() else {
+            continue;
+        };
+        let mut seen = HashSet::new();
+        let mut members: Vec<String> = members
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .filter(|member| !removed.contains(member))
+            .map(|member| replacements.get(member).copied().unwrap_or(member))
+            .filter(|member| seen.insert(*member))
+            .map(str::to_string)
+            .collect();
+        // mihomo 不允许空代理组
+        if members.is_empty() {
+            members.push("DIRECT".to_string());
+        }
+        group["proxies"] = json!(members);
+    }
+}
+
+// dialer-proxy 须指向最终节点、代理组或内置策略；无法解析的节点连同依赖它的节点一并移除，
+// 避免链式节点绕过前置节点直连或导致 mihomo 加载失败
+pub(super) fn drop_dangling_dialers(
+    proxies: Vec<ProxyNode>,
+    group_names: &[String],
+) -> (Vec<ProxyNode>, Vec<NodeChange>) {
+    let mut changes = Vec::new();
+    let mut kept = proxies;
+    loop {
+        let known_names: HashSet<String> = kept
+            .iter()
+            .map(|proxy| proxy.name().to_string())
+            .chain(group_names.iter().cloned())
+            .chain(BUILTIN_POLICY_NAMES.iter().map(|name| name.to_string()))
+            .collect();
+        let (dangling, rest): (Vec<_>, Vec<_>) = kept.into_iter().partition(|proxy| {
+            proxy
+                .common()
+                .dialer_proxy
+                .as_deref()
+                .is_some_and(|dialer| !known_names.contains(dialer))
+        });
+        kept = rest;
+        if dangling.is_empty() {
+            break;
+        }
+        for proxy in dangling {
+            let dialer = proxy.common().dialer_proxy.clone().unwrap_or_default();
+            log::warn!(
+                "移除 dialer-proxy 无法解析的节点：{} -> {}",
+                proxy.name(),
+                dialer
+            );
+            changes.push(NodeChange {
+                kind: NodeChangeKind::DanglingDialer,
+                original_name: proxy.name().to_string(),
+                new_name: dialer,
+            });
+        }
+    }
+    (kept, changes)
+}
+
+// 端点标识：类型 + 服务器 + 端口 + 凭据
+fn endpoint_key(proxy: &ProxyNode) -> Option<String> {
+    let credential = match proxy {
+        ProxyNode::Shadowsocks(node) => format!("{}:{}", node.cipher, node.password),
+        ProxyNode::ShadowsocksR(node) => format!("{}:{}", node.cipher, node.password),
+        ProxyNode::Vmess(node) => node.uuid.clone(),
+        ProxyNode::Vless(node) => node.uuid.clone(),
+        ProxyNode::Trojan(node) => node.password.clone(),
+        ProxyNode::AnyTls(node) => node.password.clone(),
+        ProxyNode::Hysteria(node) => node.auth_str.clone().unwrap_or_default(),
+        ProxyNode::Hysteria2(node) => node.password.clone().unwrap_or_default(),
+        ProxyNode::Tuic(node) => format!(
+            "{}:{}",
+            node.uuid
+                .as_deref()
+                .or(node.token.as_deref())
+                .unwrap_or_default(),
+            node.password.as_deref().unwrap_or_default()
+        ),
+        ProxyNode::Http(node) => format!(
+            "{}:{}",
+            node.username.as_deref().unwrap_or_default(),
+            node.password.as_deref().unwrap_or_default()
+        ),
+        ProxyNode::Socks5(node) => format!(
+            "{}:{}",
+            node.username.as_deref().unwrap_or_default(),
+            node.password.as_deref().unwrap_or_default()
+        ),
+        ProxyNode::WireGuard(node) => node.private_key.clone(),
+        ProxyNode::Mieru(node) => format!("{}:{}", node.username, node.password),
+        ProxyNode::Snell(node) => node.psk.clone(),
+        ProxyNode::Ssh(node) => format!(
+            "{}:{}:{}",
+            node.username,
+            node.password.as_deref().unwrap_or_default(),
+            node.private_key.as_deref().unwrap_or_default()
+        ),
+        // 无端点或未建模的类型不参与去重
+        ProxyNode::Direct(_) | ProxyNode::Dns(_) | ProxyNode::Unknown(_) => return None,
+    };
+
+    let common = proxy.common();
+    Some(format!(
+        "{}|{}|{}|{}",
+        proxy.type_name(),
+        common.server.to_ascii_lowercase(),
+        common.port,
+        credential
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deduplicate_yaml_names, normalize_proxies, remap_group_members};
+    use crate::atoms::ProxyNode;
+    use crate::atoms::proxy_parser::groups::GroupTemplate;
+    use crate::atoms::proxy_parser::options::ParseOptions;
+    use crate::atoms::proxy_parser::report::NodeChangeKind;
+    use serde_json::json;
+
+    fn trojan(name: &str, server: &str) -> Result<ProxyNode, String> {
+        ProxyNode::from_json(json!({
+            "name": name, "type": "trojan", "server": server, "port": 443, "password": "pw",
+        }))
+        .map_err(String::from)
+    }
+
+    #[test]
+    fn rename_duplicates_and_drop_identical_endpoints() -> Result<(), String> {
+        let proxies = vec![
+            trojan("HK", "a.com")?,
+            trojan("HK", "b.com")?,
+            trojan("HK 2", "c.com")?,
+            trojan("HK copy", "A.com")?,
+        ];
+
+        let (renamed, changes) = normalize_proxies(proxies.clone(), &ParseOptions::default());
+        let names: Vec<_> = renamed.iter().map(ProxyNode::name).collect();
+        assert_eq!(names, vec!["HK", "HK 3", "HK 2", "HK copy"]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, NodeChangeKind::Renamed);
+
+        let options = ParseOptions {
+            drop_duplicate_endpoints: true,
+            ..Default::default()
+        };
+        let (deduplicated, changes) = normalize_proxies(proxies, &options);
+        assert_eq!(deduplicated.len(), 3);
+        assert_eq!(changes[0].kind, NodeChangeKind::DroppedDuplicate);
+        assert_eq!(changes[0].original_name, "HK copy");
+        assert_eq!(changes[0].new_name, "HK");
+
+        // SSH 同一用户名但密码或私钥不同的节点不视为重复
+        let ssh = |name: &str, credential: (&str, &str)| {
+            let mut proxy = json!({
+                "name": name, "type": "ssh", "server": "a.com", "port": 22, "username": "root",
+            });
+            proxy[credential.0] = json!(credential.1);
+            ProxyNode::from_json(proxy)
+        };
+        let ssh_proxies = vec![
+            ssh("SSH A", ("password", "a"))?,
+            ssh("SSH B", ("password", "b"))?,
+            ssh("SSH Key", ("private-key", "key"))?,
+            ssh("SSH A copy", ("password", "a"))?,
+        ];
+        let (ssh_kept, ssh_changes) = normalize_proxies(ssh_proxies, &options);
+        assert_eq!(ssh_kept.len(), 3);
+        assert_eq!(ssh_changes[0].original_name, "SSH A copy");
+
+        let mut groups = vec![json!({"name": "G", "proxies": ["HK copy", "HK", "HK 2"]})];
+        remap_group_members(&mut groups, &deduplicated, &changes);
+        assert_eq!(groups[0]["proxies"], json!(["HK", "HK 2"]));
+        Ok(())
+    }
+
+    #[test]
+    fn rename_nodes_colliding_with_generated_groups() -> Result<(), String> {
+        let chained = ProxyNode::from_json(json!({
+            "name": "Chained", "type": "trojan", "server": "d.com", "port": 443,
+            "password": "pw", "dialer-proxy": "AUTO",
+        }))?;
+        let proxies = vec![
+            trojan("PROXY", "a.com")?,
+            trojan("AUTO", "b.com")?,
+            trojan("🇭🇰 香港", "c.com")?,
+            trojan("DIRECT", "e.com")?,
+            chained,
+        ];
+        let template = GroupTemplate {
+            region_groups: true,
+            ..Default::default()
+        };
+        let options = ParseOptions {
+            group_template: Some(template.clone()),
+            ..Default::default()
+        };
+
+        let (renamed, changes) = normalize_proxies(proxies, &options);
+        let names: Vec<String> = renamed.iter().map(|p| p.name().to_string()).collect();
+        assert_eq!(
+            names,
+            vec!["PROXY 2", "AUTO 2", "🇭🇰 香港 2", "DIRECT 2", "Chained"]
+        );
+        assert_eq!(changes.len(), 4);
+        assert_eq!(renamed[4].common().dialer_proxy.as_deref(), Some("AUTO 2"));
+
+        let (groups, _) = template.build(&names)?;
+        let group_names: Vec<_> = groups.iter().filter_map(|g| g["name"].as_str()).collect();
+        assert!(
+            group_names
+                .iter()
+                .all(|group| !names.iter().any(|name| name == group))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rename_duplicates_in_clash_yaml() -> Result<(), String> {
+        let config = r#"
+proxies:
+  - {name: HK, type: trojan, server: a.com, port: 443, password: pw}
+  - {name: HK, type: trojan, server: b.com, port: 443, password: pw}
+  - {name: JP, type: trojan, server: c.com, port: 443, password: pw}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: [HK, JP]}
+"#;
+        let (config, changes) = deduplicate_yaml_names(config)?.ok_or("未处理重名节点")?;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].new_name, "HK 2");
+
+        let root: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(&config).map_err(|e| e.to_string())?;
+        assert_eq!(root["proxies"][1]["name"], "HK 2");
+        let members: Vec<_> = root["proxy-groups"][0]["proxies"]
+            .as_sequence()
+            .ok_or("缺少成员")?
+            .iter()
+            .filter_map(|member| member.as_str())
+            .collect();
+        assert_eq!(members, vec!["HK", "HK 2", "JP"]);
+
+        assert!(deduplicate_yaml_names(&config)?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+// 订阅解析选项
+
+use super::groups::GroupTemplate;
+use super::pipeline::NodePipeline;
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+
+// 订阅解析选项（未提供时使用默认值）
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, Default)]
+pub struct ParseOptions {
+    // 移除服务器、端口与凭据完全相同的重复节点
+    pub drop_duplicate_endpoints: bool,
+    // 代理组模板（为空时生成默认 PROXY/AUTO 组）
+    pub group_template: Option<GroupTemplate>,
+    // 节点过滤与重命名（在生成配置之后、覆写之前执行）
+    pub node_pipeline: Option<NodePipeline>,
+    // 保留流量、到期等信息节点（默认移除）
+    pub keep_info_nodes: bool,
+    // 信息节点匹配规则（正则，为空时使用内置关键字）
+    pub info_node_patterns: Vec<String>,
+}
@@ -0,0 +1,428 @@
+// 节点处理流水线：按名称过滤、正则重命名、添加旗帜与前缀。
+// 作用于最终 Clash 配置的 proxies，并同步代理组成员与 dialer-proxy 引用。
+
+use super::groups::{RegionMatcher, builtin_region_matchers};
+use super::normalize::deduplicate_names;
+use super::report::{NodeChange, NodeChangeKind};
+use crate::atoms::ProxyNode;
+use regex::Regex;
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::{Mapping, Value as YamlValue};
+use std::collections::{HashMap, HashSet};
+
+// mihomo 内置策略名，节点不可与之重名
+pub(super) const BUILTIN_POLICY_NAMES: &[&str] =
+    &["DIRECT", "REJECT", "REJECT-DROP", "PASS", "COMPATIBLE"];
+
+// 正则重命名规则
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct RenameRule {
+    pub pattern: String,     // 匹配节点名称的正则表达式
+    pub replacement: String, // 替换内容，支持 $1 等捕获组
+}
+
+// 节点处理流水线（各步骤按字段顺序执行）
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, Default)]
+pub struct NodePipeline {
+    pub include_pattern: String,  // 仅保留名称匹配的节点（为空时不过滤）
+    pub exclude_pattern: String,  // 移除名称匹配的节点（为空时不过滤）
+    pub renames: Vec<RenameRule>, // 依次执行的正则重命名
+    pub add_flag_emoji: bool,     // 为识别出地区的节点添加旗帜前缀
+    pub name_prefix: String,      // 节点名称前缀（如订阅名称）
+}
+
+// 流水线处理结果
+pub(super) struct PipelineOutput {
+    pub(super) config: String,
+    pub(super) changes: Vec<NodeChange>,
+    pub(super) proxies: Vec<ProxyNode>,
+}
+
+impl NodePipeline {
+    // 是否没有任何处理步骤
+    pub(super) fn is_empty(&self) -> bool {
+        self.include_pattern.trim().is_empty()
+            && self.exclude_pattern.trim().is_empty()
+            && self.renames.is_empty()
+            && !self.add_flag_emoji
+            && self.name_prefix.is_empty()
+    }
+
+    // 处理 Clash 配置中的节点
+    pub(super) fn apply(&self, config: &str) -> Result<PipelineOutput, String> {
+        let include = compile_optional(&self.include_pattern, "节点包含规则")?;
+        let exclude = compile_optional(&self.exclude_pattern, "节点排除规则")?;
+        let renames = self
+            .renames
+            .iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|regex| (regex, rule.replacement.as_str()))
+                    .map_err(|e| format!("重命名规则 {} 无效：{}", rule.pattern, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let regions = if self.add_flag_emoji {
+            builtin_region_matchers()?
+        } else {
+            Vec::new()
+        };
+
+        let mut root: YamlValue =
+            serde_yaml_ng::from_str(config).map_err(|e| format!("YAML 解析失败：{}", e))?;
+        let Some(root_map) = root.as_mapping_mut() else {
+            return Err("配置格式无效：根节点不是映射".to_string());
+        };
+        let proxies = match root_map
+            .get_mut("proxies")
+            .and_then(YamlValue::as_sequence_mut)
+        {
+            Some(proxies) => std::mem::take(proxies),
+            None => Vec::new(),
+        };
+
+        // 过滤
+        let mut changes = Vec::new();
+        let mut removed_names = HashSet::new();
+        let mut kept = Vec::new();
+        for proxy in proxies {
+            let name = proxy_name(&proxy);
+            let included = include.as_ref().is_none_or(|regex| regex.is_match(&name));
+            let excluded = exclude.as_ref().is_some_and(|regex| regex.is_match(&name));
+            if included && !excluded {
+                kept.push(proxy);
+            } else {
+                changes.push(NodeChange {
+                    kind: NodeChangeKind::Filtered,
+                    original_name: name.clone(),
+                    new_name: String::new(),
+                });
+                removed_names.insert(name);
+            }
+        }
+
+        // dialer-proxy 指向已移除节点的节点一并移除，避免链式节点绕过前置节点直连
+        loop {
+            let (dangling, rest): (Vec<_>, Vec<_>) = kept.into_iter().partition(|proxy| {
+                proxy
+                    .get("dialer-proxy")
+                    .and_then(YamlValue::as_str)
+                    .is_some_and(|dialer| removed_names.contains(dialer))
+            });
+            kept = rest;
+            if dangling.is_empty() {
+                break;
+            }
+            for proxy in dangling {
+                let name = proxy_name(&proxy);
+                changes.push(NodeChange {
+                    kind: NodeChangeKind::Filtered,
+                    original_name: name.clone(),
+                    new_name: String::new(),
+                });
+                removed_names.insert(name);
+            }
+        }
+
+        // 重命名（结果为空时保留原名），再为冲突名称追加序号；
+        // 代理组名与内置策略名优先保留，避免节点与其重名
+        let original_names: Vec<String> = kept.iter().map(proxy_name).collect();
+        let mut reserved_names: Vec<String> = BUILTIN_POLICY_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        reserved_names.extend(group_names(root_map));
+        let reserved_count = reserved_names.len();
+        reserved_names.extend(
+            original_names
+                .iter()
+                .map(|name| self.rename(name, &renames, &regions)),
+        );
+        let final_names = deduplicate_names(&reserved_names).split_off(reserved_count);
+
+        let mut rename_map: HashMap<String, String> = HashMap::new();
+        for ((proxy, original), new_name) in kept.iter_mut().zip(&original_names).zip(final_names) {
+            if *original == new_name {
+                continue;
+            }
+            if let Some(map) = proxy.as_mapping_mut() {
+                map.insert("name".into(), YamlValue::String(new_name.clone()));
+            }
+            changes.push(NodeChange {
+                kind: NodeChangeKind::Renamed,
+                original_name: original.clone(),
+                new_name: new_name.clone(),
+            });
+            rename_map.entry(original.clone()).or_insert(new_name);
+        }
+
+        // 同步 dialer-proxy 引用
+        for proxy in &mut kept {
+            if let Some(map) = proxy.as_mapping_mut()
+                && let Some(dialer) = map.get("dialer-proxy").and_then(YamlValue::as_str)
+                && let Some(new_name) = rename_map.get(dialer)
+            {
+                map.insert("dialer-proxy".into(), YamlValue::String(new_name.clone()));
+            }
+        }
+
+        let nodes: Vec<ProxyNode> = kept
+            .iter()
+            .filter_map(|proxy| ProxyNode::from_yaml_lenient(proxy).ok())
+            .collect();
+        root_map.insert("proxies".into(), YamlValue::Sequence(kept));
+
+        // 同步代理组成员
+        if let Some(groups) = root_map
+            .get_mut("proxy-groups")
+            .and_then(YamlValue::as_sequence_mut)
+        {
+            for group in groups.iter_mut().filter_map(YamlValue::as_mapping_mut) {
+                update_group_members(group, &removed_names, &rename_map);
+            }
+        }
+
+        // 同步规则目标：指向已移除节点的规则删除，指向重命名节点的规则改写
+        if let Some(rules) = root_map
+            .get_mut("rules")
+            .and_then(YamlValue::as_sequence_mut)
+        {
+            rules.retain_mut(|rule| update_rule_target(rule, &removed_names, &rename_map));
+        }
+
+        let config =
+            serde_yaml_ng::to_string(&root).map_err(|e| format!("YAML 序列化失败：{}", e))?;
+
+        Ok(PipelineOutput {
+            config,
+            changes,
+            proxies: nodes,
+        })
+    }
+
+    fn rename(&self, name: &str, renames: &[(Regex, &str)], regions: &[RegionMatcher]) -> String {
+        let mut new_name = name.to_string();
+        for (regex, replacement) in renames {
+            new_name = regex
+                .replace_all(&new_name, *replacement)
+                .trim()
+                .to_string();
+        }
+        if new_name.is_empty() {
+            new_name = name.to_string();
+        }
+
+        if !has_flag_emoji(&new_name)
+            && let Some(flag) = regions
+                .iter()
+                .find(|region| region.regex.is_match(&new_name))
+                .and_then(|region| region.flag)
+        {
+            new_name = format!("{} {}", flag, new_name);
+        }
+
+        format!("{}{}", self.name_prefix, new_name)
+    }
+}
+
+// 移除被过滤的节点并替换重命名的节点。
+// 成员被清空且没有 use/include-all 等来源的组保留 DIRECT，避免 mihomo 拒绝空组。
+fn update_group_members(
+    group: &mut Mapping,
+    removed_names: &HashSet<String>,
+    rename_map: &HashMap<String, String>,
+) {
+    let Some(members) = group.get("proxies").and_then(YamlValue::as_sequence) else {
+        return;
+    };
+    let mut members: Vec<YamlValue> = members
+        .iter()
+        .filter(|member| {
+            member
+                .as_str()
+                .is_none_or(|name| !removed_names.contains(name))
+        })
+        .map(
+            |member| match member.as_str().and_then(|name| rename_map.get(name)) {
+                Some(new_name) => YamlValue::String(new_name.clone()),
+                None => member.clone(),
+            },
+        )
+        .collect();
+
+    let has_other_source = [
+        "use",
+        "include-all",
+        "include-all-proxies",
+        "include-all-providers",
+    ]
+    .iter()
+    .any(|key| group.contains_key(*key));
+    if members.is_empty() && !has_other_source {
+        members.push(YamlValue::String("DIRECT".to_string()));
+    }
+    group.insert("proxies".into(), YamlValue::Sequence(members));
+}
+
+// 返回 false 表示规则应删除
+fn update_rule_target(
+    rule: &mut YamlValue,
+    removed_names: &HashSet<String>,
+    rename_map: &HashMap<String, String>,
+) -> bool {
+    let Some(text) = rule.as_str() else {
+        return true;
+    };
+    let mut parts: Vec<String> = text.split(',').map(str::to_string).collect();
+    // 目标为最后一项，no-resolve/src 等参数位于目标之后
+    let mut index = parts.len() - 1;
+    if index > 1 && matches!(parts[index].trim(), "no-resolve" | "src") {
+        index -= 1;
+    }
+    let target = parts[index].trim();
+    if removed_names.contains(target) {
+        log::warn!("规则 {} 的目标节点已被移除，已删除该规则", text);
+        return false;
+    }
+    if let Some(new_name) = rename_map.get(target) {
+        parts[index] = new_name.clone();
+        *rule = YamlValue::String(parts.join(","));
+    }
+    true
+}
+
+fn group_names(root_map: &Mapping) -> Vec<String> {
+    root_map
+        .get("proxy-groups")
+        .and_then(YamlValue::as_sequence)
+        .map(|groups| {
+            groups
+                .iter()
+                .filter_map(|group| group.get("name").and_then(YamlValue::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn compile_optional(pattern: &str, label: &str) -> Result<Option<Regex>, String> {
+    if pattern.trim().is_empty() {
+        return Ok(None);
+    }
+    Regex::new(pattern)
+        .map(Some)
+        .map_err(|e| format!("{}无效：{}", label, e))
+}
+
+fn proxy_name(proxy: &YamlValue) -> String {
+    proxy
+        .get("name")
+        .and_then(YamlValue::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+// 是否已包含旗帜 emoji（区域指示符号）
+fn has_flag_emoji(name: &str) -> bool {
+    name.chars()
+        .any(|c| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NodePipeline, RenameRule};
+    use crate::atoms::proxy_parser::report::NodeChangeKind;
+    use serde_yaml_ng::Value as YamlValue;
+
+    #[test]
+    fn filter_rename_and_keep_groups_consistent() -> Result<(), String> {
+        let config = r#"
+proxies:
+  - {name: "HK 01 [0.5x]", type: trojan, server: a.com, port: 443, password: pw}
+  - {name: "剩余流量：10GB", type: trojan, server: b.com, port: 443, password: pw}
+  - {name: "Japan 02", type: trojan, server: c.com, port: 443, password: pw, dialer-proxy: "HK 01 [0.5x]"}
+  - {name: "Chained", type: trojan, server: d.com, port: 443, password: pw, dialer-proxy: "剩余流量：10GB"}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: [AUTO, "HK 01 [0.5x]", "剩余流量：10GB", "Japan 02"]}
+  - {name: INFO, type: select, proxies: ["剩余流量：10GB"]}
+  - {name: AUTO, type: url-test, proxies: ["HK 01 [0.5x]", "Japan 02"]}
+rules:
+  - DOMAIN,info.com,剩余流量：10GB
+  - IP-CIDR,1.1.1.1/32,HK 01 [0.5x],no-resolve
+  - MATCH,PROXY
+"#;
+        let pipeline = NodePipeline {
+            exclude_pattern: "剩余|到期".to_string(),
+            renames: vec![RenameRule {
+                pattern: r"\s*\[.*\]".to_string(),
+                replacement: String::new(),
+            }],
+            add_flag_emoji: true,
+            name_prefix: "A | ".to_string(),
+            ..Default::default()
+        };
+
+        let output = pipeline.apply(config)?;
+        assert_eq!(output.proxies.len(), 2);
+        assert!(
+            output
+                .changes
+                .iter()
+                .any(|c| c.kind == NodeChangeKind::Filtered)
+        );
+
+        let root: YamlValue = serde_yaml_ng::from_str(&output.config).map_err(|e| e.to_string())?;
+        let names: Vec<_> = output.proxies.iter().map(|p| p.name()).collect();
+        assert_eq!(names, vec!["A | 🇭🇰 HK 01", "A | 🇯🇵 Japan 02"]);
+        assert_eq!(root["proxies"][1]["dialer-proxy"], "A | 🇭🇰 HK 01");
+        // 前置节点被移除的链式节点一并移除，规则目标同步
+        assert!(!output.config.contains("Chained"));
+        let rules: Vec<_> = root["rules"]
+            .as_sequence()
+            .ok_or("缺少 rules")?
+            .iter()
+            .filter_map(|rule| rule.as_str())
+            .collect();
+        assert_eq!(
+            rules,
+            vec!["IP-CIDR,1.1.1.1/32,A | 🇭🇰 HK 01,no-resolve", "MATCH,PROXY"]
+        );
+
+        let members = |index: usize| -> Vec<String> {
+            root["proxy-groups"][index]["proxies"]
+                .as_sequence()
+                .map(|s| {
+                    s.iter()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        assert_eq!(members(0), vec!["AUTO", "A | 🇭🇰 HK 01", "A | 🇯🇵 Japan 02"]);
+        assert_eq!(members(1), vec!["DIRECT"]);
+        Ok(())
+    }
+
+    #[test]
+    fn suffix_renames_colliding_with_group_names() -> Result<(), String> {
+        let config = r#"
+proxies:
+  - {name: "PROXY [1x]", type: trojan, server: a.com, port: 443, password: pw}
+  - {name: "DIRECT [1x]", type: trojan, server: b.com, port: 443, password: pw}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: ["PROXY [1x]", "DIRECT [1x]"]}
+"#;
+        let pipeline = NodePipeline {
+            renames: vec![RenameRule {
+                pattern: r"\s*\[.*\]".to_string(),
+                replacement: String::new(),
+            }],
+            ..Default::default()
+        };
+
+        let output = pipeline.apply(config)?;
+        let names: Vec<_> = output.proxies.iter().map(|p| p.name()).collect();
+        assert_eq!(names, vec!["PROXY 2", "DIRECT 2"]);
+        Ok(())
+    }
+}
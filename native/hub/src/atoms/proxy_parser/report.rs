@@ -0,0 +1,198 @@
+// 订阅解析报告：记录输入格式、逐行解析结果与各协议节点数量。
+// 供 Dart 端展示被拒绝的链接及原因。
+
+use super::info_nodes::SubscriptionUsage;
+use crate::atoms::ProxyNode;
+use crate::atoms::proxy_node::{ParseError, ParseErrorKind};
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// 预览内容的最大字符数
+const PREVIEW_MAX_CHARS: usize = 80;
+
+// 订阅输入格式
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionFormat {
+    ClashYaml = 0,     // 完整 Clash 配置
+    ProxyList = 1,     // 仅含 proxies 的 YAML/JSON 列表
+    SingBoxJson = 2,   // sing-box JSON 配置
+    XrayJson = 3,      // Xray JSON 配置
+    WireGuardConf = 4, // wg-quick 配置文件
+    ProxyLinks = 5,    // 分享链接或 Surge/Loon/Quantumult X 节点行
+}
+
+// 单行解析结果
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineStatus {
+    Ok = 0,
+    UnsupportedScheme = 1, // 不支持的协议或无法映射的节点类型
+    MissingField = 2,      // 缺少必需字段
+    Invalid = 3,           // 其他格式错误
+}
+
+// 单行（或单个出站）解析结果
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct LineOutcome {
+    pub line_number: u32, // 行号（JSON 配置为出站序号），从 1 开始
+    pub preview: String,  // 行内容预览
+    pub status: LineStatus,
+    pub protocol: String, // 解析成功时的 mihomo 代理类型
+    pub message: String,  // 失败原因
+}
+
+// 协议节点数量
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct ProtocolCount {
+    pub protocol: String,
+    pub count: u32,
+}
+
+// 节点规范化变更类型
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeChangeKind {
+    Renamed = 0,          // 重名节点追加序号
+    DroppedDuplicate = 1, // 端点重复的节点被移除
+    Filtered = 2,         // 被包含/排除规则过滤
+    InfoNode = 3,         // 流量、到期等信息节点被移除
+    DanglingDialer = 4,   // dialer-proxy 指向不存在的节点或代理组，节点被移除
+}
+
+// 节点规范化变更记录
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct NodeChange {
+    pub kind: NodeChangeKind,
+    pub original_name: String,
+    pub new_name: String, // 重命名后的名称；去重时为保留的同端点节点名称，悬空引用时为引用目标，过滤时为空
+}
+
+// 订阅解析报告
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct ParseReport {
+    pub format: SubscriptionFormat,
+    pub is_base64: bool,
+    pub lines: Vec<LineOutcome>,
+    pub protocol_counts: Vec<ProtocolCount>,
+    pub proxy_count: u32,
+    pub rejected_count: u32,
+    pub node_changes: Vec<NodeChange>,
+    pub usage: Option<SubscriptionUsage>, // 从信息节点推导的订阅用量
+}
+
+impl LineOutcome {
+    pub(super) fn ok(line_number: usize, source: &str, proxy: &ProxyNode) -> Self {
+        Self {
+            line_number: line_number as u32,
+            preview: preview(source),
+            status: LineStatus::Ok,
+            protocol: proxy.type_name().to_string(),
+            message: String::new(),
+        }
+    }
+
+    pub(super) fn failed(line_number: usize, source: &str, error: &ParseError) -> Self {
+        Self {
+            line_number: line_number as u32,
+            preview: preview(source),
+            status: error.kind.into(),
+            protocol: String::new(),
+            message: error.message.clone(),
+        }
+    }
+}
+
+impl ParseReport {
+    pub(super) fn new(format: SubscriptionFormat, is_base64: bool) -> Self {
+        Self {
+            format,
+            is_base64,
+            lines: Vec::new(),
+            protocol_counts: Vec::new(),
+            proxy_count: 0,
+            rejected_count: 0,
+            node_changes: Vec::new(),
+            usage: None,
+        }
+    }
+
+    // 根据最终节点列表与逐行结果统计数量
+    pub(super) fn finish(&mut self, proxies: &[ProxyNode]) {
+        let mut counts = BTreeMap::new();
+        for proxy in proxies {
+            *counts.entry(proxy.type_name().to_string()).or_insert(0u32) += 1;
+        }
+
+        self.protocol_counts = counts
+            .into_iter()
+            .map(|(protocol, count)| ProtocolCount { protocol, count })
+            .collect();
+        self.proxy_count = proxies.len() as u32;
+        self.rejected_count = self
+            .lines
+            .iter()
+            .filter(|line| line.status != LineStatus::Ok)
+            .count() as u32;
+    }
+}
+
+// 根据解析错误类别归类失败原因
+impl From<ParseErrorKind> for LineStatus {
+    fn from(kind: ParseErrorKind) -> Self {
+        match kind {
+            ParseErrorKind::Unsupported => Self::UnsupportedScheme,
+            ParseErrorKind::MissingField => Self::MissingField,
+            ParseErrorKind::Invalid => Self::Invalid,
+        }
+    }
+}
+
+// 使用 chars 截断，避免 UTF-8 字符边界问题
+fn preview(source: &str) -> String {
+    let mut chars = source.chars();
+    let mut preview: String = chars.by_ref().take(PREVIEW_MAX_CHARS).collect();
+    if chars.next().is_some() {
+        preview.push('…');
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineStatus, SubscriptionFormat};
+    use crate::atoms::ProxyParser;
+    use crate::atoms::proxy_parser::ParseOptions;
+
+    #[test]
+    fn report_rejected_lines_and_protocol_counts() {
+        let content = [
+            "trojan://pw@a.example.com:443#A",
+            "trojan://pw@b.example.com:443#B",
+            "foo://bar",
+            "hy2://pw@c.example.com:443#C",
+            "SS = ss, example.com, 8388",
+        ]
+        .join("\n");
+
+        let (result, report) =
+            ProxyParser::parse_subscription_with_report(&content, &ParseOptions::default());
+        assert!(result.is_ok());
+        assert_eq!(report.format, SubscriptionFormat::ProxyLinks);
+        assert_eq!(report.proxy_count, 3);
+        assert_eq!(report.rejected_count, 2);
+
+        let statuses: Vec<_> = report
+            .lines
+            .iter()
+            .map(|l| (l.line_number, l.status))
+            .collect();
+        assert!(statuses.contains(&(3, LineStatus::UnsupportedScheme)));
+        assert!(statuses.contains(&(5, LineStatus::MissingField)));
+
+        let counts: Vec<_> = report
+            .protocol_counts
+            .iter()
+            .map(|c| (c.protocol.as_str(), c.count))
+            .collect();
+        assert_eq!(counts, vec![("hysteria2", 1), ("trojan", 2)]);
+    }
+}
@@ -0,0 +1,356 @@
+// Shadowsocks 解析器：支持 SIP002 与传统整体 Base64 链接、SIP003 插件及 SS2022 密钥校验。
+// 插件参数映射为 mihomo 的 plugin/plugin-opts。
+
+use super::ProxyParser;
+use crate::atoms::proxy_node::ParseError;
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use serde_json::{Value as JsonValue, json};
+
+impl ProxyParser {
+    // 解析 Shadowsocks 链接
+    // SIP002：ss://base64url(method:password)@server:port/?plugin=...#name
+    //         ss://method:password@server:port#name（百分号编码，SS2022 推荐写法）
+    // 传统：  ss://base64(method:password@server:port)#name
+    pub(super) fn parse_shadowsocks(link: &str) -> Result<JsonValue, ParseError> {
+        let link = link.strip_prefix("ss://").ok_or("无效的 SS 链接")?;
+
+        let (body, name_part) = link.split_once('#').unwrap_or((link, "Shadowsocks"));
+        let (body, query) = body.split_once('?').unwrap_or((body, ""));
+        let body = body.trim_end_matches('/');
+
+        // 传统格式整体 Base64 编码，不含 @
+        let body = if body.contains('@') {
+            body.to_string()
+        } else {
+            decode_base64_text(body)?
+        };
+
+        let (auth_part, server_port) = body
+            .rsplit_once('@')
+            .ok_or_else(|| ParseError::missing("SS 链接格式错误：缺少 @"))?;
+
+        // 解析认证部分
+        let auth_part = Self::url_decode(auth_part);
+        let decoded_auth = if auth_part.contains(':') {
+            auth_part
+        } else {
+            decode_base64_text(&auth_part)?
+        };
+
+        let (method, password) = decoded_auth.split_once(':').ok_or("SS 认证格式错误")?;
+        validate_ss2022_key(method, password)?;
+
+        // 解析服务器和端口
+        let (server, port_str) = server_port
+            .rsplit_once(':')
+            .ok_or_else(|| ParseError::missing("SS 链接格式错误：缺少端口"))?;
+        let server = server.trim_start_matches('[').trim_end_matches(']');
+        let port = port_str.parse::<i64>().map_err(|_| "端口解析失败")?;
+
+        let name = Self::url_decode(name_part);
+
+        let mut proxy = json!({
+            "name": name,
+            "type": "ss",
+            "server": server,
+            "port": port,
+            "cipher": method,
+            "password": password,
+            "udp": true,
+        });
+
+        let params = Self::parse_query_params(query);
+        if let Some(plugin) = params.get("plugin").filter(|p| !p.is_empty()) {
+            let (plugin_name, plugin_opts) = parse_plugin(plugin)?;
+            proxy["plugin"] = json!(plugin_name);
+            proxy["plugin-opts"] = plugin_opts;
+        }
+
+        Ok(proxy)
+    }
+}
+
+// 将 SIP003 插件字符串转换为 mihomo plugin 与 plugin-opts。
+// 示例：obfs-local;obfs=http;obfs-host=example.com
+pub(super) fn parse_plugin(plugin: &str) -> Result<(&'static str, JsonValue), ParseError> {
+    let mut options = split_plugin_options(plugin).into_iter();
+    let plugin_name = options.next().map(|(name, _)| name).unwrap_or_default();
+    let options: Vec<(String, Option<String>)> = options.collect();
+
+    let get = |key: &str| {
+        options
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.clone())
+    };
+    let has_flag = |key: &str| options.iter().any(|(k, _)| k == key);
+
+    match plugin_name.as_str() {
+        "obfs-local" | "simple-obfs" | "obfs" => {
+            let mode = get("obfs").ok_or_else(|| ParseError::missing("obfs 插件缺少 obfs 参数"))?;
+            let mut opts = json!({ "mode": mode });
+            if let Some(host) = get("obfs-host") {
+                opts["host"] = json!(host);
+            }
+            Ok(("obfs", opts))
+        }
+        "v2ray-plugin" => {
+            let mut opts = json!({
+                "mode": get("mode").unwrap_or_else(|| "websocket".to_string()),
+            });
+            if has_flag("tls") {
+                opts["tls"] = json!(true);
+            }
+            if let Some(host) = get("host") {
+                opts["host"] = json!(host);
+            }
+            if let Some(path) = get("path") {
+                opts["path"] = json!(path);
+            }
+            if let Some(mux) = get("mux") {
+                opts["mux"] = json!(mux != "0");
+            }
+            Ok(("v2ray-plugin", opts))
+        }
+        "shadow-tls" => {
+            let host =
+                get("host").ok_or_else(|| ParseError::missing("shadow-tls 插件缺少 host 参数"))?;
+            let password = get("password")
+                .ok_or_else(|| ParseError::missing("shadow-tls 插件缺少 password 参数"))?;
+            let version = get("version")
+                .map(|v| v.parse::<i64>().map_err(|_| "shadow-tls version 解析失败"))
+                .transpose()?
+                .unwrap_or(2);
+            Ok((
+                "shadow-tls",
+                json!({ "host": host, "password": password, "version": version }),
+            ))
+        }
+        "restls" => {
+            let host =
+                get("host").ok_or_else(|| ParseError::missing("restls 插件缺少 host 参数"))?;
+            let password = get("password")
+                .ok_or_else(|| ParseError::missing("restls 插件缺少 password 参数"))?;
+            let mut opts = json!({ "host": host, "password": password });
+            if let Some(version_hint) = get("version-hint") {
+                opts["version-hint"] = json!(version_hint);
+            }
+            if let Some(script) = get("restls-script") {
+                opts["restls-script"] = json!(script);
+            }
+            Ok(("restls", opts))
+        }
+        other => Err(ParseError::unsupported(format!(
+            "不支持的 SS 插件：{}",
+            other
+        ))),
+    }
+}
+
+// 将 mihomo plugin/plugin-opts 转换回 SIP003 插件字符串
+pub(super) fn plugin_to_string(plugin: &str, opts: &JsonValue) -> Result<String, String> {
+    let text = |key: &str| opts[key].as_str().map(escape_plugin_value);
+    let mut parts = Vec::new();
+
+    match plugin {
+        "obfs" => {
+            parts.push("obfs-local".to_string());
+            if let Some(mode) = text("mode") {
+                parts.push(format!("obfs={}", mode));
+            }
+            if let Some(host) = text("host") {
+                parts.push(format!("obfs-host={}", host));
+            }
+        }
+        "v2ray-plugin" => {
+            parts.push("v2ray-plugin".to_string());
+            parts.push(format!("mode={}", text("mode").unwrap_or_default()));
+            if opts["tls"].as_bool() == Some(true) {
+                parts.push("tls".to_string());
+            }
+            if let Some(host) = text("host") {
+                parts.push(format!("host={}", host));
+            }
+            if let Some(path) = text("path") {
+                parts.push(format!("path={}", path));
+            }
+            if let Some(mux) = opts["mux"].as_bool() {
+                parts.push(format!("mux={}", if mux { 1 } else { 0 }));
+            }
+        }
+        "shadow-tls" | "restls" => {
+            parts.push(plugin.to_string());
+            for key in ["host", "password", "version-hint", "restls-script"] {
+                if let Some(value) = text(key) {
+                    parts.push(format!("{}={}", key, value));
+                }
+            }
+            if let Some(version) = opts["version"].as_i64() {
+                parts.push(format!("version={}", version));
+            }
+        }
+        other => return Err(format!("不支持导出的 SS 插件：{}", other)),
+    }
+
+    Ok(parts.join(";"))
+}
+
+// 按未转义的 ; 与 = 拆分插件参数（SIP003 使用反斜杠转义）
+fn split_plugin_options(plugin: &str) -> Vec<(String, Option<String>)> {
+    let mut options = Vec::new();
+    let mut key = String::new();
+    let mut value: Option<String> = None;
+    let mut chars = plugin.chars();
+
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '\\' => match chars.next() {
+                Some(escaped) => escaped,
+                None => continue,
+            },
+            ';' => {
+                options.push((key.trim().to_string(), value.take()));
+                key.clear();
+                continue;
+            }
+            '=' if value.is_none() => {
+                value = Some(String::new());
+                continue;
+            }
+            c => c,
+        };
+
+        match value.as_mut() {
+            Some(value) => value.push(c),
+            None => key.push(c),
+        }
+    }
+
+    if !key.trim().is_empty() || value.is_some() {
+        options.push((key.trim().to_string(), value));
+    }
+
+    options
+}
+
+fn escape_plugin_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace('=', "\\=")
+}
+
+// 校验 SS2022 密钥：每段 PSK 的 Base64 解码长度必须与加密方式匹配
+// 多用户写法为 "服务端PSK:用户PSK"
+pub(super) fn validate_ss2022_key(method: &str, password: &str) -> Result<(), String> {
+    let key_len = match method {
+        "2022-blake3-aes-128-gcm" => 16,
+        "2022-blake3-aes-256-gcm" | "2022-blake3-chacha20-poly1305" => 32,
+        _ => return Ok(()),
+    };
+
+    for psk in password.split(':') {
+        let decoded =
+            decode_base64(psk).map_err(|_| format!("SS2022 密钥不是有效的 Base64：{}", method))?;
+        if decoded.len() != key_len {
+            return Err(format!(
+                "SS2022 密钥长度无效：{} 需要 {} 字节密钥，实际为 {} 字节",
+                method,
+                key_len,
+                decoded.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// 兼容标准/URL 安全、有无填充的 Base64
+pub(super) fn decode_base64(s: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    let s = s.trim();
+    STANDARD
+        .decode(s)
+        .or_else(|_| STANDARD_NO_PAD.decode(s))
+        .or_else(|_| URL_SAFE.decode(s))
+        .or_else(|_| URL_SAFE_NO_PAD.decode(s))
+}
+
+fn decode_base64_text(s: &str) -> Result<String, String> {
+    let decoded = decode_base64(s).map_err(|e| format!("Base64 解码失败：{}", e))?;
+    String::from_utf8(decoded).map_err(|e| format!("UTF-8 转换失败：{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyParser;
+    use crate::atoms::proxy_node::ParseError;
+    use serde_json::json;
+
+    #[test]
+    fn parse_sip002_plugins() -> Result<(), String> {
+        let obfs = ProxyParser::parse_single_proxy(
+            "ss://YWVzLTI1Ni1nY206cGFzc3dvcmQ@example.com:8388/?plugin=obfs-local%3Bobfs%3Dhttp%3Bobfs-host%3Dbing.com#OBFS",
+        )?;
+        assert_eq!(obfs["plugin"], "obfs");
+        assert_eq!(
+            obfs["plugin-opts"],
+            json!({"mode": "http", "host": "bing.com"})
+        );
+
+        let v2ray = ProxyParser::parse_single_proxy(
+            "ss://YWVzLTI1Ni1nY206cGFzc3dvcmQ@example.com:443?plugin=v2ray-plugin%3Bmode%3Dwebsocket%3Btls%3Bhost%3Dcdn.com%3Bpath%3D%2Fws#V2",
+        )?;
+        assert_eq!(
+            v2ray["plugin-opts"],
+            json!({"mode": "websocket", "tls": true, "host": "cdn.com", "path": "/ws"})
+        );
+
+        let shadow_tls = ProxyParser::parse_single_proxy(
+            "ss://YWVzLTI1Ni1nY206cGFzc3dvcmQ@example.com:443?plugin=shadow-tls%3Bhost%3Dcloud.tencent.com%3Bpassword%3Dpw%3Bversion%3D3#STLS",
+        )?;
+        assert_eq!(shadow_tls["plugin"], "shadow-tls");
+        assert_eq!(shadow_tls["plugin-opts"]["version"], 3);
+
+        let err = ProxyParser::parse_single_proxy(
+            "ss://YWVzLTI1Ni1nY206cGFzc3dvcmQ@example.com:443?plugin=kcptun#K",
+        )
+        .err();
+        assert_eq!(
+            err,
+            Some(ParseError::unsupported("不支持的 SS 插件：kcptun"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_legacy_base64_link() -> Result<(), String> {
+        // base64("aes-128-gcm:p@ss@example.com:8388")
+        let proxy = ProxyParser::parse_single_proxy(
+            "ss://YWVzLTEyOC1nY206cEBzc0BleGFtcGxlLmNvbTo4Mzg4#Old",
+        )?;
+        assert_eq!(proxy["cipher"], "aes-128-gcm");
+        assert_eq!(proxy["password"], "p@ss");
+        assert_eq!(proxy["server"], "example.com");
+        assert_eq!(proxy["port"], 8388);
+        Ok(())
+    }
+
+    #[test]
+    fn validate_ss2022_key_length() -> Result<(), String> {
+        let valid = ProxyParser::parse_single_proxy(
+            "ss://2022-blake3-aes-128-gcm:AAAAAAAAAAAAAAAAAAAAAA%3D%3D@example.com:443#OK",
+        )?;
+        assert_eq!(valid["password"], "AAAAAAAAAAAAAAAAAAAAAA==");
+
+        let err = ProxyParser::parse_single_proxy(
+            "ss://2022-blake3-aes-256-gcm:AAAAAAAAAAAAAAAAAAAAAA%3D%3D@example.com:443#Bad",
+        )
+        .err();
+        assert_eq!(
+            err.map(|e| e.message).as_deref(),
+            Some("SS2022 密钥长度无效：2022-blake3-aes-256-gcm 需要 32 字节密钥，实际为 16 字节")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+// 传输层与 TLS 参数映射：将 Xray/V2Ray 分享链接参数转换为 mihomo 的 *-opts 结构。
+// VLESS、VMess、Trojan 共用同一套参数名（VMess 的 JSON 字段先转换为同名参数）。
+
+use super::ProxyParser;
+use crate::atoms::proxy_node::ParseError;
+use serde_json::{Value as JsonValue, json};
+use std::collections::HashMap;
+
+// WebSocket 早期数据使用的请求头
+const EARLY_DATA_HEADER_NAME: &str = "Sec-WebSocket-Protocol";
+
+impl ProxyParser {
+    // 应用 TLS 参数（security/sni/fp/alpn/allowInsecure/pbk/sid）。
+    // implicit_tls 为 true 时（Trojan）默认启用 TLS，不写入 tls 字段且 SNI 写入 sni。
+    pub(super) fn apply_tls_params(
+        proxy: &mut JsonValue,
+        params: &HashMap<String, String>,
+        implicit_tls: bool,
+    ) {
+        let default_security = if implicit_tls { "tls" } else { "none" };
+        let security = params
+            .get("security")
+            .map(String::as_str)
+            .filter(|s| !s.is_empty())
+            .unwrap_or(default_security);
+
+        let is_insecure = ["allowInsecure", "insecure"]
+            .iter()
+            .filter_map(|key| params.get(*key))
+            .any(|value| is_truthy(value));
+        proxy["skip-cert-verify"] = json!(is_insecure);
+
+        if security != "tls" && security != "reality" {
+            return;
+        }
+
+        if !implicit_tls {
+            proxy["tls"] = json!(true);
+        }
+
+        let sni_key = if implicit_tls { "sni" } else { "servername" };
+        if let Some(sni) = non_empty(params, "sni").or_else(|| non_empty(params, "peer")) {
+            proxy[sni_key] = json!(sni);
+        }
+
+        if let Some(fingerprint) = non_empty(params, "fp") {
+            proxy["client-fingerprint"] = json!(fingerprint);
+        }
+
+        if let Some(alpn) = non_empty(params, "alpn") {
+            proxy["alpn"] = json!(split_list(alpn));
+        }
+
+        if security == "reality" {
+            proxy["reality-opts"] = json!({
+                "public-key": params.get("pbk").cloned().unwrap_or_default(),
+                "short-id": params.get("sid").cloned().unwrap_or_default(),
+            });
+        }
+    }
+
+    // 应用传输层参数（type/headerType/host/path/serviceName/mode）。
+    // mihomo 不支持的传输（如 kcp、quic）返回错误，避免生成无法连接的节点。
+    pub(super) fn apply_transport_params(
+        proxy: &mut JsonValue,
+        params: &HashMap<String, String>,
+    ) -> Result<(), ParseError> {
+        let network = non_empty(params, "type").unwrap_or("tcp");
+        let host = non_empty(params, "host");
+        let path = non_empty(params, "path");
+
+        match network {
+            "tcp" | "raw" => {
+                if non_empty(params, "headerType") == Some("http") {
+                    let mut http_opts = json!({
+                        "method": "GET",
+                        "path": [path.unwrap_or("/")],
+                    });
+                    if let Some(host) = host {
+                        http_opts["headers"] = json!({ "Host": split_list(host) });
+                    }
+                    proxy["network"] = json!("http");
+                    proxy["http-opts"] = http_opts;
+                } else {
+                    proxy["network"] = json!("tcp");
+                }
+            }
+            "ws" | "httpupgrade" => {
+                let (path, early_data) = split_early_data(path.unwrap_or("/"));
+                let mut ws_opts = json!({ "path": path });
+                if let Some(host) = host {
+                    ws_opts["headers"] = json!({ "Host": host });
+                }
+                if let Some(early_data) = early_data {
+                    ws_opts["max-early-data"] = json!(early_data);
+                    ws_opts["early-data-header-name"] = json!(EARLY_DATA_HEADER_NAME);
+                }
+                if network == "httpupgrade" {
+                    ws_opts["v2ray-http-upgrade"] = json!(true);
+                }
+                proxy["network"] = json!("ws");
+                proxy["ws-opts"] = ws_opts;
+            }
+            "http" | "h2" => {
+                let mut h2_opts = json!({ "path": path.unwrap_or("/") });
+                if let Some(host) = host {
+                    h2_opts["host"] = json!(split_list(host));
+                }
+                proxy["network"] = json!("h2");
+                proxy["h2-opts"] = h2_opts;
+            }
+            "grpc" => {
+                proxy["network"] = json!("grpc");
+                proxy["grpc-opts"] = json!({
+                    "grpc-service-name": params.get("serviceName").cloned().unwrap_or_default(),
+                });
+            }
+            "xhttp" | "splithttp" => {
+                let mut xhttp_opts = json!({ "path": path.unwrap_or("/") });
+                if let Some(host) = host {
+                    xhttp_opts["host"] = json!(host);
+                }
+                if let Some(mode) = non_empty(params, "mode") {
+                    xhttp_opts["mode"] = json!(mode);
+                }
+                proxy["network"] = json!("xhttp");
+                proxy["xhttp-opts"] = xhttp_opts;
+            }
+            other => {
+                return Err(ParseError::unsupported(format!(
+                    "mihomo 不支持 {} 传输",
+                    other
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// 拆分 WebSocket 路径中的早期数据参数（如 /ws?ed=2048）
+fn split_early_data(path: &str) -> (&str, Option<i64>) {
+    if let Some((base, ed)) = path.split_once("?ed=")
+        && let Ok(early_data) = ed.parse::<i64>()
+    {
+        return (base, Some(early_data));
+    }
+
+    (path, None)
+}
+
+fn non_empty<'a>(params: &'a HashMap<String, String>, key: &str) -> Option<&'a str> {
+    params
+        .get(key)
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+}
+
+pub(super) fn is_truthy(value: &str) -> bool {
+    value == "1" || value.eq_ignore_ascii_case("true")
+}
+
+fn split_list(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyParser;
+    use crate::atoms::proxy_node::ParseError;
+    use serde_json::json;
+
+    const UUID: &str = "b831381d-6324-4d53-ad4f-8cda48b30811";
+
+    fn vless(query: &str) -> Result<serde_json::Value, ParseError> {
+        ProxyParser::parse_single_proxy(&format!("vless://{}@example.com:443?{}#n", UUID, query))
+    }
+
+    #[test]
+    fn map_vless_transports() -> Result<(), String> {
+        let tcp_http = vless("type=tcp&headerType=http&host=a.com,b.com&path=%2Findex")?;
+        assert_eq!(tcp_http["network"], "http");
+        assert_eq!(tcp_http["http-opts"]["path"], json!(["/index"]));
+        assert_eq!(
+            tcp_http["http-opts"]["headers"]["Host"],
+            json!(["a.com", "b.com"])
+        );
+
+        let ws = vless("type=ws&path=%2Fws%3Fed%3D2048&host=cdn.com")?;
+        assert_eq!(ws["ws-opts"]["path"], "/ws");
+        assert_eq!(ws["ws-opts"]["max-early-data"], 2048);
+        assert_eq!(ws["ws-opts"]["headers"]["Host"], "cdn.com");
+
+        let upgrade = vless("type=httpupgrade&path=%2Fup")?;
+        assert_eq!(upgrade["network"], "ws");
+        assert_eq!(upgrade["ws-opts"]["v2ray-http-upgrade"], true);
+
+        let h2 = vless("type=http&path=%2Fh2&host=h2.com&security=tls")?;
+        assert_eq!(h2["network"], "h2");
+        assert_eq!(h2["h2-opts"], json!({"path": "/h2", "host": ["h2.com"]}));
+
+        let grpc = vless("type=grpc&serviceName=svc&mode=multi")?;
+        assert_eq!(grpc["grpc-opts"]["grpc-service-name"], "svc");
+
+        let xhttp = vless("type=xhttp&path=%2Fx&host=x.com&mode=stream-one")?;
+        assert_eq!(
+            xhttp["xhttp-opts"],
+            json!({"path": "/x", "host": "x.com", "mode": "stream-one"})
+        );
+
+        assert_eq!(
+            vless("type=kcp").err(),
+            Some(ParseError::unsupported("mihomo 不支持 kcp 传输"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn map_vless_tls_and_reality_options() -> Result<(), String> {
+        let tls = vless(
+            "security=tls&sni=a.com&fp=chrome&alpn=h2,http%2F1.1&allowInsecure=1&encryption=none&packetEncoding=xudp",
+        )?;
+        assert_eq!(tls["tls"], true);
+        assert_eq!(tls["servername"], "a.com");
+        assert_eq!(tls["client-fingerprint"], "chrome");
+        assert_eq!(tls["alpn"], json!(["h2", "http/1.1"]));
+        assert_eq!(tls["skip-cert-verify"], true);
+        assert_eq!(tls["packet-encoding"], "xudp");
+        assert!(tls.get("encryption").is_none());
+
+        let reality = vless(
+            "security=reality&sni=www.apple.com&fp=safari&pbk=key&sid=ab&flow=xtls-rprx-vision&encryption=mlkem768x25519plus.native.0rtt.abc",
+        )?;
+        assert_eq!(
+            reality["reality-opts"],
+            json!({"public-key": "key", "short-id": "ab"})
+        );
+        assert_eq!(reality["flow"], "xtls-rprx-vision");
+        assert_eq!(reality["encryption"], "mlkem768x25519plus.native.0rtt.abc");
+        Ok(())
+    }
+
+    #[test]
+    fn map_vmess_json_fields() -> Result<(), String> {
+        use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+
+        let data = json!({
+            "v": "2", "ps": "VMess H2", "add": "example.com", "port": 443,
+            "id": UUID, "aid": 0, "net": "h2", "type": "none",
+            "host": "h2.com", "path": "/h2", "tls": "tls",
+            "sni": "h2.com", "fp": "firefox", "alpn": "h2",
+        });
+        let link = format!("vmess://{}", BASE64.encode(data.to_string()));
+
+        let proxy = ProxyParser::parse_single_proxy(&link)?;
+        assert_eq!(proxy["port"], 443);
+        assert_eq!(proxy["network"], "h2");
+        assert_eq!(proxy["h2-opts"]["host"], json!(["h2.com"]));
+        assert_eq!(proxy["servername"], "h2.com");
+        assert_eq!(proxy["client-fingerprint"], "firefox");
+        assert_eq!(proxy["alpn"], json!(["h2"]));
+        Ok(())
+    }
+
+    #[test]
+    fn map_trojan_defaults_to_tls() -> Result<(), String> {
+        let proxy = ProxyParser::parse_single_proxy(
+            "trojan://pw@example.com:443?peer=a.com&fp=chrome&type=grpc&serviceName=svc#T",
+        )?;
+        assert_eq!(proxy["sni"], "a.com");
+        assert_eq!(proxy["client-fingerprint"], "chrome");
+        assert_eq!(proxy["network"], "grpc");
+        assert!(proxy.get("tls").is_none());
+        Ok(())
+    }
+}
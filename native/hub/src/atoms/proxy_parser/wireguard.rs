@@ -0,0 +1,389 @@
+// WireGuard 解析器：支持 wireguard:// 链接与 wg-quick INI 配置文件。
+// 输出为 mihomo 的 type: wireguard 代理节点。
+
+use super::ProxyParser;
+use crate::atoms::proxy_node::ParseError;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use serde_json::{Value as JsonValue, json};
+use url::Url;
+
+const DEFAULT_ALLOWED_IPS: [&str; 2] = ["0.0.0.0/0", "::/0"];
+
+// wg-quick 配置中的单个 Peer
+#[derive(Default)]
+struct WireGuardPeer {
+    public_key: Option<String>,
+    pre_shared_key: Option<String>,
+    endpoint: Option<String>,
+    allowed_ips: Vec<String>,
+    reserved: Option<String>,
+}
+
+impl ProxyParser {
+    // 解析 WireGuard 链接
+    // wireguard://私钥@server:port?publickey=...&address=...&reserved=...&mtu=...#name
+    pub(super) fn parse_wireguard(link: &str) -> Result<JsonValue, ParseError> {
+        let link = match link.strip_prefix("wg://") {
+            Some(rest) => format!("wireguard://{}", rest),
+            None => link.to_string(),
+        };
+        let url = Url::parse(&link).map_err(|e| format!("URL 解析失败：{}", e))?;
+
+        let private_key = Self::url_decode(url.username());
+        let server = url
+            .host_str()
+            .ok_or_else(|| ParseError::missing("缺少服务器地址"))?
+            .to_string();
+        let port = url.port().ok_or_else(|| ParseError::missing("缺少端口"))? as i64;
+
+        let params = Self::parse_query_params(url.query().unwrap_or(""));
+        let name = Self::url_decode(url.fragment().unwrap_or("WireGuard"));
+
+        let param = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| params.get(*key))
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+
+        let public_key = param(&["publickey", "public_key", "peer_public_key"])
+            .ok_or_else(|| ParseError::missing("缺少 publickey 参数"))?;
+        validate_key(&private_key, "私钥")?;
+        validate_key(&public_key, "公钥")?;
+
+        let mut proxy = json!({
+            "name": name,
+            "type": "wireguard",
+            "server": server,
+            "port": port,
+            "private-key": private_key,
+            "public-key": public_key,
+            "udp": true,
+        });
+
+        if let Some(psk) = param(&["presharedkey", "pre_shared_key", "psk"]) {
+            validate_key(&psk, "预共享密钥")?;
+            proxy["pre-shared-key"] = json!(psk);
+        }
+
+        if let Some(address) = param(&["address", "ip"]) {
+            apply_addresses(&mut proxy, address.split(','));
+        }
+
+        let allowed_ips = param(&["allowedips", "allowed_ips"])
+            .map(|ips| split_list(&ips))
+            .unwrap_or_else(default_allowed_ips);
+        proxy["allowed-ips"] = json!(allowed_ips);
+
+        if let Some(reserved) = param(&["reserved"]) {
+            proxy["reserved"] = json!(parse_reserved(&reserved)?);
+        }
+
+        if let Some(mtu) = param(&["mtu"]) {
+            let mtu = mtu.parse::<i64>().map_err(|_| "MTU 解析失败")?;
+            proxy["mtu"] = json!(mtu);
+        }
+
+        Ok(proxy)
+    }
+
+    // 判断是否为 wg-quick 配置文件
+    pub(super) fn is_wireguard_conf(content: &str) -> bool {
+        let mut has_interface = false;
+        let mut has_peer = false;
+
+        for line in content.lines().map(str::trim) {
+            if line.eq_ignore_ascii_case("[Interface]") {
+                has_interface = true;
+            } else if line.eq_ignore_ascii_case("[Peer]") {
+                has_peer = true;
+            }
+        }
+
+        has_interface && has_peer
+    }
+
+    // 解析 wg-quick 配置文件（[Interface] + 一个或多个 [Peer]）
+    // 单个 Peer 输出扁平字段，多个 Peer 输出 peers 列表
+    pub(super) fn parse_wireguard_conf(content: &str) -> Result<JsonValue, ParseError> {
+        let mut section = String::new();
+        let mut name = None;
+        let mut private_key = None;
+        let mut addresses = Vec::new();
+        let mut dns = Vec::new();
+        let mut mtu = None;
+        let mut keepalive = None;
+        let mut peers: Vec<WireGuardPeer> = Vec::new();
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            // 兼容部分导出工具写入的 "# Name = xxx" 注释
+            if let Some(comment) = line.strip_prefix('#').or_else(|| line.strip_prefix(';')) {
+                if let Some((key, value)) = comment.split_once('=')
+                    && key.trim().eq_ignore_ascii_case("name")
+                {
+                    name = Some(value.trim().to_string());
+                }
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                section = line[1..line.len() - 1].trim().to_ascii_lowercase();
+                if section == "peer" {
+                    peers.push(WireGuardPeer::default());
+                }
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim().to_string();
+
+            match section.as_str() {
+                "interface" => match key.as_str() {
+                    "privatekey" => private_key = Some(value),
+                    "address" => addresses.extend(split_list(&value)),
+                    "dns" => dns.extend(split_list(&value)),
+                    "mtu" => mtu = Some(value.parse::<i64>().map_err(|_| "MTU 解析失败")?),
+                    _ => {}
+                },
+                "peer" => {
+                    let Some(peer) = peers.last_mut() else {
+                        continue;
+                    };
+                    match key.as_str() {
+                        "publickey" => peer.public_key = Some(value),
+                        "presharedkey" => peer.pre_shared_key = Some(value),
+                        "endpoint" => peer.endpoint = Some(value),
+                        "allowedips" => peer.allowed_ips.extend(split_list(&value)),
+                        "reserved" => peer.reserved = Some(value),
+                        "persistentkeepalive" => {
+                            keepalive = value.parse::<i64>().ok();
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let private_key =
+            private_key.ok_or_else(|| ParseError::missing("[Interface] 缺少 PrivateKey"))?;
+        validate_key(&private_key, "私钥")?;
+
+        if peers.is_empty() {
+            return Err(ParseError::missing("缺少 [Peer] 配置"));
+        }
+
+        let mut proxy = json!({
+            "name": name.unwrap_or_else(|| "WireGuard".to_string()),
+            "type": "wireguard",
+            "private-key": private_key,
+            "udp": true,
+        });
+
+        apply_addresses(&mut proxy, addresses.iter().map(String::as_str));
+
+        let peer_values = peers
+            .into_iter()
+            .map(peer_to_json)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let [peer] = peer_values.as_slice() {
+            if let (Some(proxy_map), Some(peer_map)) = (proxy.as_object_mut(), peer.as_object()) {
+                proxy_map.extend(peer_map.clone());
+            }
+        } else {
+            proxy["peers"] = json!(peer_values);
+        }
+
+        if let Some(mtu) = mtu {
+            proxy["mtu"] = json!(mtu);
+        }
+        if !dns.is_empty() {
+            proxy["dns"] = json!(dns);
+            proxy["remote-dns-resolve"] = json!(true);
+        }
+        if let Some(keepalive) = keepalive {
+            proxy["persistent-keepalive"] = json!(keepalive);
+        }
+
+        Ok(proxy)
+    }
+}
+
+// 将 [Peer] 转换为 mihomo peer 字段
+fn peer_to_json(peer: WireGuardPeer) -> Result<JsonValue, ParseError> {
+    let public_key = peer
+        .public_key
+        .ok_or_else(|| ParseError::missing("[Peer] 缺少 PublicKey"))?;
+    validate_key(&public_key, "公钥")?;
+
+    let endpoint = peer
+        .endpoint
+        .ok_or_else(|| ParseError::missing("[Peer] 缺少 Endpoint"))?;
+    let (server, port) = split_endpoint(&endpoint)?;
+
+    let allowed_ips = if peer.allowed_ips.is_empty() {
+        default_allowed_ips()
+    } else {
+        peer.allowed_ips
+    };
+
+    let mut value = json!({
+        "server": server,
+        "port": port,
+        "public-key": public_key,
+        "allowed-ips": allowed_ips,
+    });
+
+    if let Some(psk) = peer.pre_shared_key {
+        validate_key(&psk, "预共享密钥")?;
+        value["pre-shared-key"] = json!(psk);
+    }
+
+    if let Some(reserved) = peer.reserved {
+        value["reserved"] = json!(parse_reserved(&reserved)?);
+    }
+
+    Ok(value)
+}
+
+// 拆分 Endpoint（支持 [IPv6]:port）
+fn split_endpoint(endpoint: &str) -> Result<(String, i64), String> {
+    let (host, port) = endpoint
+        .rsplit_once(':')
+        .ok_or_else(|| format!("Endpoint 格式错误：{}", endpoint))?;
+    let port = port
+        .parse::<i64>()
+        .map_err(|_| format!("Endpoint 端口解析失败：{}", endpoint))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    Ok((host.to_string(), port))
+}
+
+// 将接口地址写入 ip/ipv6 字段（去掉前缀长度）
+fn apply_addresses<'a>(proxy: &mut JsonValue, addresses: impl Iterator<Item = &'a str>) {
+    for address in addresses {
+        let address = address.trim();
+        let ip = address.split('/').next().unwrap_or(address);
+        if ip.is_empty() {
+            continue;
+        }
+
+        let key = if ip.contains(':') { "ipv6" } else { "ip" };
+        if proxy.get(key).is_none() {
+            proxy[key] = json!(ip);
+        }
+    }
+}
+
+// 解析 reserved：支持 "1,2,3" 与 Base64（如 "AQID"）两种写法
+fn parse_reserved(value: &str) -> Result<Vec<u8>, String> {
+    let bytes = if value.contains(',') {
+        value
+            .split(',')
+            .map(|b| b.trim().parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("reserved 格式无效：{}", value))?
+    } else {
+        BASE64
+            .decode(value.trim())
+            .map_err(|_| format!("reserved 格式无效：{}", value))?
+    };
+
+    if bytes.len() != 3 {
+        return Err(format!("reserved 必须为 3 个字节：{}", value));
+    }
+
+    Ok(bytes)
+}
+
+// 校验 WireGuard 密钥（Base64 编码的 32 字节）
+fn validate_key(key: &str, label: &str) -> Result<(), String> {
+    match BASE64.decode(key.trim()) {
+        Ok(bytes) if bytes.len() == 32 => Ok(()),
+        _ => Err(format!("WireGuard {}格式无效", label)),
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn default_allowed_ips() -> Vec<String> {
+    DEFAULT_ALLOWED_IPS.iter().map(|s| s.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyParser;
+    use crate::atoms::proxy_node::ParseError;
+    use serde_json::json;
+
+    const PRIVATE_KEY: &str = "yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=";
+    const PUBLIC_KEY: &str = "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=";
+    const PSK: &str = "HIgo9xNzJMWLKASShiTqIybxZ0U3wGLiUeJ1PKf8ykw=";
+
+    #[test]
+    fn parse_wireguard_link() -> Result<(), String> {
+        let link = format!(
+            "wireguard://{}@162.159.192.1:2408?publickey={}&address=172.16.0.2/32,fd01::2/128&reserved=1,2,3&mtu=1280#WARP",
+            urlencoding::encode(PRIVATE_KEY),
+            urlencoding::encode(PUBLIC_KEY),
+        );
+
+        let proxy = ProxyParser::parse_wireguard(&link)?;
+
+        assert_eq!(proxy["name"], "WARP");
+        assert_eq!(proxy["private-key"], PRIVATE_KEY);
+        assert_eq!(proxy["public-key"], PUBLIC_KEY);
+        assert_eq!(proxy["ip"], "172.16.0.2");
+        assert_eq!(proxy["ipv6"], "fd01::2");
+        assert_eq!(proxy["reserved"], json!([1, 2, 3]));
+        assert_eq!(proxy["mtu"], 1280);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_wg_quick_conf_with_single_peer() -> Result<(), String> {
+        let conf = format!(
+            "[Interface]\nPrivateKey = {}\nAddress = 10.0.0.2/32, fd00::2/128\nDNS = 1.1.1.1\nMTU = 1420\n\n[Peer]\nPublicKey = {}\nPresharedKey = {}\nAllowedIPs = 0.0.0.0/0\nEndpoint = [2001:db8::1]:51820\nPersistentKeepalive = 25\n",
+            PRIVATE_KEY, PUBLIC_KEY, PSK
+        );
+
+        assert!(ProxyParser::is_wireguard_conf(&conf));
+        let proxy = ProxyParser::parse_wireguard_conf(&conf)?;
+
+        assert_eq!(proxy["server"], "2001:db8::1");
+        assert_eq!(proxy["port"], 51820);
+        assert_eq!(proxy["pre-shared-key"], PSK);
+        assert_eq!(proxy["allowed-ips"], json!(["0.0.0.0/0"]));
+        assert_eq!(proxy["ip"], "10.0.0.2");
+        assert_eq!(proxy["dns"], json!(["1.1.1.1"]));
+        assert_eq!(proxy["persistent-keepalive"], 25);
+        assert!(proxy.get("peers").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn reject_invalid_wireguard_key() {
+        let link = format!(
+            "wireguard://short@example.com:51820?publickey={}#bad",
+            urlencoding::encode(PUBLIC_KEY)
+        );
+
+        let err = ProxyParser::parse_wireguard(&link).err();
+        assert_eq!(err, Some(ParseError::invalid("WireGuard 私钥格式无效")));
+    }
+}
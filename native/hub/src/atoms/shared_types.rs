@@ -19,3 +19,19 @@ pub struct OverrideConfig {
     pub format: OverrideFormat,
     pub content: String,
 }
+
+// JavaScript 覆写沙箱限制（每个覆写脚本独立计算）
+#[derive(Debug, Deserialize, Serialize, SignalPiece, Clone, Copy)]
+pub struct JsSandboxLimits {
+    pub timeout_ms: u64,         // 执行时限（毫秒）
+    pub memory_limit_bytes: u64, // 堆内存上限（字节）
+}
+
+impl Default for JsSandboxLimits {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 3_000,
+            memory_limit_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
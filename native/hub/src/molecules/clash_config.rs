@@ -1,10 +1,12 @@
 // Clash 配置管理分子模块
 
+pub mod aggregator;
 pub mod chain_proxy;
 pub mod generator;
 pub mod injector;
 pub mod runtime_params;
 
+pub use aggregator::{AggregationConfig, AggregationSource};
 pub use chain_proxy::{
     BuildChainProxyConfigRequest, BuildChainProxyConfigResponse, ChainProxyCustomConfig,
 };
@@ -0,0 +1,406 @@
+// 多订阅聚合：合并多个已解析订阅的节点，按来源添加名称前缀，
+// 生成每个来源的选择组与全部节点组，并以主来源的规则、DNS 等配置为模板。
+
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::{Mapping, Value as YamlValue};
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_ALL_NODES_GROUP_NAME: &str = "ALL";
+const DEFAULT_MAIN_GROUP_NAME: &str = "PROXY";
+
+// 聚合来源
+#[derive(Debug, Clone, Serialize, Deserialize, SignalPiece)]
+pub struct AggregationSource {
+    pub name: String,                // 来源名称，同时作为该来源的选择组名
+    pub config_content: String,      // 已解析的 Clash 配置
+    pub name_prefix: Option<String>, // 节点名前缀，为空时使用“来源名称 | ”
+}
+
+// 聚合设置
+#[derive(Debug, Clone, Serialize, Deserialize, SignalPiece)]
+pub struct AggregationConfig {
+    pub sources: Vec<AggregationSource>,
+    pub primary_source_index: u32, // 提供规则、DNS 及其余配置的来源
+    pub all_nodes_group_name: Option<String>, // 为空时使用 ALL
+}
+
+// 合并各来源，返回聚合后的配置内容
+pub fn aggregate_subscriptions(config: &AggregationConfig) -> Result<String, String> {
+    if config.sources.is_empty() {
+        return Err("聚合订阅至少需要一个来源".to_string());
+    }
+    let primary_index = config.primary_source_index as usize;
+    if primary_index >= config.sources.len() {
+        return Err(format!(
+            "主来源索引 {} 超出范围（共 {} 个来源）",
+            primary_index,
+            config.sources.len()
+        ));
+    }
+
+    let mut roots = config
+        .sources
+        .iter()
+        .map(
+            |source| match serde_yaml_ng::from_str::<YamlValue>(&source.config_content) {
+                Ok(YamlValue::Mapping(root)) => Ok(root),
+                Ok(_) => Err(format!("来源 {} 的配置不是有效的 Clash 配置", source.name)),
+                Err(e) => Err(format!("解析来源 {} 的配置失败：{}", source.name, e)),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // 主来源的代理组名保持不变，节点名需避开这些名称
+    let mut primary = std::mem::take(&mut roots[primary_index]);
+    let mut primary_groups = take_sequence(&mut primary, "proxy-groups");
+    let mut used_names: HashSet<String> = primary_groups
+        .iter()
+        .filter_map(|group| string_field(group, "name"))
+        .map(str::to_string)
+        .collect();
+
+    let mut all_proxies = Vec::new();
+    let mut source_groups = Vec::new();
+    for (index, source) in config.sources.iter().enumerate() {
+        let is_primary = index == primary_index;
+        let root = if is_primary {
+            &mut primary
+        } else {
+            &mut roots[index]
+        };
+        if !is_primary && root.contains_key(yaml_key("proxy-providers")) {
+            log::warn!("来源 {} 的 proxy-providers 不会被聚合", source.name);
+        }
+
+        let prefix = source
+            .name_prefix
+            .clone()
+            .unwrap_or_else(|| format!("{} | ", source.name));
+        let mut proxies = take_sequence(root, "proxies");
+        // 按位置重命名，同一来源内的重名节点各自获得唯一名称；
+        // 原名对应的全部新名称按出现顺序记录，供引用同步使用
+        let mut renames: HashMap<String, Vec<String>> = HashMap::new();
+        let mut member_names = Vec::new();
+        for proxy in &mut proxies {
+            let Some(name) = string_field(proxy, "name").map(str::to_string) else {
+                continue;
+            };
+            let new_name = unique_name(&format!("{}{}", prefix, name), &mut used_names);
+            proxy.insert(yaml_key("name"), YamlValue::String(new_name.clone()));
+            renames.entry(name).or_default().push(new_name.clone());
+            member_names.push(new_name);
+        }
+        for proxy in &mut proxies {
+            rename_dialer_proxy(proxy, &renames, is_primary, &source.name);
+        }
+
+        if is_primary {
+            for group in &mut primary_groups {
+                rename_members(group, &renames);
+            }
+            if let Some(YamlValue::Sequence(rules)) = root.get_mut(yaml_key("rules")) {
+                for rule in rules {
+                    rename_rule_target(rule, &renames);
+                }
+            }
+        }
+
+        let group_name = unique_name(&source.name, &mut used_names);
+        source_groups.push((group_name, member_names));
+        all_proxies.extend(proxies);
+    }
+
+    let all_nodes_group_name = unique_name(
+        config
+            .all_nodes_group_name
+            .as_deref()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or(DEFAULT_ALL_NODES_GROUP_NAME),
+        &mut used_names,
+    );
+    let all_member_names: Vec<String> = source_groups
+        .iter()
+        .flat_map(|(_, members)| members.iter().cloned())
+        .collect();
+
+    // 聚合组加入主来源的首个选择组；主来源没有代理组时新建
+    let mut aggregated_group_names = vec![all_nodes_group_name.clone()];
+    aggregated_group_names.extend(source_groups.iter().map(|(name, _)| name.clone()));
+    match primary_groups
+        .iter_mut()
+        .find(|group| string_field(group, "type") == Some("select"))
+    {
+        Some(main_group) => append_members(main_group, &aggregated_group_names),
+        None => {
+            let main_group_name = unique_name(DEFAULT_MAIN_GROUP_NAME, &mut used_names);
+            primary_groups.insert(0, select_group(&main_group_name, &aggregated_group_names));
+            if !primary.contains_key(yaml_key("rules")) {
+                primary.insert(
+                    yaml_key("rules"),
+                    YamlValue::Sequence(vec![YamlValue::String(format!(
+                        "MATCH,{}",
+                        main_group_name
+                    ))]),
+                );
+            }
+        }
+    }
+
+    primary_groups.push(select_group(&all_nodes_group_name, &all_member_names));
+    for (name, members) in &source_groups {
+        primary_groups.push(select_group(name, members));
+    }
+
+    log::info!(
+        "已聚合 {} 个来源，共 {} 个节点",
+        config.sources.len(),
+        all_proxies.len()
+    );
+    primary.insert(
+        yaml_key("proxies"),
+        YamlValue::Sequence(all_proxies.into_iter().map(YamlValue::Mapping).collect()),
+    );
+    primary.insert(
+        yaml_key("proxy-groups"),
+        YamlValue::Sequence(primary_groups.into_iter().map(YamlValue::Mapping).collect()),
+    );
+
+    serde_yaml_ng::to_string(&YamlValue::Mapping(primary))
+        .map_err(|e| format!("序列化聚合配置失败：{}", e))
+}
+
+fn take_sequence(root: &mut Mapping, key: &str) -> Vec<Mapping> {
+    match root.remove(yaml_key(key)) {
+        Some(YamlValue::Sequence(items)) => items
+            .into_iter()
+            .filter_map(|item| match item {
+                YamlValue::Mapping(mapping) => Some(mapping),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// 同步 dialer-proxy 引用（重名时指向首个同名节点）；
+// 非主来源的代理组已被丢弃，指向代理组的引用需移除
+fn rename_dialer_proxy(
+    proxy: &mut Mapping,
+    renames: &HashMap<String, Vec<String>>,
+    is_primary: bool,
+    source_name: &str,
+) {
+    let Some(target) = string_field(proxy, "dialer-proxy").map(str::to_string) else {
+        return;
+    };
+    if let Some(new_target) = renames.get(&target).and_then(|names| names.first()) {
+        proxy.insert(
+            yaml_key("dialer-proxy"),
+            YamlValue::String(new_target.clone()),
+        );
+    } else if !is_primary {
+        log::warn!(
+            "来源 {} 的节点引用了未聚合的代理组 {}，已移除 dialer-proxy",
+            source_name,
+            target
+        );
+        proxy.remove(yaml_key("dialer-proxy"));
+    }
+}
+
+// 成员引用重名节点时展开为全部同名节点
+fn rename_members(group: &mut Mapping, renames: &HashMap<String, Vec<String>>) {
+    if let Some(YamlValue::Sequence(members)) = group.get_mut(yaml_key("proxies")) {
+        *members = members
+            .iter()
+            .flat_map(
+                |member| match member.as_str().and_then(|name| renames.get(name)) {
+                    Some(new_names) => new_names
+                        .iter()
+                        .map(|name| YamlValue::String(name.clone()))
+                        .collect(),
+                    None => vec![member.clone()],
+                },
+            )
+            .collect();
+    }
+}
+
+// 规则目标为重名节点时指向首个同名节点
+fn rename_rule_target(rule: &mut YamlValue, renames: &HashMap<String, Vec<String>>) {
+    let Some(text) = rule.as_str() else {
+        return;
+    };
+    let mut parts: Vec<String> = text.split(',').map(str::to_string).collect();
+    // 目标为最后一项，no-resolve/src 等参数位于目标之后
+    let mut index = parts.len() - 1;
+    if index > 1 && matches!(parts[index].trim(), "no-resolve" | "src") {
+        index -= 1;
+    }
+    if let Some(new_target) = renames
+        .get(parts[index].trim())
+        .and_then(|names| names.first())
+    {
+        parts[index] = new_target.clone();
+        *rule = YamlValue::String(parts.join(","));
+    }
+}
+
+fn append_members(group: &mut Mapping, names: &[String]) {
+    let members = group
+        .entry(yaml_key("proxies"))
+        .or_insert_with(|| YamlValue::Sequence(Vec::new()));
+    if let YamlValue::Sequence(members) = members {
+        members.extend(names.iter().map(|name| YamlValue::String(name.clone())));
+    }
+}
+
+fn select_group(name: &str, members: &[String]) -> Mapping {
+    let mut group = Mapping::new();
+    group.insert(yaml_key("name"), YamlValue::String(name.to_string()));
+    group.insert(yaml_key("type"), YamlValue::String("select".to_string()));
+    let members = if members.is_empty() {
+        vec![YamlValue::String("DIRECT".to_string())]
+    } else {
+        members
+            .iter()
+            .map(|name| YamlValue::String(name.clone()))
+            .collect()
+    };
+    group.insert(yaml_key("proxies"), YamlValue::Sequence(members));
+    group
+}
+
+// 名称重复时追加序号
+fn unique_name(name: &str, used_names: &mut HashSet<String>) -> String {
+    let mut candidate = name.to_string();
+    let mut index = 2;
+    while used_names.contains(&candidate) {
+        candidate = format!("{} {}", name, index);
+        index += 1;
+    }
+    used_names.insert(candidate.clone());
+    candidate
+}
+
+fn string_field<'a>(mapping: &'a Mapping, key: &str) -> Option<&'a str> {
+    mapping.get(yaml_key(key)).and_then(|value| value.as_str())
+}
+
+fn yaml_key(key: &str) -> YamlValue {
+    YamlValue::String(key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AggregationConfig, AggregationSource, aggregate_subscriptions};
+
+    #[test]
+    fn merge_sources_with_prefixes_and_groups() -> Result<(), String> {
+        let primary = r#"
+dns: {enable: true}
+proxies:
+  - {name: HK, type: trojan, server: a.com, port: 443, password: pw}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: [HK]}
+rules:
+  - DOMAIN,example.com,HK
+  - IP-CIDR,1.1.1.1/32,HK,no-resolve
+  - MATCH,PROXY
+"#;
+        let secondary = r#"
+proxies:
+  - {name: HK, type: trojan, server: b.com, port: 443, password: pw}
+  - {name: JP, type: trojan, server: c.com, port: 443, password: pw, dialer-proxy: HK}
+proxy-groups:
+  - {name: Other, type: select, proxies: [HK, JP]}
+rules:
+  - MATCH,DIRECT
+"#;
+        let config = AggregationConfig {
+            sources: vec![
+                AggregationSource {
+                    name: "A".to_string(),
+                    config_content: primary.to_string(),
+                    name_prefix: None,
+                },
+                AggregationSource {
+                    name: "B".to_string(),
+                    config_content: secondary.to_string(),
+                    name_prefix: Some("[B] ".to_string()),
+                },
+            ],
+            primary_source_index: 0,
+            all_nodes_group_name: None,
+        };
+
+        let merged: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(&aggregate_subscriptions(&config)?)
+                .map_err(|e| e.to_string())?;
+        let names: Vec<_> = merged["proxies"]
+            .as_sequence()
+            .ok_or("缺少 proxies")?
+            .iter()
+            .filter_map(|proxy| proxy["name"].as_str())
+            .collect();
+        assert_eq!(names, vec!["A | HK", "[B] HK", "[B] JP"]);
+        assert_eq!(merged["proxies"][2]["dialer-proxy"], "[B] HK");
+        assert_eq!(merged["dns"]["enable"], serde_yaml_ng::Value::Bool(true));
+        // 主来源规则指向的节点随重命名同步
+        assert_eq!(merged["rules"][0], "DOMAIN,example.com,A | HK");
+        assert_eq!(merged["rules"][1], "IP-CIDR,1.1.1.1/32,A | HK,no-resolve");
+        assert_eq!(merged["rules"][2], "MATCH,PROXY");
+
+        let groups = merged["proxy-groups"]
+            .as_sequence()
+            .ok_or("缺少 proxy-groups")?;
+        let group_names: Vec<_> = groups.iter().filter_map(|g| g["name"].as_str()).collect();
+        assert_eq!(group_names, vec!["PROXY", "ALL", "A", "B"]);
+        assert_eq!(groups[0]["proxies"].as_sequence().map(Vec::len), Some(4));
+        assert_eq!(groups[1]["proxies"].as_sequence().map(Vec::len), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn rename_duplicate_names_within_source() -> Result<(), String> {
+        let primary = r#"
+proxies:
+  - {name: HK, type: trojan, server: a.com, port: 443, password: pw}
+  - {name: HK, type: trojan, server: b.com, port: 443, password: pw}
+  - {name: JP, type: trojan, server: c.com, port: 443, password: pw, dialer-proxy: HK}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: [HK, JP]}
+"#;
+        let config = AggregationConfig {
+            sources: vec![AggregationSource {
+                name: "A".to_string(),
+                config_content: primary.to_string(),
+                name_prefix: None,
+            }],
+            primary_source_index: 0,
+            all_nodes_group_name: None,
+        };
+
+        let merged: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(&aggregate_subscriptions(&config)?)
+                .map_err(|e| e.to_string())?;
+        let names: Vec<_> = merged["proxies"]
+            .as_sequence()
+            .ok_or("缺少 proxies")?
+            .iter()
+            .filter_map(|proxy| proxy["name"].as_str())
+            .collect();
+        assert_eq!(names, vec!["A | HK", "A | HK 2", "A | JP"]);
+        assert_eq!(merged["proxies"][2]["dialer-proxy"], "A | HK");
+
+        let members: Vec<_> = merged["proxy-groups"][0]["proxies"]
+            .as_sequence()
+            .ok_or("缺少成员")?
+            .iter()
+            .filter_map(|member| member.as_str())
+            .collect();
+        assert_eq!(members, vec!["A | HK", "A | HK 2", "A | JP", "ALL", "A"]);
+        Ok(())
+    }
+}
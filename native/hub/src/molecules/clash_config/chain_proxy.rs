@@ -1,3 +1,4 @@
+use crate::atoms::ProxyNode;
 use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 use serde_yaml_ng::{Mapping, Value as YamlValue};
@@ -68,7 +69,7 @@ fn build_chain_proxy_config(
         });
     };
 
-    let proxies = extract_mapping_sequence(root, "proxies");
+    let proxies = extract_proxy_nodes(root)?;
     let proxy_groups = extract_mapping_sequence(root, "proxy-groups");
     let builtin_chain_proxy_names = collect_builtin_chain_proxy_names(&proxies);
     let filtered_proxies = filter_proxies(
@@ -87,12 +88,8 @@ fn build_chain_proxy_config(
 
     root.insert(
         yaml_key("proxies"),
-        YamlValue::Sequence(
-            filtered_proxies
-                .into_iter()
-                .map(YamlValue::Mapping)
-                .collect(),
-        ),
+        serde_yaml_ng::to_value(&filtered_proxies)
+            .map_err(|e| format!("序列化代理节点失败：{}", e))?,
     );
     root.insert(
         yaml_key("proxy-groups"),
@@ -125,36 +122,49 @@ fn extract_mapping_sequence(root: &Mapping, key: &str) -> Vec<Mapping> {
         .unwrap_or_default()
 }
 
-fn collect_builtin_chain_proxy_names(proxies: &[Mapping]) -> Vec<String> {
-    proxies
-        .iter()
-        .filter_map(|proxy| {
-            let dialer_proxy = string_field(proxy, "dialer-proxy")?;
-            if dialer_proxy.is_empty() {
-                return None;
-            }
+// 解析 proxies 为强类型节点（未建模的类型与字段类型不符的节点原样保留）
+fn extract_proxy_nodes(root: &Mapping) -> Result<Vec<ProxyNode>, String> {
+    let Some(items) = root
+        .get(yaml_key("proxies"))
+        .and_then(|value| value.as_sequence())
+    else {
+        return Ok(Vec::new());
+    };
 
-            let name = string_field(proxy, "name")?;
-            if name.is_empty() {
-                return None;
-            }
+    items
+        .iter()
+        .filter(|item| item.is_mapping())
+        .map(|item| {
+            ProxyNode::from_yaml_lenient(item).map_err(|e| format!("解析代理节点失败：{}", e))
+        })
+        .collect()
+}
 
-            Some(name.to_string())
+fn collect_builtin_chain_proxy_names(proxies: &[ProxyNode]) -> Vec<String> {
+    proxies
+        .iter()
+        .filter(|proxy| {
+            proxy
+                .common()
+                .dialer_proxy
+                .as_deref()
+                .is_some_and(|dialer_proxy| !dialer_proxy.is_empty())
         })
+        .map(ProxyNode::name)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
         .collect()
 }
 
 fn filter_proxies(
-    proxies: &[Mapping],
+    proxies: &[ProxyNode],
     builtin_chain_proxy_names: &[String],
     disabled_builtin_chain_proxy_names: &[String],
-) -> Vec<Mapping> {
+) -> Vec<ProxyNode> {
     proxies
         .iter()
         .filter(|proxy| {
-            let Some(name) = string_field(proxy, "name") else {
-                return true;
-            };
+            let name = proxy.name();
             if name.is_empty() {
                 return true;
             }
@@ -197,7 +207,7 @@ fn filter_proxy_groups(
 }
 
 fn build_runtime_relay_group(
-    proxies: &[Mapping],
+    proxies: &[ProxyNode],
     custom_proxy: &ChainProxyCustomConfig,
 ) -> Option<Mapping> {
     if custom_proxy.node_names.len() < 2 {
@@ -229,10 +239,8 @@ fn build_runtime_relay_group(
     Some(group)
 }
 
-fn has_proxy_named(proxies: &[Mapping], name: &str) -> bool {
-    proxies
-        .iter()
-        .any(|proxy| string_field(proxy, "name") == Some(name))
+fn has_proxy_named(proxies: &[ProxyNode], name: &str) -> bool {
+    proxies.iter().any(|proxy| proxy.name() == name)
 }
 
 fn string_field<'a>(mapping: &'a Mapping, key: &str) -> Option<&'a str> {
@@ -270,3 +278,50 @@ pub fn init() {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildChainProxyConfigRequest, ChainProxyCustomConfig, build_chain_proxy_config};
+
+    #[test]
+    fn build_relay_group_and_keep_unmodelled_fields() -> Result<(), String> {
+        let raw_config = r#"
+proxies:
+  - {name: A, type: ss, server: a.com, port: 8388, cipher: aes-128-gcm, password: pw, smux: {enabled: true}}
+  - {name: B, type: trojan, server: b.com, port: 443, password: pw, dialer-proxy: A}
+  - {name: C, type: masque, server: c.com, port: 443, private-key: k}
+  - {name: D, type: trojan, server: d.com, port: 443, password: pw, alpn: h2, udp: "true"}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: [A, B, C]}
+"#;
+        let request = BuildChainProxyConfigRequest {
+            request_id: "test".to_string(),
+            raw_config: raw_config.to_string(),
+            fallback_builtin_chain_proxy_names: Vec::new(),
+            disabled_builtin_chain_proxy_names: vec!["B".to_string()],
+            custom_chain_proxies: vec![ChainProxyCustomConfig {
+                display_name: "A -> C".to_string(),
+                node_names: vec!["A".to_string(), "C".to_string()],
+            }],
+        };
+
+        let result = build_chain_proxy_config(&request)?;
+        assert_eq!(result.builtin_chain_proxy_names, vec!["B".to_string()]);
+
+        let config: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(&result.config_content).map_err(|e| e.to_string())?;
+        let proxies = config["proxies"].as_sequence().ok_or("缺少 proxies")?;
+        assert_eq!(proxies.len(), 3);
+        assert_eq!(
+            proxies[0]["smux"]["enabled"],
+            serde_yaml_ng::Value::Bool(true)
+        );
+        assert_eq!(proxies[1]["type"], "masque");
+        assert_eq!(proxies[1]["private-key"], "k");
+        // 弱类型字段的节点按原样保留
+        assert_eq!(proxies[2]["alpn"], "h2");
+        assert_eq!(proxies[2]["udp"], "true");
+        assert_eq!(config["proxy-groups"][1]["type"], "relay");
+        Ok(())
+    }
+}
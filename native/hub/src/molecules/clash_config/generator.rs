@@ -4,9 +4,12 @@
 use rinf::{DartSignal, RustSignal};
 use serde::{Deserialize, Serialize};
 
+use super::aggregator::{AggregationConfig, aggregate_subscriptions};
+use super::injector::download_node_port;
 use super::runtime_params::RuntimeConfigParams;
 use crate::atoms::OverrideProcessor;
-use crate::molecules::OverrideConfig;
+use crate::molecules::http_client::set_download_node_port;
+use crate::molecules::{JsSandboxLimits, OverrideConfig};
 
 // Dart → Rust：生成运行时配置请求
 #[derive(Debug, Clone, Serialize, Deserialize, DartSignal)]
@@ -16,9 +19,15 @@ pub struct GenerateRuntimeConfigRequest {
     // 基础配置内容（来自订阅）
     pub base_config_content: String,
 
+    // 聚合多个订阅（设置时忽略 base_config_content）
+    pub aggregation: Option<AggregationConfig>,
+
     // 覆写列表
     pub overrides: Vec<OverrideConfig>,
 
+    // JavaScript 覆写沙箱限制（为空时使用默认值）
+    pub js_limits: Option<JsSandboxLimits>,
+
     // 运行时参数
     pub runtime_params: RuntimeConfigParams,
 }
@@ -42,17 +51,28 @@ impl GenerateRuntimeConfigRequest {
             self.runtime_params
         );
 
-        match generate_runtime_config_internal(
-            &self.base_config_content,
-            &self.overrides,
-            &self.runtime_params,
-        ) {
-            Ok(config) => GenerateRuntimeConfigResponse {
-                request_id: self.request_id,
-                is_successful: true,
-                result_config: config,
-                error_message: String::new(),
-            },
+        let base_content = match &self.aggregation {
+            Some(aggregation) => aggregate_subscriptions(aggregation),
+            None => Ok(self.base_config_content.clone()),
+        };
+
+        match base_content.and_then(|base_content| {
+            generate_runtime_config_internal(
+                &base_content,
+                &self.overrides,
+                self.js_limits.unwrap_or_default(),
+                &self.runtime_params,
+            )
+        }) {
+            Ok(config) => {
+                set_download_node_port(download_node_port(&self.runtime_params));
+                GenerateRuntimeConfigResponse {
+                    request_id: self.request_id,
+                    is_successful: true,
+                    result_config: config,
+                    error_message: String::new(),
+                }
+            }
             Err(e) => {
                 log::error!("[{}] 生成运行时配置失败：{}", self.request_id, e);
                 GenerateRuntimeConfigResponse {
@@ -70,6 +90,7 @@ impl GenerateRuntimeConfigRequest {
 fn generate_runtime_config_internal(
     base_content: &str,
     overrides: &[OverrideConfig],
+    js_limits: JsSandboxLimits,
     params: &RuntimeConfigParams,
 ) -> Result<String, String> {
     // 1. 应用覆写
@@ -79,8 +100,8 @@ fn generate_runtime_config_internal(
         log::info!("应用 {} 个覆写…", overrides.len());
 
         // 创建覆写处理器
-        let mut processor =
-            OverrideProcessor::new().map_err(|e| format!("初始化覆写处理器失败：{}", e))?;
+        let mut processor = OverrideProcessor::with_js_limits(js_limits)
+            .map_err(|e| format!("初始化覆写处理器失败：{}", e))?;
 
         processor.apply_overrides(base_content, overrides.to_vec())?
     };
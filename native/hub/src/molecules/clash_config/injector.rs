@@ -3,6 +3,8 @@
 use serde_yaml_ng::{Mapping, Value as YamlValue};
 
 use super::runtime_params::RuntimeConfigParams;
+use crate::atoms::ProxyNode;
+use crate::molecules::http_client::DOWNLOAD_NODE_GROUP_NAME;
 
 // 注入运行时参数到 Clash 配置
 pub fn inject_runtime_params(
@@ -296,6 +298,12 @@ pub fn inject_runtime_params(
         config_map.remove(YamlValue::String("skip-auth-prefixes".to_string()));
     }
 
+    // 注入指定节点下载入站
+    if let Some(port) = download_node_port(params) {
+        inject_download_node_listener(config_map, port);
+        log::info!("指定节点下载入站已注入：127.0.0.1:{}", port);
+    }
+
     // 注入 DNS（优先级：用户覆写 > TUN 默认 > 不注入）
     if params.is_dns_override_enabled {
         inject_user_dns_override(config_map, params)?;
@@ -312,6 +320,111 @@ pub fn inject_runtime_params(
     Ok(yaml_string)
 }
 
+// 有效的指定节点下载入站端口
+pub fn download_node_port(params: &RuntimeConfigParams) -> Option<u16> {
+    params
+        .download_node_port
+        .and_then(|port| u16::try_from(port).ok())
+        .filter(|port| *port > 0)
+}
+
+// 注入仅监听本机的 mixed 入站，流量固定交给包含全部节点的下载代理组
+fn inject_download_node_listener(config_map: &mut Mapping, port: u16) {
+    let (members, providers) = download_node_group_members(config_map);
+    let mut group = Mapping::new();
+    group.insert(
+        YamlValue::String("name".to_string()),
+        YamlValue::String(DOWNLOAD_NODE_GROUP_NAME.to_string()),
+    );
+    group.insert(
+        YamlValue::String("type".to_string()),
+        YamlValue::String("select".to_string()),
+    );
+    group.insert(
+        YamlValue::String("proxies".to_string()),
+        YamlValue::Sequence(members.into_iter().map(YamlValue::String).collect()),
+    );
+    if !providers.is_empty() {
+        group.insert(
+            YamlValue::String("use".to_string()),
+            YamlValue::Sequence(providers.into_iter().map(YamlValue::String).collect()),
+        );
+    }
+    // 仅供下载使用，不在代理组列表中显示
+    group.insert(
+        YamlValue::String("hidden".to_string()),
+        YamlValue::Bool(true),
+    );
+    append_sequence_item(config_map, "proxy-groups", YamlValue::Mapping(group));
+
+    let mut listener = Mapping::new();
+    listener.insert(
+        YamlValue::String("name".to_string()),
+        YamlValue::String("stelliberty-download".to_string()),
+    );
+    listener.insert(
+        YamlValue::String("type".to_string()),
+        YamlValue::String("mixed".to_string()),
+    );
+    listener.insert(
+        YamlValue::String("port".to_string()),
+        YamlValue::Number(port.into()),
+    );
+    listener.insert(
+        YamlValue::String("listen".to_string()),
+        YamlValue::String("127.0.0.1".to_string()),
+    );
+    listener.insert(YamlValue::String("udp".to_string()), YamlValue::Bool(false));
+    listener.insert(
+        YamlValue::String("proxy".to_string()),
+        YamlValue::String(DOWNLOAD_NODE_GROUP_NAME.to_string()),
+    );
+    append_sequence_item(config_map, "listeners", YamlValue::Mapping(listener));
+}
+
+// 下载代理组成员：配置中的全部节点（按强类型节点解析）与全部 proxy-providers
+fn download_node_group_members(config_map: &Mapping) -> (Vec<String>, Vec<String>) {
+    let mut members: Vec<String> = config_map
+        .get("proxies")
+        .and_then(YamlValue::as_sequence)
+        .map(|proxies| {
+            proxies
+                .iter()
+                .filter_map(|proxy| ProxyNode::from_yaml_lenient(proxy).ok())
+                .map(|node| node.name().to_string())
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let providers: Vec<String> = config_map
+        .get("proxy-providers")
+        .and_then(YamlValue::as_mapping)
+        .map(|providers| {
+            providers
+                .keys()
+                .filter_map(YamlValue::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // 空代理组无法通过核心校验
+    if members.is_empty() && providers.is_empty() {
+        members.push("DIRECT".to_string());
+    }
+    (members, providers)
+}
+
+fn append_sequence_item(config_map: &mut Mapping, key: &str, item: YamlValue) {
+    let key = YamlValue::String(key.to_string());
+    match config_map.get_mut(&key) {
+        Some(YamlValue::Sequence(items)) => items.push(item),
+        _ => {
+            config_map.insert(key, YamlValue::Sequence(vec![item]));
+        }
+    }
+}
+
 // 注入 TUN 模式默认 DNS 配置
 fn inject_dns_config(config_map: &mut Mapping, params: &RuntimeConfigParams) -> Result<(), String> {
     let existing_dns = config_map
@@ -437,3 +550,37 @@ fn inject_user_dns_override(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::inject_download_node_listener;
+    use crate::molecules::http_client::DOWNLOAD_NODE_GROUP_NAME;
+    use serde_yaml_ng::{Mapping, Value as YamlValue};
+
+    #[test]
+    fn inject_hidden_download_group_and_listener() -> Result<(), String> {
+        let config = r#"
+proxies:
+  - {name: HK, type: trojan, server: a.com, port: 443, password: pw}
+proxy-providers:
+  remote: {type: http, url: "https://example.com/p.yaml"}
+listeners:
+  - {name: existing, type: socks, port: 7000}
+"#;
+        let mut config_map: Mapping = serde_yaml_ng::from_str(config).map_err(|e| e.to_string())?;
+        inject_download_node_listener(&mut config_map, 8087);
+        let root = YamlValue::Mapping(config_map);
+
+        let group = &root["proxy-groups"][0];
+        assert_eq!(group["name"], DOWNLOAD_NODE_GROUP_NAME);
+        assert_eq!(group["hidden"], YamlValue::Bool(true));
+        assert_eq!(group["proxies"][0], "HK");
+        assert_eq!(group["use"][0], "remote");
+
+        let listener = &root["listeners"][1];
+        assert_eq!(listener["listen"], "127.0.0.1");
+        assert_eq!(listener["port"], YamlValue::Number(8087.into()));
+        assert_eq!(listener["proxy"], DOWNLOAD_NODE_GROUP_NAME);
+        Ok(())
+    }
+}
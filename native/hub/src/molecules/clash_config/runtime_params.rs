@@ -54,4 +54,7 @@ pub struct RuntimeConfigParams {
 
     // 跳过认证的 IP 前缀
     pub skip_auth_prefixes: Vec<String>,
+
+    // 经指定节点下载订阅的专用入站端口（为空或 0 时不注入）
+    pub download_node_port: Option<i32>,
 }
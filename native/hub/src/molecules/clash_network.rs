@@ -14,7 +14,8 @@ pub use handlers::{
     IpcPostRequest, IpcPutRequest, IpcResponse, IpcTrafficData, StartConnectionStream,
     StartLogStream, StartMemoryStream, StartTrafficStream, StopConnectionStream, StopLogStream,
     StopMemoryStream, StopTrafficStream, StreamResult, cleanup_all_network_resources,
-    init_rest_api_listeners, internal_ipc_get, start_connection_pool_health_check,
+    init_rest_api_listeners, internal_ipc_get, internal_ipc_request,
+    start_connection_pool_health_check,
 };
 pub use ipc_client::{HttpResponse, IpcClient};
 pub use ws_client::WebSocketClient;
@@ -1020,11 +1020,21 @@ impl StopConnectionStream {
 // 内部 IPC GET 接口：直接使用连接池发送请求。
 // 用于批量延迟测试等内部调用场景。
 pub async fn internal_ipc_get(path: &str) -> Result<String, String> {
+    internal_ipc_request("GET", path, None).await
+}
+
+// 内部 IPC 请求接口：支持任意方法与请求体。
+// 用于下载前切换节点等内部调用场景。
+pub async fn internal_ipc_request(
+    method: &str,
+    path: &str,
+    body: Option<&str>,
+) -> Result<String, String> {
     // 从连接池获取连接
     let ipc_conn = acquire_connection().await?;
 
     // 使用连接发送请求
-    match IpcClient::request_with_connection("GET", path, None, ipc_conn).await {
+    match IpcClient::request_with_connection(method, path, body, ipc_conn).await {
         Ok((response, ipc_conn)) => {
             // 归还连接
             release_connection(ipc_conn).await;
@@ -2,10 +2,11 @@
 // 处理覆写文件的 HTTP 下载，支持多种代理模式
 
 use crate::molecules::ProxyMode;
-use reqwest::Client;
+use crate::molecules::http_client::{
+    HttpRequestOptions, apply_request_options, create_http_client, select_download_node,
+};
 use rinf::{DartSignal, RustSignal};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
 
 // Dart → Rust：下载覆写文件请求
 #[derive(Deserialize, DartSignal)]
@@ -16,6 +17,7 @@ pub struct DownloadOverrideRequest {
     pub user_agent: String,
     pub timeout_seconds: u64,
     pub mixed_port: u16,
+    pub request_options: Option<HttpRequestOptions>, // 自定义请求头、认证与 TLS 选项
 }
 
 // Rust → Dart：下载覆写文件响应
@@ -37,6 +39,7 @@ impl DownloadOverrideRequest {
             &self.user_agent,
             self.timeout_seconds,
             self.mixed_port,
+            &self.request_options.unwrap_or_default(),
         )
         .await;
 
@@ -70,24 +73,28 @@ impl DownloadOverrideRequest {
 }
 
 // 下载覆写文件并返回内容。
-// 支持代理模式、超时与自定义 User-Agent。
+// 支持代理模式、超时、自定义 User-Agent 与请求选项。
 pub async fn download_override(
     url: &str,
     proxy_mode: ProxyMode,
     user_agent: &str,
     timeout_seconds: u64,
     mixed_port: u16,
+    request_options: &HttpRequestOptions,
 ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     log::info!("开始下载覆写文件：{}", url);
     log::info!("代理模式：{:?}", proxy_mode);
 
     // 创建 HTTP 客户端
-    let client = create_http_client(proxy_mode, timeout_seconds, mixed_port)?;
+    // 指定节点模式下先切换核心的下载节点（持有到响应体读取完毕）
+    let _node_guard = select_download_node(proxy_mode, request_options).await?;
 
-    // 发送 HTTP GET 请求
-    let response = client
-        .get(url)
-        .header("User-Agent", user_agent)
+    let client = create_http_client(proxy_mode, timeout_seconds, mixed_port, request_options)
+        .map_err(|e| format!("创建 HTTP 客户端失败：{}", e))?;
+
+    // 发送 HTTP GET 请求（自定义请求头可覆盖 User-Agent）
+    let request = client.get(url).header("User-Agent", user_agent);
+    let response = apply_request_options(request, request_options)?
         .send()
         .await?;
 
@@ -114,41 +121,6 @@ pub async fn download_override(
     Ok(content)
 }
 
-// 创建 HTTP 客户端（复用订阅下载的逻辑）
-fn create_http_client(
-    proxy_mode: ProxyMode,
-    timeout_seconds: u64,
-    mixed_port: u16,
-) -> Result<Client, Box<dyn std::error::Error + Send + Sync>> {
-    use reqwest::Proxy;
-
-    let mut builder = Client::builder()
-        .timeout(Duration::from_secs(timeout_seconds))
-        .connect_timeout(Duration::from_secs(10)) // 连接超时
-        .danger_accept_invalid_certs(false); // 验证 SSL 证书
-
-    // 根据代理模式配置客户端
-    match proxy_mode {
-        ProxyMode::Direct => {
-            log::debug!("使用直连模式");
-            // 不设置代理
-        }
-        ProxyMode::System => {
-            log::debug!("使用系统代理模式");
-            // reqwest 默认会读取系统环境变量（HTTP_PROXY, HTTPS_PROXY）
-            // 无需额外配置
-        }
-        ProxyMode::Core => {
-            log::debug!("使用核心代理模式：127.0.0.1:{}", mixed_port);
-            let proxy_url = format!("http://127.0.0.1:{}", mixed_port);
-            let proxy = Proxy::all(&proxy_url)?;
-            builder = builder.proxy(proxy);
-        }
-    }
-
-    Ok(builder.build()?)
-}
-
 pub fn init() {
     use tokio::spawn;
 
@@ -1,9 +1,9 @@
 // 覆写处理器
 // 处理配置覆写（YAML 合并 + JavaScript 执行）
 
-use crate::atoms::ProxyParser;
 use crate::atoms::override_processor::OverrideProcessor;
-use crate::molecules::OverrideConfig;
+use crate::atoms::{ParseOptions, ParseReport, ProxyParser};
+use crate::molecules::{JsSandboxLimits, OverrideConfig};
 use rinf::{DartSignal, RustSignal};
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +13,7 @@ pub struct ApplyOverridesRequest {
     pub request_id: String,
     pub base_config_content: String,
     pub overrides: Vec<OverrideConfig>,
+    pub js_limits: Option<JsSandboxLimits>, // 为空时使用默认执行时限与内存上限
 }
 
 // Rust → Dart：应用覆写响应
@@ -30,6 +31,7 @@ pub struct ApplyOverridesResponse {
 pub struct ParseSubscriptionRequest {
     pub request_id: String, // 请求标识符，用于响应匹配
     pub content: String,
+    pub options: Option<ParseOptions>, // 为空时使用默认解析选项
 }
 
 // Rust → Dart：解析订阅响应
@@ -39,6 +41,7 @@ pub struct ParseSubscriptionResponse {
     pub is_successful: bool,
     pub parsed_config: String,
     pub error_message: String,
+    pub report: ParseReport, // 解析报告（失败时仍包含逐行原因）
 }
 
 impl ApplyOverridesRequest {
@@ -49,21 +52,22 @@ impl ApplyOverridesRequest {
             self.overrides.len()
         );
 
-        let mut processor = match OverrideProcessor::new() {
-            Ok(p) => p,
-            Err(e) => {
-                log::error!("[{}] 初始化覆写处理器失败：{}", self.request_id, e);
-                let response = ApplyOverridesResponse {
-                    request_id: self.request_id,
-                    is_successful: false,
-                    result_config: String::new(),
-                    error_message: format!("初始化处理器失败：{}", e),
-                    logs: vec![],
-                };
-                response.send_signal_to_dart();
-                return;
-            }
-        };
+        let mut processor =
+            match OverrideProcessor::with_js_limits(self.js_limits.unwrap_or_default()) {
+                Ok(p) => p,
+                Err(e) => {
+                    log::error!("[{}] 初始化覆写处理器失败：{}", self.request_id, e);
+                    let response = ApplyOverridesResponse {
+                        request_id: self.request_id,
+                        is_successful: false,
+                        result_config: String::new(),
+                        error_message: format!("初始化处理器失败：{}", e),
+                        logs: vec![],
+                    };
+                    response.send_signal_to_dart();
+                    return;
+                }
+            };
 
         // 先解析订阅内容为标准 Clash 配置
         let parsed_config = match ProxyParser::parse_subscription(&self.base_config_content) {
@@ -124,18 +128,24 @@ impl ParseSubscriptionRequest {
             self.content.len()
         );
 
-        match ProxyParser::parse_subscription(&self.content) {
+        let options = self.options.unwrap_or_default();
+        let (result, report) = ProxyParser::parse_subscription_with_report(&self.content, &options);
+
+        match result {
             Ok(parsed_config) => {
                 log::info!(
-                    "订阅解析成功 [{}]，配置长度：{}字节",
+                    "订阅解析成功 [{}]，配置长度：{}字节，节点：{}，拒绝：{}",
                     self.request_id,
-                    parsed_config.len()
+                    parsed_config.len(),
+                    report.proxy_count,
+                    report.rejected_count
                 );
                 let response = ParseSubscriptionResponse {
                     request_id: self.request_id,
                     is_successful: true,
                     parsed_config,
                     error_message: String::new(),
+                    report,
                 };
                 response.send_signal_to_dart();
             }
@@ -146,6 +156,7 @@ impl ParseSubscriptionRequest {
                     is_successful: false,
                     parsed_config: String::new(),
                     error_message: e,
+                    report,
                 };
                 response.send_signal_to_dart();
             }
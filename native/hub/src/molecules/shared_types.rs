@@ -5,12 +5,14 @@ use rinf::SignalPiece;
 use serde::{Deserialize, Serialize};
 
 // 从 atoms 层重新导出
-pub use crate::atoms::shared_types::{OverrideConfig, OverrideFormat};
+pub use crate::atoms::shared_types::{JsSandboxLimits, OverrideConfig, OverrideFormat};
 
 // 代理模式（分子层特有）
-#[derive(Deserialize, Serialize, Clone, Copy, Debug, SignalPiece)]
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, SignalPiece)]
 pub enum ProxyMode {
-    Direct = 0, // 直连
-    System = 1, // 系统代理
-    Core = 2,   // Clash 核心代理
+    Direct = 0,   // 直连
+    System = 1,   // 系统代理
+    Core = 2,     // Clash 核心代理
+    Upstream = 3, // 自定义上游代理（HttpRequestOptions.upstream_proxy_url）
+    CoreNode = 4, // 经核心的指定节点（HttpRequestOptions.core_node_name）
 }
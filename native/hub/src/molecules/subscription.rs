@@ -1,13 +1,30 @@
 // 订阅管理分子模块
 
+mod age_hybrid;
+pub mod cache;
+pub mod diff;
 pub mod downloader;
-pub mod parser;
+pub mod exporter;
+pub mod retry;
+pub mod scheduler;
+pub mod source;
 
+pub use cache::RemoveSubscriptionCacheRequest;
+pub use diff::{NodeEndpointChange, NodeRename, SubscriptionDiff};
 pub use downloader::{
-    DownloadSubscriptionRequest, DownloadSubscriptionResponse, SubscriptionInfoData,
+    DownloadSubscriptionProgress, DownloadSubscriptionRequest, DownloadSubscriptionResponse,
+    SubscriptionInfoData,
 };
-pub use parser::ProxyParser;
+pub use exporter::{ExportProxyLinksRequest, ExportProxyLinksResponse};
+pub use retry::{DownloadAttempt, RetryPolicy};
+pub use scheduler::{ScheduleSubscriptionsRequest, ScheduledSubscription, SubscriptionUpdated};
+
+// 从 atoms 层重新导出订阅解析器
+pub use crate::atoms::ProxyParser;
 
 pub fn init_listeners() {
+    cache::init();
     downloader::init();
+    exporter::init();
+    scheduler::init();
 }
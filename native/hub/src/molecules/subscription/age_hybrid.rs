@@ -0,0 +1,224 @@
+// age 混合接收方（mlkem768x25519）：X-Wing KEM 封装共享密钥，
+// 再以 HPKE（HKDF-SHA256、ChaCha20Poly1305）加密文件密钥
+
+mod mlkem;
+
+use age_core::format::{FILE_KEY_BYTES, FileKey, Stanza};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD_NO_PAD;
+use bech32::FromBase32;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Shake256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+pub const SECRET_KEY_PREFIX: &str = "AGE-SECRET-KEY-PQ-1";
+const SECRET_KEY_HRP: &str = "age-secret-key-pq-";
+const STANZA_TAG: &str = "mlkem768x25519";
+const HPKE_INFO: &[u8] = b"age-encryption.org/mlkem768x25519";
+// KEM 0x647a（MLKEM768-X25519）、KDF 0x0001（HKDF-SHA256）、AEAD 0x0003（ChaCha20Poly1305）
+const HPKE_SUITE_ID: &[u8] = b"HPKE\x64\x7a\x00\x01\x00\x03";
+const XWING_LABEL: &[u8] = b"\\.//^\\";
+const ENCAPSULATED_KEY_LEN: usize = mlkem::CIPHERTEXT_LEN + 32;
+const TAG_LEN: usize = 16;
+
+pub struct HybridIdentity {
+    mlkem: mlkem::DecapsulationKey,
+    x25519: StaticSecret,
+    x25519_public: PublicKey,
+}
+
+impl HybridIdentity {
+    // 32 字节种子经 SHAKE256 展开为 ML-KEM 的 d、z 与 X25519 私钥
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let mut xof = Shake256::default();
+        xof.update(seed);
+        let mut expanded = [0u8; 96];
+        xof.finalize_xof().read(&mut expanded);
+
+        let mut d = [0u8; 32];
+        let mut z = [0u8; 32];
+        let mut x25519 = [0u8; 32];
+        d.copy_from_slice(&expanded[..32]);
+        z.copy_from_slice(&expanded[32..64]);
+        x25519.copy_from_slice(&expanded[64..]);
+
+        let x25519 = StaticSecret::from(x25519);
+        Self {
+            mlkem: mlkem::DecapsulationKey::from_seed(&d, &z),
+            x25519_public: PublicKey::from(&x25519),
+            x25519,
+        }
+    }
+
+    // 解析 Bech32 编码的 AGE-SECRET-KEY-PQ-1… 私钥
+    pub fn from_bech32(secret_key: &str) -> Result<Self, String> {
+        let (hrp, data, variant) =
+            bech32::decode(secret_key).map_err(|e| format!("age-secret-key 格式无效：{}", e))?;
+        if hrp != SECRET_KEY_HRP || variant != bech32::Variant::Bech32 {
+            return Err("age-secret-key 格式无效：混合私钥前缀错误".to_string());
+        }
+        let seed =
+            Vec::<u8>::from_base32(&data).map_err(|e| format!("age-secret-key 格式无效：{}", e))?;
+        let seed: [u8; 32] = seed
+            .try_into()
+            .map_err(|_| "age-secret-key 格式无效：混合私钥长度错误".to_string())?;
+        Ok(Self::from_seed(&seed))
+    }
+
+    #[cfg(test)]
+    pub fn to_public(&self) -> HybridRecipient {
+        HybridRecipient {
+            mlkem: self.mlkem.encapsulation_key().to_vec(),
+            x25519: self.x25519_public,
+        }
+    }
+
+    // X-Wing 解封装
+    fn decapsulate(&self, encapsulated_key: &[u8]) -> [u8; 32] {
+        let (mlkem_ciphertext, x25519_ciphertext) =
+            encapsulated_key.split_at(mlkem::CIPHERTEXT_LEN);
+        let mut ephemeral_public = [0u8; 32];
+        ephemeral_public.copy_from_slice(x25519_ciphertext);
+
+        let mlkem_shared = self.mlkem.decapsulate(mlkem_ciphertext);
+        let x25519_shared = self
+            .x25519
+            .diffie_hellman(&PublicKey::from(ephemeral_public));
+        xwing_combine(
+            &mlkem_shared,
+            x25519_shared.as_bytes(),
+            &ephemeral_public,
+            self.x25519_public.as_bytes(),
+        )
+    }
+}
+
+impl age::Identity for HybridIdentity {
+    fn unwrap_stanza(&self, stanza: &Stanza) -> Option<Result<FileKey, age::DecryptError>> {
+        if stanza.tag != STANZA_TAG {
+            return None;
+        }
+
+        let encapsulated_key = match &stanza.args[..] {
+            [arg] => STANDARD_NO_PAD
+                .decode(arg)
+                .ok()
+                .filter(|key| key.len() == ENCAPSULATED_KEY_LEN),
+            _ => None,
+        };
+        let Some(encapsulated_key) = encapsulated_key else {
+            return Some(Err(age::DecryptError::InvalidHeader));
+        };
+        if stanza.body.len() != FILE_KEY_BYTES + TAG_LEN {
+            return Some(Err(age::DecryptError::InvalidHeader));
+        }
+
+        let shared_secret = self.decapsulate(&encapsulated_key);
+        // 与 x25519 相同：无法判断接收方节属于哪把私钥，解密失败时交给其他私钥尝试
+        let (key, nonce) = hpke_key_schedule(&shared_secret)?;
+        ChaCha20Poly1305::new(&key)
+            .decrypt(&nonce, stanza.body.as_slice())
+            .ok()
+            .filter(|file_key| file_key.len() == FILE_KEY_BYTES)
+            .map(|file_key| {
+                Ok(FileKey::init_with_mut(|output| {
+                    output.copy_from_slice(&file_key)
+                }))
+            })
+    }
+}
+
+// 仅测试使用：构造混合接收方以生成加密订阅
+#[cfg(test)]
+pub struct HybridRecipient {
+    mlkem: Vec<u8>,
+    x25519: PublicKey,
+}
+
+#[cfg(test)]
+impl age::Recipient for HybridRecipient {
+    fn wrap_file_key(
+        &self,
+        file_key: &FileKey,
+    ) -> Result<(Vec<Stanza>, std::collections::HashSet<String>), age::EncryptError> {
+        use age_core::secrecy::ExposeSecret;
+
+        let (mlkem_shared, mlkem_ciphertext) =
+            mlkem::encapsulate(&self.mlkem, &rand::random::<[u8; 32]>());
+        let ephemeral = StaticSecret::from(rand::random::<[u8; 32]>());
+        let ephemeral_public = PublicKey::from(&ephemeral);
+        let x25519_shared = ephemeral.diffie_hellman(&self.x25519);
+        let shared_secret = xwing_combine(
+            &mlkem_shared,
+            x25519_shared.as_bytes(),
+            ephemeral_public.as_bytes(),
+            self.x25519.as_bytes(),
+        );
+
+        let encrypt_error = || age::EncryptError::Io(std::io::Error::other("HPKE 加密失败"));
+        let (key, nonce) = hpke_key_schedule(&shared_secret).ok_or_else(encrypt_error)?;
+        let body = ChaCha20Poly1305::new(&key)
+            .encrypt(&nonce, file_key.expose_secret().as_slice())
+            .map_err(|_| encrypt_error())?;
+
+        let encapsulated_key = [mlkem_ciphertext.as_slice(), ephemeral_public.as_bytes()].concat();
+        let stanza = Stanza {
+            tag: STANZA_TAG.to_string(),
+            args: vec![STANDARD_NO_PAD.encode(encapsulated_key)],
+            body,
+        };
+        Ok((vec![stanza], ["postquantum".to_string()].into()))
+    }
+}
+
+// X-Wing 组合器：SHA3-256(ss_M ‖ ss_X ‖ ct_X ‖ pk_X ‖ XWingLabel)
+fn xwing_combine(
+    mlkem_shared: &[u8],
+    x25519_shared: &[u8],
+    x25519_ciphertext: &[u8],
+    x25519_public: &[u8],
+) -> [u8; 32] {
+    let digest = Sha3_256::new()
+        .chain_update(mlkem_shared)
+        .chain_update(x25519_shared)
+        .chain_update(x25519_ciphertext)
+        .chain_update(x25519_public)
+        .chain_update(XWING_LABEL)
+        .finalize();
+    let mut shared_secret = [0u8; 32];
+    shared_secret.copy_from_slice(&digest);
+    shared_secret
+}
+
+// HPKE base 模式的密钥派生，首条消息直接使用 base_nonce
+fn hpke_key_schedule(shared_secret: &[u8]) -> Option<(Key, Nonce)> {
+    let psk_id_hash = labeled_extract(b"", b"psk_id_hash", b"");
+    let info_hash = labeled_extract(b"", b"info_hash", HPKE_INFO);
+    let context = [&[0u8][..], &psk_id_hash, &info_hash].concat();
+    let secret = labeled_extract(shared_secret, b"secret", b"");
+
+    let mut key = Key::default();
+    let mut nonce = Nonce::default();
+    labeled_expand(&secret, b"key", &context, &mut key)?;
+    labeled_expand(&secret, b"base_nonce", &context, &mut nonce)?;
+    Some((key, nonce))
+}
+
+fn labeled_extract(salt: &[u8], label: &[u8], ikm: &[u8]) -> Vec<u8> {
+    let labeled_ikm = [&b"HPKE-v1"[..], HPKE_SUITE_ID, label, ikm].concat();
+    let (prk, _) = Hkdf::<Sha256>::extract(Some(salt), &labeled_ikm);
+    prk.to_vec()
+}
+
+fn labeled_expand(prk: &[u8], label: &[u8], info: &[u8], output: &mut [u8]) -> Option<()> {
+    let length = u16::try_from(output.len()).ok()?.to_be_bytes();
+    let labeled_info = [&length[..], b"HPKE-v1", HPKE_SUITE_ID, label, info].concat();
+    Hkdf::<Sha256>::from_prk(prk)
+        .ok()?
+        .expand(&labeled_info, output)
+        .ok()
+}
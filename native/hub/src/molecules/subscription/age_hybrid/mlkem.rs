@@ -0,0 +1,394 @@
+// ML-KEM-768（FIPS 203）：仅实现 age 混合接收方所需的密钥生成、封装与解封装
+
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Sha3_512, Shake128, Shake256};
+
+const N: usize = 256;
+const Q: u32 = 3329;
+const K: usize = 3;
+// ML-KEM-768 的 η1 = η2 = 2
+const ETA: usize = 2;
+const DU: u32 = 10;
+const DV: u32 = 4;
+
+const POLY_BYTES: usize = 384;
+const U_BYTES: usize = 32 * DU as usize * K;
+pub const ENCAPSULATION_KEY_LEN: usize = POLY_BYTES * K + 32;
+pub const CIPHERTEXT_LEN: usize = U_BYTES + 32 * DV as usize;
+
+type Poly = [u32; N];
+type PolyVec = [Poly; K];
+
+const fn pow_mod(base: u32, mut exp: u32) -> u32 {
+    let mut result = 1;
+    let mut base = base % Q;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % Q;
+        }
+        base = base * base % Q;
+        exp >>= 1;
+    }
+    result
+}
+
+const fn bit_rev7(value: u32) -> u32 {
+    let mut result = 0;
+    let mut bit = 0;
+    while bit < 7 {
+        result |= ((value >> bit) & 1) << (6 - bit);
+        bit += 1;
+    }
+    result
+}
+
+// NTT 使用的 ζ^BitRev7(i)
+const ZETAS: [u32; 128] = {
+    let mut zetas = [0; 128];
+    let mut i = 0;
+    while i < 128 {
+        zetas[i] = pow_mod(17, bit_rev7(i as u32));
+        i += 1;
+    }
+    zetas
+};
+
+// 基础乘法使用的 ζ^(2·BitRev7(i)+1)
+const GAMMAS: [u32; 128] = {
+    let mut gammas = [0; 128];
+    let mut i = 0;
+    while i < 128 {
+        gammas[i] = pow_mod(17, 2 * bit_rev7(i as u32) + 1);
+        i += 1;
+    }
+    gammas
+};
+
+pub struct DecapsulationKey {
+    secret: PolyVec,
+    encapsulation_key: Vec<u8>,
+    encapsulation_key_hash: [u8; 32],
+    implicit_rejection: [u8; 32],
+}
+
+impl DecapsulationKey {
+    // ML-KEM.KeyGen_internal(d, z)
+    pub fn from_seed(d: &[u8; 32], z: &[u8; 32]) -> Self {
+        let mut seed = [0u8; 33];
+        seed[..32].copy_from_slice(d);
+        seed[32] = K as u8;
+        let expanded = Sha3_512::digest(seed);
+        let (rho, sigma) = expanded.split_at(32);
+
+        let matrix = sample_matrix(rho);
+        let mut counter = 0;
+        let secret = sample_vector(sigma, &mut counter, true);
+        let error = sample_vector(sigma, &mut counter, true);
+
+        let mut encapsulation_key = Vec::with_capacity(ENCAPSULATION_KEY_LEN);
+        for (row, error) in matrix.iter().zip(&error) {
+            let mut t = *error;
+            for (a, s) in row.iter().zip(&secret) {
+                add_assign(&mut t, &multiply_ntts(a, s));
+            }
+            byte_encode(&t, 12, &mut encapsulation_key);
+        }
+        encapsulation_key.extend_from_slice(rho);
+
+        let mut encapsulation_key_hash = [0u8; 32];
+        encapsulation_key_hash.copy_from_slice(&Sha3_256::digest(&encapsulation_key));
+
+        Self {
+            secret,
+            encapsulation_key,
+            encapsulation_key_hash,
+            implicit_rejection: *z,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn encapsulation_key(&self) -> &[u8] {
+        &self.encapsulation_key
+    }
+
+    // ML-KEM.Decaps_internal：密文不一致时按隐式拒绝返回伪随机共享密钥
+    pub fn decapsulate(&self, ciphertext: &[u8]) -> [u8; 32] {
+        let message = decrypt(&self.secret, ciphertext);
+        let expanded = Sha3_512::digest([&message[..], &self.encapsulation_key_hash].concat());
+        let (shared_secret, randomness) = expanded.split_at(32);
+
+        let mut rejection = Shake256::default();
+        rejection.update(&self.implicit_rejection);
+        rejection.update(ciphertext);
+        let mut result = [0u8; 32];
+        rejection.finalize_xof().read(&mut result);
+
+        let expected = encrypt(&self.encapsulation_key, &message, randomness);
+        let difference = expected
+            .iter()
+            .zip(ciphertext)
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+        if difference == 0 && expected.len() == ciphertext.len() {
+            result.copy_from_slice(shared_secret);
+        }
+        result
+    }
+}
+
+// ML-KEM.Encaps_internal(ek, m)，返回（共享密钥，密文）
+#[cfg(test)]
+pub fn encapsulate(encapsulation_key: &[u8], message: &[u8; 32]) -> ([u8; 32], Vec<u8>) {
+    let key_hash = Sha3_256::digest(encapsulation_key);
+    let expanded = Sha3_512::digest([&message[..], &key_hash].concat());
+    let (shared_secret, randomness) = expanded.split_at(32);
+
+    let mut result = [0u8; 32];
+    result.copy_from_slice(shared_secret);
+    (result, encrypt(encapsulation_key, message, randomness))
+}
+
+// K-PKE.Encrypt
+fn encrypt(encapsulation_key: &[u8], message: &[u8; 32], randomness: &[u8]) -> Vec<u8> {
+    let (t_bytes, rho) = encapsulation_key.split_at(POLY_BYTES * K);
+    let matrix = sample_matrix(rho);
+
+    let mut counter = 0;
+    let y = sample_vector(randomness, &mut counter, true);
+    let error1 = sample_vector(randomness, &mut counter, false);
+    let error2 = sample_cbd(&prf(randomness, counter));
+
+    let mut ciphertext = Vec::with_capacity(CIPHERTEXT_LEN);
+    for (column, error1) in error1.iter().enumerate() {
+        let mut u = [0; N];
+        for (row, y) in matrix.iter().zip(&y) {
+            add_assign(&mut u, &multiply_ntts(&row[column], y));
+        }
+        inverse_ntt(&mut u);
+        add_assign(&mut u, error1);
+        compress_poly(&mut u, DU);
+        byte_encode(&u, DU, &mut ciphertext);
+    }
+
+    let mut v = [0; N];
+    for (t_bytes, y) in t_bytes.chunks_exact(POLY_BYTES).zip(&y) {
+        add_assign(&mut v, &multiply_ntts(&byte_decode(t_bytes, 12), y));
+    }
+    inverse_ntt(&mut v);
+    add_assign(&mut v, &error2);
+    for (i, coefficient) in v.iter_mut().enumerate() {
+        let bit = u32::from((message[i / 8] >> (i % 8)) & 1);
+        *coefficient = (*coefficient + decompress(bit, 1)) % Q;
+    }
+    compress_poly(&mut v, DV);
+    byte_encode(&v, DV, &mut ciphertext);
+    ciphertext
+}
+
+// K-PKE.Decrypt
+fn decrypt(secret: &PolyVec, ciphertext: &[u8]) -> [u8; 32] {
+    let (c1, c2) = ciphertext.split_at(U_BYTES.min(ciphertext.len()));
+
+    let mut w = [0; N];
+    for (s, chunk) in secret.iter().zip(c1.chunks_exact(32 * DU as usize)) {
+        let mut u = byte_decode(chunk, DU);
+        decompress_poly(&mut u, DU);
+        ntt(&mut u);
+        add_assign(&mut w, &multiply_ntts(s, &u));
+    }
+    inverse_ntt(&mut w);
+
+    let mut v = byte_decode(c2, DV);
+    decompress_poly(&mut v, DV);
+
+    let mut message = [0u8; 32];
+    for (i, (v, w)) in v.iter().zip(&w).enumerate() {
+        let bit = compress((v + Q - w) % Q, 1) as u8;
+        message[i / 8] |= bit << (i % 8);
+    }
+    message
+}
+
+// Â[i][j] = SampleNTT(ρ‖j‖i)
+fn sample_matrix(rho: &[u8]) -> [PolyVec; K] {
+    let mut matrix = [[[0; N]; K]; K];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            *entry = sample_ntt(rho, j as u8, i as u8);
+        }
+    }
+    matrix
+}
+
+fn sample_ntt(rho: &[u8], j: u8, i: u8) -> Poly {
+    let mut xof = Shake128::default();
+    xof.update(rho);
+    xof.update(&[j, i]);
+    let mut reader = xof.finalize_xof();
+
+    let mut poly = [0; N];
+    let mut count = 0;
+    let mut bytes = [0u8; 3];
+    while count < N {
+        reader.read(&mut bytes);
+        let d1 = u32::from(bytes[0]) | ((u32::from(bytes[1]) & 0x0f) << 8);
+        let d2 = (u32::from(bytes[1]) >> 4) | (u32::from(bytes[2]) << 4);
+        if d1 < Q {
+            poly[count] = d1;
+            count += 1;
+        }
+        if d2 < Q && count < N {
+            poly[count] = d2;
+            count += 1;
+        }
+    }
+    poly
+}
+
+// 依次以 PRF(seed, N) 采样 k 个多项式，需要时转换到 NTT 域
+fn sample_vector(seed: &[u8], counter: &mut u8, to_ntt: bool) -> PolyVec {
+    let mut vector = [[0; N]; K];
+    for poly in &mut vector {
+        *poly = sample_cbd(&prf(seed, *counter));
+        *counter += 1;
+        if to_ntt {
+            ntt(poly);
+        }
+    }
+    vector
+}
+
+fn prf(seed: &[u8], counter: u8) -> [u8; 64 * ETA] {
+    let mut xof = Shake256::default();
+    xof.update(seed);
+    xof.update(&[counter]);
+    let mut output = [0u8; 64 * ETA];
+    xof.finalize_xof().read(&mut output);
+    output
+}
+
+// SamplePolyCBD_2：每个系数占 4 个比特
+fn sample_cbd(bytes: &[u8; 64 * ETA]) -> Poly {
+    let mut poly = [0; N];
+    for (i, coefficient) in poly.iter_mut().enumerate() {
+        let bits = u32::from(bytes[i / 2] >> (4 * (i % 2)));
+        let x = (bits & 1) + ((bits >> 1) & 1);
+        let y = ((bits >> 2) & 1) + ((bits >> 3) & 1);
+        *coefficient = (Q + x - y) % Q;
+    }
+    poly
+}
+
+fn ntt(poly: &mut Poly) {
+    let mut k = 1;
+    let mut len = 128;
+    while len >= 2 {
+        for start in (0..N).step_by(2 * len) {
+            let zeta = ZETAS[k];
+            k += 1;
+            for j in start..start + len {
+                let t = zeta * poly[j + len] % Q;
+                poly[j + len] = (poly[j] + Q - t) % Q;
+                poly[j] = (poly[j] + t) % Q;
+            }
+        }
+        len /= 2;
+    }
+}
+
+fn inverse_ntt(poly: &mut Poly) {
+    let mut k = 127;
+    let mut len = 2;
+    while len <= 128 {
+        for start in (0..N).step_by(2 * len) {
+            let zeta = ZETAS[k];
+            k -= 1;
+            for j in start..start + len {
+                let t = poly[j];
+                poly[j] = (t + poly[j + len]) % Q;
+                poly[j + len] = zeta * ((poly[j + len] + Q - t) % Q) % Q;
+            }
+        }
+        len *= 2;
+    }
+    // 乘以 128⁻¹ mod q
+    for coefficient in poly.iter_mut() {
+        *coefficient = *coefficient * 3303 % Q;
+    }
+}
+
+fn multiply_ntts(f: &Poly, g: &Poly) -> Poly {
+    let mut h = [0; N];
+    for (((h, f), g), gamma) in h
+        .chunks_exact_mut(2)
+        .zip(f.chunks_exact(2))
+        .zip(g.chunks_exact(2))
+        .zip(GAMMAS)
+    {
+        h[0] = (f[0] * g[0] + f[1] * g[1] % Q * gamma) % Q;
+        h[1] = (f[0] * g[1] + f[1] * g[0]) % Q;
+    }
+    h
+}
+
+fn add_assign(f: &mut Poly, g: &Poly) {
+    for (f, g) in f.iter_mut().zip(g) {
+        *f = (*f + g) % Q;
+    }
+}
+
+fn compress(value: u32, bits: u32) -> u32 {
+    (((value << (bits + 1)) + Q) / (2 * Q)) & ((1 << bits) - 1)
+}
+
+fn decompress(value: u32, bits: u32) -> u32 {
+    (Q * value + (1 << (bits - 1))) >> bits
+}
+
+fn compress_poly(poly: &mut Poly, bits: u32) {
+    for coefficient in poly.iter_mut() {
+        *coefficient = compress(*coefficient, bits);
+    }
+}
+
+fn decompress_poly(poly: &mut Poly, bits: u32) {
+    for coefficient in poly.iter_mut() {
+        *coefficient = decompress(*coefficient, bits);
+    }
+}
+
+// ByteEncode_d：按小端比特序打包
+fn byte_encode(poly: &Poly, bits: u32, output: &mut Vec<u8>) {
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    for &coefficient in poly {
+        buffer |= coefficient << buffered;
+        buffered += bits;
+        while buffered >= 8 {
+            output.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    }
+}
+
+// ByteDecode_d：d = 12 时对系数取模
+fn byte_decode(bytes: &[u8], bits: u32) -> Poly {
+    let mask = (1 << bits) - 1;
+    let mut poly = [0; N];
+    let mut input = bytes.iter();
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    for coefficient in &mut poly {
+        while buffered < bits {
+            buffer |= u32::from(input.next().copied().unwrap_or(0)) << buffered;
+            buffered += 8;
+        }
+        *coefficient = buffer & mask;
+        buffer >>= bits;
+        buffered -= bits;
+        if bits == 12 {
+            *coefficient %= Q;
+        }
+    }
+    poly
+}
@@ -0,0 +1,169 @@
+// 订阅下载缓存
+// 按 URL 持久化最近一次成功下载的响应体与 ETag/Last-Modified，
+// 用于条件请求以及下载失败时返回旧内容。
+
+use super::downloader::SubscriptionInfoData;
+use crate::atoms::path_service;
+use rinf::DartSignal;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// Dart → Rust：删除订阅后清理其下载缓存
+#[derive(Deserialize, DartSignal)]
+pub struct RemoveSubscriptionCacheRequest {
+    pub url: String,
+}
+
+impl RemoveSubscriptionCacheRequest {
+    pub fn handle(self) {
+        let dir = path_service::subscription_cache_dir();
+        match remove(&dir, &self.url) {
+            Ok(()) => log::info!("已清理订阅缓存"),
+            Err(e) => log::warn!("清理订阅缓存失败：{}", e),
+        }
+    }
+}
+
+// 缓存条目（元数据保存为 JSON，响应体单独保存）
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CachedSubscription {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub subscription_info: Option<SubscriptionInfoData>,
+    pub saved_at: i64, // 保存时间（Unix 时间戳）
+    #[serde(skip)]
+    pub body: Vec<u8>, // 原始响应体（age 加密内容保持加密）
+}
+
+// 读取 URL 对应的缓存，不存在或损坏时返回 None
+pub fn load(dir: &Path, url: &str) -> Option<CachedSubscription> {
+    let (meta_path, body_path) = cache_paths(dir, url);
+    let meta = std::fs::read_to_string(&meta_path).ok()?;
+    let mut entry: CachedSubscription = serde_json::from_str(&meta)
+        .inspect_err(|e| log::warn!("订阅缓存元数据损坏：{}", e))
+        .ok()?;
+
+    // 防止哈希冲突时读到其他订阅的缓存
+    if entry.url != url {
+        return None;
+    }
+
+    entry.body = std::fs::read(&body_path).ok()?;
+    if entry.body.is_empty() {
+        return None;
+    }
+    Some(entry)
+}
+
+// 保存缓存（先写响应体，再写元数据，避免元数据指向不完整的内容）
+pub fn save(dir: &Path, entry: &CachedSubscription) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("无法创建订阅缓存目录 {}：{}", dir.display(), e))?;
+
+    let (meta_path, body_path) = cache_paths(dir, &entry.url);
+    write_atomically(&body_path, &entry.body)?;
+
+    let meta =
+        serde_json::to_string(entry).map_err(|e| format!("序列化订阅缓存元数据失败：{}", e))?;
+    write_atomically(&meta_path, meta.as_bytes())
+}
+
+// 删除 URL 对应的缓存，缓存不存在时视为成功
+pub fn remove(dir: &Path, url: &str) -> Result<(), String> {
+    let (meta_path, body_path) = cache_paths(dir, url);
+    // 先删元数据，避免残留的元数据指向已删除的响应体
+    for path in [meta_path, body_path] {
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("删除订阅缓存失败 {}：{}", path.display(), e)),
+        }
+    }
+    Ok(())
+}
+
+// 临时文件名包含目标文件名、进程号与随机数，元数据与响应体及并发写入互不干扰
+fn write_atomically(path: &Path, content: &[u8]) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(
+        "{}.{}-{:08x}.tmp",
+        file_name,
+        std::process::id(),
+        rand::random::<u32>()
+    ));
+    std::fs::write(&temp_path, content)
+        .map_err(|e| format!("写入订阅缓存失败 {}：{}", temp_path.display(), e))?;
+    std::fs::rename(&temp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        format!("替换订阅缓存失败 {}：{}", path.display(), e)
+    })
+}
+
+// 初始化 Dart 信号监听器
+pub fn init() {
+    tokio::spawn(async {
+        let receiver = RemoveSubscriptionCacheRequest::get_dart_signal_receiver();
+        while let Some(dart_signal) = receiver.recv().await {
+            dart_signal.message.handle();
+        }
+    });
+}
+
+fn cache_paths(dir: &Path, url: &str) -> (PathBuf, PathBuf) {
+    let key = cache_key(url);
+    (
+        dir.join(format!("{}.json", key)),
+        dir.join(format!("{}.body", key)),
+    )
+}
+
+// FNV-1a 64 位哈希，保证文件名跨版本稳定
+fn cache_key(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CachedSubscription, load, remove, save};
+
+    #[test]
+    fn save_and_load_cached_subscription() -> Result<(), String> {
+        let dir = std::env::temp_dir().join(format!("subscription_cache_{}", std::process::id()));
+        let entry = CachedSubscription {
+            url: "https://example.com/sub".to_string(),
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+            subscription_info: None,
+            saved_at: 1,
+            body: b"proxies: []".to_vec(),
+        };
+
+        save(&dir, &entry)?;
+        let loaded = load(&dir, &entry.url).ok_or("缓存读取失败")?;
+        assert_eq!(loaded.etag, entry.etag);
+        assert_eq!(loaded.body, entry.body);
+        assert!(load(&dir, "https://example.com/other").is_none());
+
+        // 临时文件已全部替换为正式文件
+        let file_count = std::fs::read_dir(&dir).map_err(|e| e.to_string())?.count();
+        assert_eq!(file_count, 2);
+
+        remove(&dir, &entry.url)?;
+        assert!(load(&dir, &entry.url).is_none());
+        assert_eq!(
+            std::fs::read_dir(&dir).map_err(|e| e.to_string())?.count(),
+            0
+        );
+        remove(&dir, &entry.url)?;
+
+        std::fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+// 订阅变更对比
+// 比较两次刷新解析后的 Clash 配置，给出节点、代理组与规则的结构化差异。
+
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::{Mapping, Value as YamlValue};
+use std::collections::{HashMap, HashSet};
+
+// 节点重命名（配置除名称外完全相同）
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, PartialEq, Eq)]
+pub struct NodeRename {
+    pub old_name: String,
+    pub new_name: String,
+}
+
+// 节点地址变更（名称不变，类型、服务器或端口变化）
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, PartialEq, Eq)]
+pub struct NodeEndpointChange {
+    pub name: String,
+    pub old_endpoint: String, // 格式：type://server:port
+    pub new_endpoint: String,
+}
+
+// 订阅差异
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, Default)]
+pub struct SubscriptionDiff {
+    pub added_nodes: Vec<String>,
+    pub removed_nodes: Vec<String>,
+    pub renamed_nodes: Vec<NodeRename>,
+    pub endpoint_changed_nodes: Vec<NodeEndpointChange>,
+    pub added_groups: Vec<String>,
+    pub removed_groups: Vec<String>,
+    pub changed_groups: Vec<String>, // 类型或成员变化
+    pub added_rules: Vec<String>,
+    pub removed_rules: Vec<String>,
+}
+
+impl SubscriptionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.renamed_nodes.is_empty()
+            && self.endpoint_changed_nodes.is_empty()
+            && self.added_groups.is_empty()
+            && self.removed_groups.is_empty()
+            && self.changed_groups.is_empty()
+            && self.added_rules.is_empty()
+            && self.removed_rules.is_empty()
+    }
+}
+
+// 对比两份 Clash 配置
+pub fn diff_configs(previous: &str, current: &str) -> Result<SubscriptionDiff, String> {
+    let previous = parse_root(previous, "旧")?;
+    let current = parse_root(current, "新")?;
+    let mut diff = SubscriptionDiff::default();
+
+    diff_nodes(
+        &named_items(&previous, "proxies"),
+        &named_items(&current, "proxies"),
+        &mut diff,
+    );
+    diff_groups(
+        &named_items(&previous, "proxy-groups"),
+        &named_items(&current, "proxy-groups"),
+        &mut diff,
+    );
+
+    let previous_rules = rules(&previous);
+    let current_rules = rules(&current);
+    let previous_set: HashSet<&String> = previous_rules.iter().collect();
+    let current_set: HashSet<&String> = current_rules.iter().collect();
+    diff.added_rules = current_rules
+        .iter()
+        .filter(|rule| !previous_set.contains(rule))
+        .cloned()
+        .collect();
+    diff.removed_rules = previous_rules
+        .iter()
+        .filter(|rule| !current_set.contains(rule))
+        .cloned()
+        .collect();
+
+    Ok(diff)
+}
+
+fn diff_nodes(
+    previous: &[(String, Mapping)],
+    current: &[(String, Mapping)],
+    diff: &mut SubscriptionDiff,
+) {
+    let previous_map: HashMap<&str, &Mapping> = previous
+        .iter()
+        .map(|(name, node)| (name.as_str(), node))
+        .collect();
+    let current_names: HashSet<&str> = current.iter().map(|(name, _)| name.as_str()).collect();
+
+    for (name, node) in current {
+        if let Some(previous_node) = previous_map.get(name.as_str()) {
+            let (old_endpoint, new_endpoint) = (endpoint(previous_node), endpoint(node));
+            if old_endpoint != new_endpoint {
+                diff.endpoint_changed_nodes.push(NodeEndpointChange {
+                    name: name.clone(),
+                    old_endpoint,
+                    new_endpoint,
+                });
+            }
+        }
+    }
+
+    // 仅存在于一侧的节点：配置除名称外相同的视为重命名
+    let mut removed: Vec<(&String, String)> = previous
+        .iter()
+        .filter(|(name, _)| !current_names.contains(name.as_str()))
+        .map(|(name, node)| (name, fingerprint(node)))
+        .collect();
+    for (name, node) in current {
+        if previous_map.contains_key(name.as_str()) {
+            continue;
+        }
+        let node_fingerprint = fingerprint(node);
+        match removed
+            .iter()
+            .position(|(_, removed_fingerprint)| *removed_fingerprint == node_fingerprint)
+        {
+            Some(index) => {
+                let (old_name, _) = removed.remove(index);
+                diff.renamed_nodes.push(NodeRename {
+                    old_name: old_name.clone(),
+                    new_name: name.clone(),
+                });
+            }
+            None => diff.added_nodes.push(name.clone()),
+        }
+    }
+    diff.removed_nodes = removed.into_iter().map(|(name, _)| name.clone()).collect();
+}
+
+fn diff_groups(
+    previous: &[(String, Mapping)],
+    current: &[(String, Mapping)],
+    diff: &mut SubscriptionDiff,
+) {
+    let previous_map: HashMap<&str, &Mapping> = previous
+        .iter()
+        .map(|(name, group)| (name.as_str(), group))
+        .collect();
+    let current_names: HashSet<&str> = current.iter().map(|(name, _)| name.as_str()).collect();
+
+    for (name, group) in current {
+        match previous_map.get(name.as_str()) {
+            None => diff.added_groups.push(name.clone()),
+            Some(previous_group) => {
+                let is_changed = ["type", "proxies", "use"]
+                    .iter()
+                    .any(|key| previous_group.get(*key) != group.get(*key));
+                if is_changed {
+                    diff.changed_groups.push(name.clone());
+                }
+            }
+        }
+    }
+    diff.removed_groups = previous
+        .iter()
+        .filter(|(name, _)| !current_names.contains(name.as_str()))
+        .map(|(name, _)| name.clone())
+        .collect();
+}
+
+fn parse_root(content: &str, label: &str) -> Result<Mapping, String> {
+    match serde_yaml_ng::from_str::<YamlValue>(content) {
+        Ok(YamlValue::Mapping(root)) => Ok(root),
+        Ok(_) => Err(format!("{}配置根节点不是 Map", label)),
+        Err(e) => Err(format!("解析{}配置失败：{}", label, e)),
+    }
+}
+
+fn named_items(root: &Mapping, key: &str) -> Vec<(String, Mapping)> {
+    root.get(key)
+        .and_then(|value| value.as_sequence())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_mapping())
+                .filter_map(|item| {
+                    let name = item.get("name")?.as_str()?.to_string();
+                    Some((name, item.clone()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn rules(root: &Mapping) -> Vec<String> {
+    root.get("rules")
+        .and_then(|value| value.as_sequence())
+        .map(|rules| {
+            rules
+                .iter()
+                .filter_map(|rule| rule.as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn endpoint(node: &Mapping) -> String {
+    let field = |key: &str| match node.get(key) {
+        Some(YamlValue::String(value)) => value.clone(),
+        Some(YamlValue::Number(value)) => value.to_string(),
+        _ => String::new(),
+    };
+    format!("{}://{}:{}", field("type"), field("server"), field("port"))
+}
+
+// 去除名称后的配置内容（转为 JSON 值后键按字典序排列，字段顺序不影响比较）
+fn fingerprint(node: &Mapping) -> String {
+    let mut node = node.clone();
+    node.remove("name");
+    serde_json::to_value(&node)
+        .map(|value| value.to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NodeEndpointChange, NodeRename, diff_configs};
+
+    #[test]
+    fn diff_nodes_groups_and_rules() -> Result<(), String> {
+        let previous = r#"
+proxies:
+  - {name: HK 01, type: trojan, server: a.com, port: 443, password: pw}
+  - {name: JP 01, type: trojan, server: b.com, port: 443, password: pw}
+  - {name: US 01, type: trojan, server: c.com, port: 443, password: pw}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: [HK 01, JP 01, US 01]}
+  - {name: OLD, type: select, proxies: [HK 01]}
+rules:
+  - DOMAIN,a.com,PROXY
+  - MATCH,PROXY
+"#;
+        let current = r#"
+proxies:
+  - {name: 香港 01, type: trojan, server: a.com, port: 443, password: pw}
+  - {name: JP 01, type: trojan, server: b2.com, port: 443, password: pw}
+  - {name: SG 01, type: trojan, server: d.com, port: 443, password: pw}
+proxy-groups:
+  - {name: PROXY, type: select, proxies: [香港 01, JP 01, SG 01]}
+rules:
+  - MATCH,PROXY
+"#;
+        let diff = diff_configs(previous, current)?;
+        assert_eq!(diff.added_nodes, vec!["SG 01"]);
+        assert_eq!(diff.removed_nodes, vec!["US 01"]);
+        assert_eq!(
+            diff.renamed_nodes,
+            vec![NodeRename {
+                old_name: "HK 01".to_string(),
+                new_name: "香港 01".to_string(),
+            }]
+        );
+        assert_eq!(
+            diff.endpoint_changed_nodes,
+            vec![NodeEndpointChange {
+                name: "JP 01".to_string(),
+                old_endpoint: "trojan://b.com:443".to_string(),
+                new_endpoint: "trojan://b2.com:443".to_string(),
+            }]
+        );
+        assert_eq!(diff.changed_groups, vec!["PROXY"]);
+        assert_eq!(diff.removed_groups, vec!["OLD"]);
+        assert_eq!(diff.removed_rules, vec!["DOMAIN,a.com,PROXY"]);
+        assert!(diff.added_rules.is_empty());
+        assert!(diff_configs(current, current)?.is_empty());
+        Ok(())
+    }
+}
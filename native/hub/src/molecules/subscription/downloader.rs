@@ -1,16 +1,35 @@
 // 订阅下载器
 // 处理订阅配置的 HTTP 下载，支持多种代理模式
 
+use super::age_hybrid::{self, HybridIdentity};
+use super::cache::{self, CachedSubscription};
+use super::diff::{SubscriptionDiff, diff_configs};
+use super::retry::{AttemptError, DownloadAttempt, RetryPolicy};
+use super::source;
+use crate::atoms::ProxyParser;
+use crate::atoms::path_service;
 use crate::molecules::ProxyMode;
-use reqwest::{Client, Proxy};
+use crate::molecules::http_client::{
+    HttpRequestOptions, apply_request_options, create_http_client, select_download_node,
+};
+use age::armor::ArmoredReader;
+use age::secrecy::SecretString;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use futures_util::StreamExt;
+use reqwest::StatusCode;
+use reqwest::header::{
+    CONTENT_ENCODING, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+    USER_AGENT,
+};
 use rinf::{DartSignal, RustSignal};
 use serde::{Deserialize, Serialize};
+use std::io::Read;
 use std::str::FromStr;
-use std::time::Duration;
 
 const AGE_ARMOR_HEADER: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
 const AGE_X25519_SECRET_KEY_PREFIX: &str = "AGE-SECRET-KEY-1";
-const AGE_HYBRID_SECRET_KEY_PREFIX: &str = "AGE-SECRET-KEY-PQ-1";
+const AGE_BINARY_HEADER: &[u8] = b"age-encryption.org/v1";
+const AGE_HYBRID_STANZA_PREFIX: &[u8] = b"-> mlkem768x25519";
 
 // Dart → Rust：下载订阅请求
 #[derive(Deserialize, DartSignal)]
@@ -22,6 +41,18 @@ pub struct DownloadSubscriptionRequest {
     pub timeout_seconds: u64,
     pub mixed_port: u16, // Clash 混合端口
     pub age_secret_key: Option<String>,
+    pub retry_policy: Option<RetryPolicy>, // 为空时使用默认重试策略
+    pub request_options: Option<HttpRequestOptions>, // 自定义请求头、认证与 TLS 选项
+    pub max_body_bytes: Option<u64>,       // 内容大小上限，为空时使用默认值（32 MB）
+}
+
+// Rust → Dart：订阅下载进度通知（仅大文件发送）
+#[derive(Serialize, RustSignal)]
+pub struct DownloadSubscriptionProgress {
+    pub request_id: String, // 与下载请求一致；自动更新时为订阅标识符
+    pub progress: f64,      // 0.0 - 1.0，总大小未知时为 0
+    pub downloaded: u64,    // 已下载字节数
+    pub total: u64,         // 总字节数，未知时为 0
 }
 
 // Rust → Dart：下载订阅响应
@@ -31,7 +62,12 @@ pub struct DownloadSubscriptionResponse {
     pub is_successful: bool,
     pub content: String,
     pub subscription_info: Option<SubscriptionInfoData>,
-    pub error_message: Option<String>,
+    pub error_message: Option<String>, // 失败原因；返回缓存内容时为下载失败的原因
+    pub is_not_modified: bool,         // 服务器返回 304，内容未变化
+    pub is_stale: bool,                // 下载失败，内容为上次成功下载的缓存
+    pub route: Option<ProxyMode>,      // 最终成功的代理模式
+    pub failed_attempts: Vec<DownloadAttempt>, // 失败的尝试记录
+    pub diff: Option<SubscriptionDiff>, // 与上次缓存内容相比的变化
 }
 
 // 订阅信息
@@ -41,35 +77,62 @@ pub struct SubscriptionInfoData {
     pub download: Option<u64>,
     pub total: Option<u64>,
     pub expire: Option<i64>,
+    pub update_interval_hours: Option<u64>, // profile-update-interval 头（小时）
+    pub profile_name: Option<String>,       // content-disposition 中的文件名（不含扩展名）
+    pub web_page_url: Option<String>,       // profile-web-page-url 头
+    pub support_url: Option<String>,        // support-url 头
+}
+
+impl SubscriptionInfoData {
+    // 是否包含流量或到期信息
+    pub fn has_usage(&self) -> bool {
+        self.upload.is_some()
+            || self.download.is_some()
+            || self.total.is_some()
+            || self.expire.is_some()
+    }
 }
 
 impl DownloadSubscriptionRequest {
     pub async fn handle(self) {
         log::info!("收到下载订阅请求 [{}]：{}", self.request_id, self.url);
 
-        let result = download_subscription(
-            &self.url,
-            self.proxy_mode,
-            &self.user_agent,
-            self.timeout_seconds,
-            self.mixed_port,
-            self.age_secret_key.as_deref(),
-        )
+        let retry_policy = self.retry_policy.unwrap_or_default();
+        let request_options = self.request_options.unwrap_or_default();
+        let result = download_subscription(&DownloadParams {
+            request_id: &self.request_id,
+            url: &self.url,
+            proxy_mode: self.proxy_mode,
+            retry_policy: &retry_policy,
+            user_agent: &self.user_agent,
+            timeout_seconds: self.timeout_seconds,
+            mixed_port: self.mixed_port,
+            age_secret_key: self.age_secret_key.as_deref(),
+            request_options: &request_options,
+            max_body_bytes: self
+                .max_body_bytes
+                .unwrap_or(source::DEFAULT_MAX_BODY_BYTES),
+        })
         .await;
 
         let response = match result {
-            Ok((content, info)) => {
+            Ok(outcome) => {
                 log::info!(
                     "订阅下载成功 [{}]，内容长度：{} 字节",
                     self.request_id,
-                    content.len()
+                    outcome.content.len()
                 );
                 DownloadSubscriptionResponse {
                     request_id: self.request_id,
                     is_successful: true,
-                    content,
-                    subscription_info: info,
-                    error_message: None,
+                    content: outcome.content,
+                    subscription_info: outcome.subscription_info,
+                    is_stale: outcome.stale_reason.is_some(),
+                    error_message: outcome.stale_reason,
+                    is_not_modified: outcome.is_not_modified,
+                    route: outcome.route,
+                    failed_attempts: outcome.failed_attempts,
+                    diff: outcome.diff,
                 }
             }
             Err(e) => {
@@ -79,7 +142,12 @@ impl DownloadSubscriptionRequest {
                     is_successful: false,
                     content: String::new(),
                     subscription_info: None,
-                    error_message: Some(e.to_string()),
+                    error_message: Some(e.message),
+                    is_not_modified: false,
+                    is_stale: false,
+                    route: None,
+                    failed_attempts: e.failed_attempts,
+                    diff: None,
                 }
             }
         };
@@ -88,110 +156,510 @@ impl DownloadSubscriptionRequest {
     }
 }
 
+// 订阅下载结果
+pub struct DownloadOutcome {
+    pub content: String,
+    pub subscription_info: Option<SubscriptionInfoData>,
+    pub is_not_modified: bool,        // 服务器返回 304，内容来自缓存
+    pub stale_reason: Option<String>, // 下载失败时返回缓存内容的原因
+    pub route: Option<ProxyMode>,     // 最终成功的代理模式（使用缓存时为空）
+    pub failed_attempts: Vec<DownloadAttempt>,
+    pub diff: Option<SubscriptionDiff>, // 下载到新内容且存在旧缓存时的变化
+}
+
+// 订阅下载失败
+#[derive(Debug)]
+pub struct DownloadFailure {
+    pub message: String,
+    pub failed_attempts: Vec<DownloadAttempt>,
+}
+
+impl DownloadFailure {
+    fn new(message: impl Into<String>, failed_attempts: Vec<DownloadAttempt>) -> Self {
+        Self {
+            message: message.into(),
+            failed_attempts,
+        }
+    }
+}
+
+impl std::fmt::Display for DownloadFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+// 单次请求结果
+enum FetchResult {
+    NotModified(Option<SubscriptionInfoData>),
+    Body(CachedSubscription),
+}
+
+// 订阅下载参数
+pub struct DownloadParams<'a> {
+    pub request_id: &'a str, // 用于进度通知
+    pub url: &'a str,
+    pub proxy_mode: ProxyMode,
+    pub retry_policy: &'a RetryPolicy,
+    pub user_agent: &'a str,
+    pub timeout_seconds: u64,
+    pub mixed_port: u16,
+    pub age_secret_key: Option<&'a str>,
+    pub request_options: &'a HttpRequestOptions,
+    pub max_body_bytes: u64,
+}
+
 // 下载订阅配置并返回内容与订阅信息。
-// 支持代理模式、超时与自定义 User-Agent。
+// 支持 HTTP(S)、file:// 与 data: 来源，以及代理模式回退链、重试、超时与自定义 User-Agent；
+// 携带缓存的 ETag/Last-Modified 发起条件请求，全部失败时返回上次成功的内容。
 pub async fn download_subscription(
-    url: &str,
-    proxy_mode: ProxyMode,
-    user_agent: &str,
-    timeout_seconds: u64,
-    mixed_port: u16,
-    age_secret_key: Option<&str>,
-) -> Result<(String, Option<SubscriptionInfoData>), Box<dyn std::error::Error + Send + Sync>> {
+    params: &DownloadParams<'_>,
+) -> Result<DownloadOutcome, DownloadFailure> {
+    let url = params.url;
     log::info!("开始下载订阅：{}", url);
-    log::info!("代理模式：{:?}", proxy_mode);
+    log::info!("代理模式：{:?}", params.proxy_mode);
+
+    // 本地文件与 data: URI 无需重试与缓存
+    let (
+        body,
+        previous_body,
+        subscription_info,
+        is_not_modified,
+        stale_reason,
+        route,
+        failed_attempts,
+    ) = if let Some(local) = source::read_local(url, params.max_body_bytes) {
+        let body = local.map_err(|e| DownloadFailure::new(e, Vec::new()))?;
+        (body, None, None, false, None, None, Vec::new())
+    } else {
+        let cache_dir = path_service::subscription_cache_dir();
+        let cached = cache::load(&cache_dir, url);
+
+        let fetched = params
+            .retry_policy
+            .run(params.proxy_mode, |mode| {
+                fetch_subscription(params, mode, cached.as_ref())
+            })
+            .await;
+
+        match (fetched, cached) {
+            (Ok(success), cached) => {
+                let (body, previous_body, info, is_not_modified) = match (success.value, cached) {
+                    (FetchResult::Body(entry), cached) => {
+                        if let Err(e) = cache::save(&cache_dir, &entry) {
+                            log::warn!("保存订阅缓存失败：{}", e);
+                        }
+                        let previous_body = cached.map(|cached| cached.body);
+                        (entry.body, previous_body, entry.subscription_info, false)
+                    }
+                    (FetchResult::NotModified(info), Some(mut entry)) => {
+                        log::info!("订阅未修改（304），使用缓存内容");
+                        if info.is_some() {
+                            entry.subscription_info = info;
+                            if let Err(e) = cache::save(&cache_dir, &entry) {
+                                log::warn!("更新订阅缓存失败：{}", e);
+                            }
+                        }
+                        (entry.body, None, entry.subscription_info, true)
+                    }
+                    (FetchResult::NotModified(_), None) => {
+                        return Err(DownloadFailure::new(
+                            "服务器返回 304，但本地没有订阅缓存",
+                            success.failed_attempts,
+                        ));
+                    }
+                };
+                log::info!("订阅下载路由：{:?}", success.route);
+                (
+                    body,
+                    previous_body,
+                    info,
+                    is_not_modified,
+                    None,
+                    Some(success.route),
+                    success.failed_attempts,
+                )
+            }
+            (Err(failed_attempts), Some(entry)) => {
+                let reason = last_error(&failed_attempts);
+                log::warn!("订阅下载失败，使用缓存内容：{}", reason);
+                (
+                    entry.body,
+                    None,
+                    entry.subscription_info,
+                    false,
+                    Some(reason),
+                    None,
+                    failed_attempts,
+                )
+            }
+            (Err(failed_attempts), None) => {
+                return Err(DownloadFailure::new(
+                    last_error(&failed_attempts),
+                    failed_attempts,
+                ));
+            }
+        }
+    };
+
+    let body = match source::decompress_if_needed(body, params.max_body_bytes) {
+        Ok(body) => body,
+        Err(e) => return Err(DownloadFailure::new(e, failed_attempts)),
+    };
+    let content = match decrypt_age_content_if_needed(&body, params.age_secret_key) {
+        Ok(content) if content.is_empty() => {
+            return Err(DownloadFailure::new("订阅内容为空", failed_attempts));
+        }
+        Ok(content) => content,
+        Err(e) => return Err(DownloadFailure::new(e.to_string(), failed_attempts)),
+    };
+
+    let diff =
+        previous_body.and_then(|previous_body| diff_with_previous(previous_body, &content, params));
+
+    log::info!("订阅下载成功，内容长度：{} 字节", content.len());
+
+    Ok(DownloadOutcome {
+        content,
+        subscription_info,
+        is_not_modified,
+        stale_reason,
+        route,
+        failed_attempts,
+        diff,
+    })
+}
 
-    // 创建 HTTP 客户端
-    let client = create_http_client(proxy_mode, timeout_seconds, mixed_port)?;
+// 解析旧缓存与新内容并对比；任一侧无法解析时不返回差异
+fn diff_with_previous(
+    previous_body: Vec<u8>,
+    content: &str,
+    params: &DownloadParams<'_>,
+) -> Option<SubscriptionDiff> {
+    let result = source::decompress_if_needed(previous_body, params.max_body_bytes)
+        .and_then(|body| {
+            decrypt_age_content_if_needed(&body, params.age_secret_key).map_err(|e| e.to_string())
+        })
+        .and_then(|previous| {
+            let previous = ProxyParser::parse_subscription(&previous)?;
+            let current = ProxyParser::parse_subscription(content)?;
+            diff_configs(&previous, &current)
+        });
+    match result {
+        Ok(diff) => {
+            log::info!(
+                "订阅变化：新增节点 {}，移除节点 {}，重命名 {}，地址变更 {}，代理组变更 {}，规则 +{}/-{}",
+                diff.added_nodes.len(),
+                diff.removed_nodes.len(),
+                diff.renamed_nodes.len(),
+                diff.endpoint_changed_nodes.len(),
+                diff.added_groups.len() + diff.removed_groups.len() + diff.changed_groups.len(),
+                diff.added_rules.len(),
+                diff.removed_rules.len()
+            );
+            Some(diff)
+        }
+        Err(e) => {
+            log::warn!("无法对比订阅变化：{}", e);
+            None
+        }
+    }
+}
+
+fn last_error(failed_attempts: &[DownloadAttempt]) -> String {
+    failed_attempts
+        .last()
+        .map(|attempt| attempt.error.clone())
+        .unwrap_or_else(|| "订阅下载失败".to_string())
+}
 
-    // 发送 HTTP GET 请求
-    let response = client
-        .get(url)
-        .header("User-Agent", user_agent)
+// 发送 HTTP 请求（有缓存时携带条件请求头）
+async fn fetch_subscription(
+    params: &DownloadParams<'_>,
+    proxy_mode: ProxyMode,
+    cached: Option<&CachedSubscription>,
+) -> Result<FetchResult, AttemptError> {
+    // 指定节点模式下先切换核心的下载节点（持有到响应体读取完毕）
+    let _node_guard = select_download_node(proxy_mode, params.request_options)
+        .await
+        .map_err(|e| AttemptError::new(e, false))?;
+
+    // 创建 HTTP 客户端（配置错误重试无意义，直接切换下一模式）
+    let client = create_http_client(
+        proxy_mode,
+        params.timeout_seconds,
+        params.mixed_port,
+        params.request_options,
+    )
+    .map_err(|e| AttemptError::new(format!("创建 HTTP 客户端失败：{}", e), false))?;
+
+    // 发送 HTTP GET 请求（自定义请求头可覆盖 User-Agent）
+    let request = client.get(params.url).header(USER_AGENT, params.user_agent);
+    let mut request = apply_request_options(request, params.request_options)
+        .map_err(|e| AttemptError::new(e, false))?;
+    if let Some(cached) = cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let response = request
         .send()
-        .await?;
+        .await
+        .map_err(|e| AttemptError::new(e.to_string(), true))?;
+
+    // 解析订阅信息头
+    let subscription_info = parse_subscription_info(response.headers());
 
     // 检查 HTTP 状态码
     let status = response.status();
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(FetchResult::NotModified(subscription_info));
+    }
     if !status.is_success() {
-        return Err(format!(
-            "HTTP {}: {}",
-            status.as_u16(),
-            status.canonical_reason().unwrap_or("Unknown")
-        )
-        .into());
+        // 仅服务端错误、超时与限流值得重试
+        let is_retryable = status.is_server_error()
+            || status == StatusCode::REQUEST_TIMEOUT
+            || status == StatusCode::TOO_MANY_REQUESTS;
+        return Err(AttemptError::new(
+            format!(
+                "HTTP {}: {}",
+                status.as_u16(),
+                status.canonical_reason().unwrap_or("Unknown")
+            ),
+            is_retryable,
+        ));
     }
 
-    // 解析订阅信息头
-    let subscription_info = parse_subscription_info(response.headers());
+    let header_text = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header_text(ETAG);
+    let last_modified = header_text(LAST_MODIFIED);
+
+    // brotli 压缩内容需解码后才能嗅探格式
+    let is_brotli = header_text(CONTENT_ENCODING)
+        .is_some_and(|encoding| encoding.trim().eq_ignore_ascii_case("br"));
+
+    // 声明的大小已超限时不再读取
+    let total = response.content_length().unwrap_or(0);
+    source::check_size(total, params.max_body_bytes).map_err(|e| AttemptError::new(e, false))?;
+    let content_type = header_text(CONTENT_TYPE);
+
+    // 流式读取响应体并限制大小（gzip/zstd 压缩内容稍后按魔数解压）
+    let mut body = Vec::new();
+    let mut is_sniffed = false;
+    let mut progress = ProgressReporter::new(params.request_id, total);
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AttemptError::new(e.to_string(), true))?;
+        source::check_size((body.len() + chunk.len()) as u64, params.max_body_bytes)
+            .map_err(|e| AttemptError::new(e, false))?;
+        body.extend_from_slice(&chunk);
+
+        // 前导字节足够时嗅探格式，尽早放弃网页等错误内容
+        if !is_brotli && !is_sniffed && body.len() >= source::SNIFF_LENGTH {
+            source::sniff_format(content_type.as_deref(), &body)
+                .map_err(|e| AttemptError::new(e, false))?;
+            is_sniffed = true;
+        }
+        progress.update(body.len() as u64);
+    }
+    if is_brotli {
+        body = source::decode_brotli(&body, params.max_body_bytes)
+            .map_err(|e| AttemptError::new(e, false))?;
+        log::info!("订阅内容为 brotli 压缩，已解压");
+    }
+    if !is_sniffed {
+        source::sniff_format(content_type.as_deref(), &body)
+            .map_err(|e| AttemptError::new(e, false))?;
+    }
+    progress.finish(body.len() as u64);
+    if body.is_empty() {
+        return Err(AttemptError::new("订阅内容为空", false));
+    }
 
-    // 读取响应体
-    let content = response.text().await?;
+    Ok(FetchResult::Body(CachedSubscription {
+        url: params.url.to_string(),
+        etag,
+        last_modified,
+        subscription_info,
+        saved_at: chrono::Utc::now().timestamp(),
+        body,
+    }))
+}
 
-    if content.is_empty() {
-        return Err("订阅内容为空".into());
+// 大文件下载进度通知（超过阈值后按步长发送）
+struct ProgressReporter<'a> {
+    request_id: &'a str,
+    total: u64,
+    last_reported: u64,
+    is_reporting: bool,
+}
+
+impl<'a> ProgressReporter<'a> {
+    const THRESHOLD: u64 = 1024 * 1024;
+    const STEP: u64 = 256 * 1024;
+
+    fn new(request_id: &'a str, total: u64) -> Self {
+        Self {
+            request_id,
+            total,
+            last_reported: 0,
+            is_reporting: total >= Self::THRESHOLD,
+        }
     }
 
-    let content = decrypt_age_content_if_needed(&content, age_secret_key)?;
-    if content.is_empty() {
-        return Err("订阅内容为空".into());
+    fn update(&mut self, downloaded: u64) {
+        // 总大小未知时，超过阈值才开始通知
+        if !self.is_reporting && downloaded >= Self::THRESHOLD {
+            self.is_reporting = true;
+        }
+        if self.is_reporting && downloaded - self.last_reported >= Self::STEP {
+            self.send(downloaded);
+        }
     }
 
-    log::info!("订阅下载成功，内容长度：{} 字节", content.len());
+    fn finish(&mut self, downloaded: u64) {
+        if self.is_reporting && downloaded != self.last_reported {
+            self.send(downloaded);
+        }
+    }
 
-    Ok((content, subscription_info))
+    fn send(&mut self, downloaded: u64) {
+        self.last_reported = downloaded;
+        let progress = if self.total > 0 {
+            (downloaded as f64 / self.total as f64).min(1.0)
+        } else {
+            0.0
+        };
+        DownloadSubscriptionProgress {
+            request_id: self.request_id.to_string(),
+            progress,
+            downloaded,
+            total: self.total,
+        }
+        .send_signal_to_dart();
+    }
 }
 
+// 解密 age 加密的订阅内容（支持 ASCII armor 与二进制格式、x25519 与 mlkem768-x25519 私钥、scrypt 密码），
+// 未加密的内容按 UTF-8 原样返回
 fn decrypt_age_content_if_needed(
-    content: &str,
+    content: &[u8],
     age_secret_key: Option<&str>,
 ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    if !content.starts_with(AGE_ARMOR_HEADER) {
-        return Ok(content.to_string());
+    if !is_age_encrypted(content) {
+        return Ok(String::from_utf8_lossy(content).into_owned());
     }
 
     let Some(secret_key) = age_secret_key.map(str::trim).filter(|key| !key.is_empty()) else {
-        return Err("订阅内容已使用 age 加密，请填写 age-secret-key".into());
+        return Err("订阅内容已使用 age 加密，请填写 age-secret-key 或密码".into());
+    };
+
+    // 先去除 armor，便于检查文件头中的接收方类型
+    let mut payload = Vec::new();
+    ArmoredReader::new(content)
+        .read_to_end(&mut payload)
+        .map_err(|e| format!("age armor 格式损坏：{}", e))?;
+
+    let decryptor = age::Decryptor::new_buffered(payload.as_slice()).map_err(|e| match e {
+        age::DecryptError::UnknownFormat => "age 文件版本不受支持".to_string(),
+        e => format!("age 文件头损坏：{}", e),
+    })?;
+
+    let reader = if decryptor.is_scrypt() {
+        // 密码加密的文件：整个 age-secret-key 字段视为密码
+        let identity = age::scrypt::Identity::new(SecretString::from(secret_key.to_string()));
+        decryptor
+            .decrypt(std::iter::once(&identity as &dyn age::Identity))
+            .map_err(|e| match e {
+                age::DecryptError::DecryptionFailed | age::DecryptError::KeyDecryptionFailed => {
+                    "age 密码错误，无法解密订阅内容".to_string()
+                }
+                age::DecryptError::ExcessiveWork { required, target } => format!(
+                    "age 密码加密强度过高（工作因子 {}，上限 {}），已拒绝解密",
+                    required, target
+                ),
+                e => age_decrypt_error_message(e),
+            })?
+    } else {
+        let has_hybrid_recipient = has_hybrid_recipient(&payload);
+        let has_hybrid_secret_key = secret_key
+            .lines()
+            .any(|line| line.trim().starts_with(age_hybrid::SECRET_KEY_PREFIX));
+        let identities = parse_age_identities(secret_key)?;
+        decryptor
+            .decrypt(identities.iter().map(|identity| identity.as_ref()))
+            .map_err(|e| match e {
+                age::DecryptError::NoMatchingKeys
+                    if has_hybrid_recipient && !has_hybrid_secret_key =>
+                {
+                    format!(
+                        "订阅使用 mlkem768-x25519 混合密钥加密，请填写 {} 开头的混合私钥",
+                        age_hybrid::SECRET_KEY_PREFIX
+                    )
+                }
+                age::DecryptError::NoMatchingKeys => {
+                    "age-secret-key 与订阅内容的加密接收方不匹配".to_string()
+                }
+                e => age_decrypt_error_message(e),
+            })?
     };
 
-    let (identities, has_hybrid_secret_key) = parse_x25519_identities(secret_key)?;
+    let mut plaintext = Vec::new();
+    std::io::BufReader::new(reader)
+        .read_to_end(&mut plaintext)
+        .map_err(|e| format!("age 加密内容损坏或被截断：{}", e))?;
+    let decrypted =
+        String::from_utf8(plaintext).map_err(|e| format!("age 解密结果不是有效 UTF-8：{}", e))?;
+    log::info!("订阅 age 解密成功");
+    Ok(decrypted)
+}
 
-    for identity in identities {
-        match age::decrypt(&identity, content.as_bytes()) {
-            Ok(bytes) => {
-                let decrypted = String::from_utf8(bytes)
-                    .map_err(|e| format!("age 解密结果不是有效 UTF-8：{}", e))?;
-                log::info!("订阅 age 解密成功");
-                return Ok(decrypted);
-            }
-            Err(e) => {
-                log::debug!("当前 age-secret-key 无法解密订阅：{}", e);
-            }
-        }
-    }
+fn is_age_encrypted(content: &[u8]) -> bool {
+    let trimmed = content.trim_ascii_start();
+    trimmed.starts_with(AGE_ARMOR_HEADER.as_bytes()) || trimmed.starts_with(AGE_BINARY_HEADER)
+}
 
-    if has_hybrid_secret_key {
-        return Err(
-            "当前内置解密仅支持 x25519 age-secret-key，暂不支持 mlkem768-x25519 key".into(),
-        );
-    }
+// 检查文件头是否包含 mlkem768-x25519 接收方节
+fn has_hybrid_recipient(payload: &[u8]) -> bool {
+    payload
+        .split(|byte| *byte == b'\n')
+        .take_while(|line| !line.starts_with(b"---"))
+        .any(|line| line.starts_with(AGE_HYBRID_STANZA_PREFIX))
+}
 
-    Err("age-secret-key 无法解密订阅内容".into())
+fn age_decrypt_error_message(error: age::DecryptError) -> String {
+    match error {
+        age::DecryptError::InvalidMac => "age 文件头校验失败，内容可能被篡改或损坏".to_string(),
+        age::DecryptError::InvalidHeader | age::DecryptError::UnknownFormat => {
+            format!("age 文件头损坏：{}", error)
+        }
+        age::DecryptError::Io(e) => format!("读取 age 加密内容失败：{}", e),
+        e => format!("age 解密失败：{}", e),
+    }
 }
 
-fn parse_x25519_identities(
+// 解析 x25519 与 mlkem768-x25519 私钥
+fn parse_age_identities(
     secret_key: &str,
-) -> Result<(Vec<age::x25519::Identity>, bool), Box<dyn std::error::Error + Send + Sync>> {
-    let mut identities = Vec::new();
-    let mut has_hybrid_secret_key = false;
+) -> Result<Vec<Box<dyn age::Identity>>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut identities: Vec<Box<dyn age::Identity>> = Vec::new();
 
     for line in secret_key.lines().map(str::trim) {
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
 
-        if line.starts_with(AGE_HYBRID_SECRET_KEY_PREFIX) {
-            has_hybrid_secret_key = true;
+        if line.starts_with(age_hybrid::SECRET_KEY_PREFIX) {
+            identities.push(Box::new(HybridIdentity::from_bech32(line)?));
             continue;
         }
 
@@ -201,61 +669,27 @@ fn parse_x25519_identities(
 
         let identity = age::x25519::Identity::from_str(line)
             .map_err(|e| format!("age-secret-key 格式无效：{}", e))?;
-        identities.push(identity);
-    }
-
-    if identities.is_empty() && has_hybrid_secret_key {
-        return Err(
-            "当前内置解密仅支持 x25519 age-secret-key，暂不支持 mlkem768-x25519 key".into(),
-        );
+        identities.push(Box::new(identity));
     }
 
     if identities.is_empty() {
         return Err("age-secret-key 未包含有效私钥".into());
     }
 
-    Ok((identities, has_hybrid_secret_key))
-}
-
-// 创建 HTTP 客户端
-fn create_http_client(
-    proxy_mode: ProxyMode,
-    timeout_seconds: u64,
-    mixed_port: u16,
-) -> Result<Client, Box<dyn std::error::Error + Send + Sync>> {
-    let mut builder = Client::builder()
-        .timeout(Duration::from_secs(timeout_seconds))
-        .connect_timeout(Duration::from_secs(10)) // 连接超时
-        .danger_accept_invalid_certs(false); // 验证 SSL 证书
-
-    // 根据代理模式配置客户端
-    match proxy_mode {
-        ProxyMode::Direct => {
-            log::debug!("使用直连模式");
-            // 不设置代理
-        }
-        ProxyMode::System => {
-            log::debug!("使用系统代理模式");
-            // reqwest 默认会读取系统环境变量（HTTP_PROXY, HTTPS_PROXY）
-            // 无需额外配置
-        }
-        ProxyMode::Core => {
-            log::debug!("使用核心代理模式：127.0.0.1:{}", mixed_port);
-            let proxy_url = format!("http://127.0.0.1:{}", mixed_port);
-            let proxy = Proxy::all(&proxy_url)?;
-            builder = builder.proxy(proxy);
-        }
-    }
-
-    Ok(builder.build()?)
+    Ok(identities)
 }
 
 // 解析订阅信息头（subscription-userinfo）。
 // 示例：upload=0; download=123; total=1073741824; expire=1735689600
 fn parse_subscription_info(headers: &reqwest::header::HeaderMap) -> Option<SubscriptionInfoData> {
-    let header_value = headers.get("subscription-userinfo")?.to_str().ok()?;
-
-    log::debug!("解析订阅信息头：{}", header_value);
+    // 部分机场直接发送 UTF-8 字节，不能使用 to_str
+    let header_text = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| std::str::from_utf8(value.as_bytes()).ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
 
     let mut upload = None;
     let mut download = None;
@@ -263,35 +697,132 @@ fn parse_subscription_info(headers: &reqwest::header::HeaderMap) -> Option<Subsc
     let mut expire = None;
 
     // 解析键值对
-    for pair in header_value.split(';') {
-        let pair = pair.trim();
-        if let Some((key, value)) = pair.split_once('=') {
-            let key = key.trim();
-            let value = value.trim();
-
-            match key {
-                "upload" => upload = value.parse::<u64>().ok(),
-                "download" => download = value.parse::<u64>().ok(),
-                "total" => total = value.parse::<u64>().ok(),
-                "expire" => expire = value.parse::<i64>().ok(),
-                _ => {}
+    if let Some(header_value) = header_text("subscription-userinfo") {
+        log::debug!("解析订阅信息头：{}", header_value);
+
+        for pair in header_value.split(';') {
+            let pair = pair.trim();
+            if let Some((key, value)) = pair.split_once('=') {
+                let key = key.trim();
+                let value = value.trim();
+
+                match key {
+                    "upload" => upload = value.parse::<u64>().ok(),
+                    "download" => download = value.parse::<u64>().ok(),
+                    "total" => total = value.parse::<u64>().ok(),
+                    "expire" => expire = value.parse::<i64>().ok(),
+                    _ => {}
+                }
             }
         }
     }
 
+    let info = SubscriptionInfoData {
+        upload,
+        download,
+        total,
+        expire,
+        // 更新间隔（小时）
+        update_interval_hours: header_text("profile-update-interval")
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|hours| *hours > 0),
+        profile_name: header_text("content-disposition").and_then(parse_content_disposition),
+        web_page_url: header_text("profile-web-page-url").map(str::to_string),
+        support_url: header_text("support-url").map(str::to_string),
+    };
+
     // 如果至少有一个字段有值，则返回订阅信息
-    if upload.is_some() || download.is_some() || total.is_some() || expire.is_some() {
-        Some(SubscriptionInfoData {
-            upload,
-            download,
-            total,
-            expire,
-        })
+    if info.has_usage()
+        || info.update_interval_hours.is_some()
+        || info.profile_name.is_some()
+        || info.web_page_url.is_some()
+        || info.support_url.is_some()
+    {
+        Some(info)
     } else {
         None
     }
 }
 
+// 从 content-disposition 中提取配置名称。
+// 优先 RFC 5987 的 filename*，其次 filename；支持百分号编码、
+// RFC 2047（=?UTF-8?B?...?=）与 base64: 前缀的文件名，并去掉常见扩展名。
+fn parse_content_disposition(value: &str) -> Option<String> {
+    let mut filename = None;
+    let mut extended_filename = None;
+
+    for param in value.split(';').map(str::trim) {
+        let Some((key, raw)) = param.split_once('=') else {
+            continue;
+        };
+        let raw = raw.trim().trim_matches('"');
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                // 格式：charset'language'value
+                let encoded = raw.splitn(3, '\'').nth(2).unwrap_or(raw);
+                extended_filename = urlencoding::decode(encoded).ok().map(|s| s.into_owned());
+            }
+            "filename" => filename = Some(decode_filename(raw)),
+            _ => {}
+        }
+    }
+
+    let name = extended_filename.or(filename)?;
+    let name = [".yaml", ".yml", ".txt", ".conf", ".json"]
+        .iter()
+        .find_map(|ext| {
+            name.len()
+                .checked_sub(ext.len())
+                .filter(|&at| name.is_char_boundary(at) && name[at..].eq_ignore_ascii_case(ext))
+                .map(|at| &name[..at])
+        })
+        .unwrap_or(&name)
+        .trim()
+        .to_string();
+
+    (!name.is_empty()).then_some(name)
+}
+
+fn decode_filename(raw: &str) -> String {
+    let decode_base64 = |encoded: &str| {
+        BASE64
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+    };
+
+    // RFC 2047 编码字
+    if let Some(inner) = raw.strip_prefix("=?").and_then(|s| s.strip_suffix("?="))
+        && let [_charset, encoding, text] = inner.splitn(3, '?').collect::<Vec<_>>()[..]
+    {
+        let decoded = match encoding.to_ascii_uppercase().as_str() {
+            "B" => decode_base64(text),
+            "Q" => urlencoding::decode(&text.replace('_', " ").replace('=', "%"))
+                .ok()
+                .map(|s| s.into_owned()),
+            _ => None,
+        };
+        if let Some(decoded) = decoded {
+            return decoded;
+        }
+    }
+
+    if let Some(encoded) = raw.strip_prefix("base64:")
+        && let Some(decoded) = decode_base64(encoded)
+    {
+        return decoded;
+    }
+
+    // 百分号编码的普通 filename
+    if raw.contains('%')
+        && let Ok(decoded) = urlencoding::decode(raw)
+    {
+        return decoded.into_owned();
+    }
+
+    raw.to_string()
+}
+
 // 初始化 Dart 信号监听器
 pub fn init() {
     use tokio::spawn;
@@ -309,8 +840,9 @@ pub fn init() {
 
 #[cfg(test)]
 mod tests {
-    use super::decrypt_age_content_if_needed;
-    use age::secrecy::ExposeSecret;
+    use super::{HybridIdentity, decrypt_age_content_if_needed, parse_content_disposition};
+    use age::secrecy::{ExposeSecret, SecretString};
+    use std::io::Write;
 
     #[test]
     fn decrypt_age_content_with_x25519_secret_key()
@@ -322,26 +854,111 @@ mod tests {
         let secret_key = identity.to_string();
 
         let decrypted =
-            decrypt_age_content_if_needed(&encrypted, Some(secret_key.expose_secret()))?;
+            decrypt_age_content_if_needed(encrypted.as_bytes(), Some(secret_key.expose_secret()))?;
 
         assert_eq!(decrypted, plaintext);
         Ok(())
     }
 
+    // 混合私钥与 x25519 私钥可以同时填写，按接收方节自动匹配
     #[test]
-    fn reject_hybrid_secret_key_with_clear_error()
+    fn decrypt_age_content_with_hybrid_secret_key()
     -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let identity = age::x25519::Identity::generate();
+        use bech32::ToBase32;
+
+        let seed = rand::random::<[u8; 32]>();
+        let identity = HybridIdentity::from_seed(&seed);
         let recipient = identity.to_public();
-        let encrypted = age::encrypt_and_armor(&recipient, b"proxies: []")?;
+        let plaintext = "proxies:\n  - name: pq\n";
+        let encrypted = age::encrypt_and_armor(&recipient, plaintext.as_bytes())?;
+
+        let hybrid_key = bech32::encode(
+            "age-secret-key-pq-",
+            seed.to_base32(),
+            bech32::Variant::Bech32,
+        )?
+        .to_uppercase();
+        let unrelated_key = age::x25519::Identity::generate().to_string();
+        let secret_key = format!("{}\n{}", unrelated_key.expose_secret(), hybrid_key);
+
+        let decrypted = decrypt_age_content_if_needed(encrypted.as_bytes(), Some(&secret_key))?;
+        assert_eq!(decrypted, plaintext);
 
-        let err =
-            match decrypt_age_content_if_needed(&encrypted, Some("AGE-SECRET-KEY-PQ-1INVALID")) {
-                Ok(_) => return Err("预期 PQ key 会被当前内置解密拒绝".into()),
-                Err(e) => e,
-            };
+        // 仅填写 x25519 私钥时提示需要混合私钥
+        let err = match decrypt_age_content_if_needed(
+            encrypted.as_bytes(),
+            Some(unrelated_key.expose_secret()),
+        ) {
+            Ok(_) => return Err("预期缺少混合私钥时解密失败".into()),
+            Err(e) => e,
+        };
+        assert!(err.to_string().contains("AGE-SECRET-KEY-PQ-1"));
+        Ok(())
+    }
+
+    #[test]
+    fn decrypt_binary_age_content_with_passphrase()
+    -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let plaintext = "proxies: []\n";
+        let mut recipient = age::scrypt::Recipient::new(SecretString::from("correct horse"));
+        // 测试中降低工作因子，避免 scrypt 耗时过长
+        recipient.set_work_factor(10);
+        let encryptor =
+            age::Encryptor::with_recipients(std::iter::once(&recipient as &dyn age::Recipient))?;
+        let mut encrypted = Vec::new();
+        let mut writer = encryptor.wrap_output(&mut encrypted)?;
+        writer.write_all(plaintext.as_bytes())?;
+        writer.finish()?;
+
+        let decrypted = decrypt_age_content_if_needed(&encrypted, Some("correct horse"))?;
+        assert_eq!(decrypted, plaintext);
+
+        let err = match decrypt_age_content_if_needed(&encrypted, Some("wrong")) {
+            Ok(_) => return Err("预期错误密码解密失败".into()),
+            Err(e) => e,
+        };
+        assert!(err.to_string().contains("密码错误"));
+
+        let err = match decrypt_age_content_if_needed(&encrypted, None) {
+            Ok(_) => return Err("预期缺少密码时解密失败".into()),
+            Err(e) => e,
+        };
+        assert!(err.to_string().contains("请填写"));
 
-        assert!(err.to_string().contains("mlkem768-x25519"));
+        // 二进制内容使用 x25519 私钥时提示接收方不匹配
+        let identity = age::x25519::Identity::generate();
+        let recipient = identity.to_public();
+        let encrypted = age::encrypt(&recipient, plaintext.as_bytes())?;
+        let other = age::x25519::Identity::generate().to_string();
+        let err = match decrypt_age_content_if_needed(&encrypted, Some(other.expose_secret())) {
+            Ok(_) => return Err("预期私钥不匹配时解密失败".into()),
+            Err(e) => e,
+        };
+        assert!(err.to_string().contains("不匹配"));
         Ok(())
     }
+
+    #[test]
+    fn parse_profile_name_from_content_disposition() {
+        assert_eq!(
+            parse_content_disposition(
+                "attachment; filename=\"fallback.yaml\"; filename*=UTF-8''%E6%9C%BA%E5%9C%BA.yaml"
+            )
+            .as_deref(),
+            Some("机场")
+        );
+        assert_eq!(
+            parse_content_disposition("attachment; filename=\"=?UTF-8?B?5py65Zy6?=\"").as_deref(),
+            Some("机场")
+        );
+        assert_eq!(
+            parse_content_disposition("attachment; filename=base64:5py65Zy6").as_deref(),
+            Some("机场")
+        );
+        assert_eq!(
+            parse_content_disposition("attachment; filename=My%20Sub.YML").as_deref(),
+            Some("My Sub")
+        );
+        assert_eq!(parse_content_disposition("inline"), None);
+    }
 }
@@ -0,0 +1,81 @@
+// 分享链接导出
+// 将 Clash 配置中的代理节点导出为分享链接与 Base64 订阅正文
+
+use crate::atoms::ProxyParser;
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+
+// Dart → Rust：导出分享链接请求
+#[derive(Deserialize, DartSignal)]
+pub struct ExportProxyLinksRequest {
+    pub request_id: String, // 请求标识符，用于响应匹配
+    pub config_content: String,
+    pub proxy_names: Vec<String>, // 为空时导出全部节点
+}
+
+// Rust → Dart：导出分享链接响应
+#[derive(Serialize, RustSignal)]
+pub struct ExportProxyLinksResponse {
+    pub request_id: String, // 请求标识符，用于请求匹配
+    pub is_successful: bool,
+    pub links: Vec<String>,
+    pub subscription_content: String, // Base64 订阅正文
+    pub skipped_proxies: Vec<String>, // 无法导出的节点及原因
+    pub error_message: String,
+}
+
+impl ExportProxyLinksRequest {
+    // 处理分享链接导出请求
+    pub fn handle(self) {
+        log::info!(
+            "收到分享链接导出请求 [{}]，指定节点数量：{}",
+            self.request_id,
+            self.proxy_names.len()
+        );
+
+        let response =
+            match ProxyParser::export_proxy_links(&self.config_content, &self.proxy_names) {
+                Ok(export) => {
+                    log::info!(
+                        "分享链接导出成功 [{}]，导出{}个，跳过{}个",
+                        self.request_id,
+                        export.links.len(),
+                        export.skipped.len()
+                    );
+                    ExportProxyLinksResponse {
+                        request_id: self.request_id,
+                        is_successful: true,
+                        subscription_content: ProxyParser::encode_subscription(&export.links),
+                        links: export.links,
+                        skipped_proxies: export.skipped,
+                        error_message: String::new(),
+                    }
+                }
+                Err(e) => {
+                    log::error!("分享链接导出失败 [{}]：{}", self.request_id, e);
+                    ExportProxyLinksResponse {
+                        request_id: self.request_id,
+                        is_successful: false,
+                        links: Vec::new(),
+                        subscription_content: String::new(),
+                        skipped_proxies: Vec::new(),
+                        error_message: e,
+                    }
+                }
+            };
+
+        response.send_signal_to_dart();
+    }
+}
+
+pub fn init() {
+    use tokio::spawn;
+
+    // 分享链接导出请求监听器
+    spawn(async {
+        let receiver = ExportProxyLinksRequest::get_dart_signal_receiver();
+        while let Some(dart_signal) = receiver.recv().await {
+            dart_signal.message.handle();
+        }
+    });
+}
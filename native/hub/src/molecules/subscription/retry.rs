@@ -0,0 +1,191 @@
+// 订阅下载重试
+// 按代理模式回退链依次尝试，同一模式内指数退避重试，并受总次数预算限制。
+
+use crate::molecules::ProxyMode;
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+const DEFAULT_ATTEMPTS_PER_MODE: u32 = 1;
+const DEFAULT_INITIAL_BACKOFF_MS: u64 = 1000;
+const MAX_BACKOFF_MS: u64 = 30_000;
+
+// 重试策略（默认值等同于仅用请求的代理模式下载一次）
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug, Default)]
+pub struct RetryPolicy {
+    pub fallback_modes: Vec<ProxyMode>, // 主代理模式失败后依次尝试的模式
+    pub attempts_per_mode: u32,         // 每种模式的尝试次数（0 使用默认值）
+    pub max_total_attempts: u32,        // 总尝试次数预算（0 表示不限制）
+    pub initial_backoff_ms: u64,        // 首次重试前等待时间，之后翻倍（0 使用默认值）
+}
+
+// 失败的下载尝试
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct DownloadAttempt {
+    pub proxy_mode: ProxyMode,
+    pub attempt: u32, // 该模式下的第几次尝试，从 1 开始
+    pub error: String,
+}
+
+// 单次尝试的错误
+#[derive(Debug)]
+pub struct AttemptError {
+    pub message: String,
+    pub is_retryable: bool, // 为 false 时不再重试当前模式，直接切换下一模式
+}
+
+impl AttemptError {
+    pub fn new(message: impl Into<String>, is_retryable: bool) -> Self {
+        Self {
+            message: message.into(),
+            is_retryable,
+        }
+    }
+}
+
+// 重试成功的结果
+pub struct RetrySuccess<T> {
+    pub value: T,
+    pub route: ProxyMode,
+    pub failed_attempts: Vec<DownloadAttempt>,
+}
+
+impl RetryPolicy {
+    // 按顺序去重后的代理模式列表
+    fn routes(&self, primary: ProxyMode) -> Vec<ProxyMode> {
+        let mut routes = vec![primary];
+        for mode in &self.fallback_modes {
+            if !routes.contains(mode) {
+                routes.push(*mode);
+            }
+        }
+        routes
+    }
+
+    fn backoff(&self, retry_index: u32) -> Duration {
+        let initial = if self.initial_backoff_ms == 0 {
+            DEFAULT_INITIAL_BACKOFF_MS
+        } else {
+            self.initial_backoff_ms
+        };
+        let delay = initial.saturating_mul(1u64 << retry_index.min(16));
+        Duration::from_millis(delay.min(MAX_BACKOFF_MS))
+    }
+
+    // 依次尝试各代理模式，全部失败时返回所有失败记录
+    pub async fn run<T, F, Fut>(
+        &self,
+        primary: ProxyMode,
+        mut attempt: F,
+    ) -> Result<RetrySuccess<T>, Vec<DownloadAttempt>>
+    where
+        F: FnMut(ProxyMode) -> Fut,
+        Fut: Future<Output = Result<T, AttemptError>>,
+    {
+        let attempts_per_mode = if self.attempts_per_mode == 0 {
+            DEFAULT_ATTEMPTS_PER_MODE
+        } else {
+            self.attempts_per_mode
+        };
+        let mut failed_attempts = Vec::new();
+
+        for route in self.routes(primary) {
+            for index in 0..attempts_per_mode {
+                if self.max_total_attempts > 0
+                    && failed_attempts.len() as u32 >= self.max_total_attempts
+                {
+                    log::warn!("已用尽下载重试预算（{} 次）", self.max_total_attempts);
+                    return Err(failed_attempts);
+                }
+                if index > 0 {
+                    let delay = self.backoff(index - 1);
+                    log::info!("{:?} 模式第 {} 次重试，等待 {:?}", route, index, delay);
+                    tokio::time::sleep(delay).await;
+                }
+
+                match attempt(route).await {
+                    Ok(value) => {
+                        return Ok(RetrySuccess {
+                            value,
+                            route,
+                            failed_attempts,
+                        });
+                    }
+                    Err(e) => {
+                        log::warn!("{:?} 模式第 {} 次下载失败：{}", route, index + 1, e.message);
+                        failed_attempts.push(DownloadAttempt {
+                            proxy_mode: route,
+                            attempt: index + 1,
+                            error: e.message,
+                        });
+                        if !e.is_retryable {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        Err(failed_attempts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttemptError, RetryPolicy};
+    use crate::molecules::ProxyMode;
+
+    #[tokio::test]
+    async fn fall_back_to_next_mode_and_record_failures() -> Result<(), String> {
+        let policy = RetryPolicy {
+            fallback_modes: vec![ProxyMode::System, ProxyMode::Core, ProxyMode::Direct],
+            attempts_per_mode: 2,
+            max_total_attempts: 0,
+            initial_backoff_ms: 1,
+        };
+
+        // Core 连接失败（可重试），System 返回 403（不可重试），Direct 成功
+        let success = policy
+            .run(ProxyMode::Core, |mode| async move {
+                match mode {
+                    ProxyMode::Core => Err(AttemptError::new("连接被拒绝", true)),
+                    ProxyMode::System => Err(AttemptError::new("HTTP 403", false)),
+                    ProxyMode::Direct => Ok("content"),
+                    _ => Err(AttemptError::new("未配置", false)),
+                }
+            })
+            .await
+            .map_err(|_| "预期回退到直连成功".to_string())?;
+
+        assert_eq!(success.value, "content");
+        assert_eq!(success.route, ProxyMode::Direct);
+        let attempts: Vec<_> = success
+            .failed_attempts
+            .iter()
+            .map(|a| (a.proxy_mode, a.attempt))
+            .collect();
+        assert_eq!(
+            attempts,
+            vec![
+                (ProxyMode::Core, 1),
+                (ProxyMode::Core, 2),
+                (ProxyMode::System, 1)
+            ]
+        );
+
+        let budget = RetryPolicy {
+            max_total_attempts: 1,
+            ..policy
+        };
+        let failures = budget
+            .run(ProxyMode::Core, |_| async {
+                Err::<(), _>(AttemptError::new("超时", true))
+            })
+            .await
+            .err()
+            .unwrap_or_default();
+        assert_eq!(failures.len(), 1);
+        Ok(())
+    }
+}
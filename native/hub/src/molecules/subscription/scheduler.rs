@@ -0,0 +1,552 @@
+// 订阅自动更新调度器
+// 按用户设置或 profile-update-interval 头的间隔定时下载订阅，
+// 启动时间加入随机抖动，并发数受限，失败后按指数退避重试，下次运行时间持久化到磁盘。
+
+use super::diff::SubscriptionDiff;
+use super::downloader::{DownloadParams, SubscriptionInfoData, download_subscription};
+use super::retry::{DownloadAttempt, RetryPolicy};
+use super::source::DEFAULT_MAX_BODY_BYTES;
+use crate::atoms::path_service;
+use crate::molecules::{HttpRequestOptions, ProxyMode};
+use once_cell::sync::Lazy;
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::spawn;
+use tokio::sync::{Notify, Semaphore};
+
+const DEFAULT_MAX_CONCURRENCY: u32 = 3;
+const MAX_START_JITTER_SECS: i64 = 300;
+const MAX_IDLE_WAIT_SECS: i64 = 60;
+const FAILURE_RETRY_BASE_SECS: i64 = 5 * 60;
+const MAX_FAILURE_RETRY_SECS: i64 = 6 * 60 * 60;
+
+// Dart → Rust：设置订阅自动更新计划（替换现有全部计划）
+#[derive(Deserialize, DartSignal)]
+pub struct ScheduleSubscriptionsRequest {
+    pub subscriptions: Vec<ScheduledSubscription>,
+    pub max_concurrency: u32, // 同时下载的订阅数（0 使用默认值）
+}
+
+// 单个订阅的自动更新设置
+#[derive(Deserialize, Serialize, SignalPiece, Clone, Debug)]
+pub struct ScheduledSubscription {
+    pub subscription_id: String,
+    pub url: String,
+    pub proxy_mode: ProxyMode,
+    pub user_agent: String,
+    pub timeout_seconds: u64,
+    pub mixed_port: u16,
+    pub age_secret_key: Option<String>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub request_options: Option<HttpRequestOptions>,
+    pub max_body_bytes: Option<u64>, // 为空时使用默认上限
+    pub interval_minutes: u64,       // 用户设置的间隔，0 表示使用 profile-update-interval 头
+}
+
+// Rust → Dart：订阅自动更新完成
+#[derive(Serialize, RustSignal)]
+pub struct SubscriptionUpdated {
+    pub subscription_id: String,
+    pub is_successful: bool,
+    pub content: String,
+    pub subscription_info: Option<SubscriptionInfoData>,
+    pub error_message: Option<String>,
+    pub is_not_modified: bool,
+    pub is_stale: bool,
+    pub failed_attempts: Vec<DownloadAttempt>,
+    pub next_run_at: i64, // 下次更新时间（Unix 时间戳，0 表示不再自动更新）
+    pub diff: Option<SubscriptionDiff>, // 与上次内容相比的变化
+}
+
+// 持久化的计划状态
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+struct PersistedSchedule {
+    next_run_at: i64,
+    last_run_at: i64, // 0 表示从未自动更新
+    header_interval_minutes: Option<u64>,
+    #[serde(default)]
+    consecutive_failures: u32,
+}
+
+struct ScheduleEntry {
+    subscription: ScheduledSubscription,
+    state: PersistedSchedule,
+    is_running: bool,
+}
+
+impl ScheduleEntry {
+    // 生效的更新间隔（秒），用户设置优先
+    fn interval_secs(&self) -> Option<i64> {
+        let minutes = match self.subscription.interval_minutes {
+            0 => self.state.header_interval_minutes?,
+            minutes => minutes,
+        };
+        Some((minutes as i64).saturating_mul(60))
+    }
+
+    // 记录一次运行结果并计算下次运行时间
+    fn finish_run(&mut self, now: i64, is_successful: bool, header_interval_minutes: Option<u64>) {
+        self.is_running = false;
+        self.state.last_run_at = now;
+        if header_interval_minutes.is_some() {
+            self.state.header_interval_minutes = header_interval_minutes;
+        }
+
+        if is_successful {
+            self.state.consecutive_failures = 0;
+            self.state.next_run_at = self
+                .interval_secs()
+                .map(|interval| now + interval + jitter_secs(interval))
+                .unwrap_or(0);
+            return;
+        }
+
+        // 失败后提前重试，等待时间随连续失败次数翻倍，不超过更新间隔与上限
+        self.state.consecutive_failures = self.state.consecutive_failures.saturating_add(1);
+        let exponent = (self.state.consecutive_failures - 1).min(16);
+        let backoff = FAILURE_RETRY_BASE_SECS
+            .saturating_mul(1 << exponent)
+            .min(MAX_FAILURE_RETRY_SECS);
+        let backoff = self
+            .interval_secs()
+            .map_or(backoff, |interval| backoff.min(interval));
+        self.state.next_run_at = now + backoff;
+    }
+}
+
+struct SchedulerState {
+    entries: HashMap<String, ScheduleEntry>,
+    semaphore: Arc<Semaphore>,
+    max_concurrency: usize,
+}
+
+impl SchedulerState {
+    // 调整并发上限：始终复用同一信号量，运行中任务持有的许可仍计入新上限
+    fn set_max_concurrency(&mut self, max_concurrency: usize) {
+        if max_concurrency > self.max_concurrency {
+            self.semaphore
+                .add_permits(max_concurrency - self.max_concurrency);
+        } else if max_concurrency < self.max_concurrency {
+            let excess = self.max_concurrency - max_concurrency;
+            let forgotten = self.semaphore.forget_permits(excess);
+            // 空闲许可不足时，等待运行中的任务释放后再回收剩余许可
+            if forgotten < excess {
+                let semaphore = self.semaphore.clone();
+                spawn(async move {
+                    if let Ok(permit) = semaphore
+                        .acquire_many_owned((excess - forgotten) as u32)
+                        .await
+                    {
+                        permit.forget();
+                    }
+                });
+            }
+        }
+        self.max_concurrency = max_concurrency;
+    }
+}
+
+static SCHEDULER: Lazy<Mutex<SchedulerState>> = Lazy::new(|| {
+    Mutex::new(SchedulerState {
+        entries: HashMap::new(),
+        semaphore: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENCY as usize)),
+        max_concurrency: DEFAULT_MAX_CONCURRENCY as usize,
+    })
+});
+
+// 计划变更时唤醒调度循环
+static SCHEDULER_NOTIFY: Lazy<Notify> = Lazy::new(Notify::new);
+
+impl ScheduleSubscriptionsRequest {
+    pub fn handle(self) {
+        log::info!("收到订阅自动更新计划：{} 个订阅", self.subscriptions.len());
+
+        let persisted = load_persisted();
+        let now = chrono::Utc::now().timestamp();
+        let max_concurrency = match self.max_concurrency {
+            0 => DEFAULT_MAX_CONCURRENCY,
+            value => value,
+        };
+
+        {
+            let mut scheduler = lock_scheduler();
+            let mut previous = std::mem::take(&mut scheduler.entries);
+
+            for subscription in self.subscriptions {
+                let id = subscription.subscription_id.clone();
+                let (state, is_running) = match previous.remove(&id) {
+                    Some(entry) => (entry.state, entry.is_running),
+                    None => (persisted.get(&id).copied().unwrap_or_default(), false),
+                };
+                let mut entry = ScheduleEntry {
+                    subscription,
+                    state,
+                    is_running,
+                };
+                entry.state.next_run_at = initial_next_run(now, &entry);
+                scheduler.entries.insert(id, entry);
+            }
+
+            scheduler.set_max_concurrency(max_concurrency as usize);
+            save_persisted(&scheduler.entries);
+        }
+
+        SCHEDULER_NOTIFY.notify_one();
+    }
+}
+
+// 计算计划加入时的下次运行时间：
+// 保留未过期的持久化时间；已错过或从未运行的订阅在抖动后尽快运行
+fn initial_next_run(now: i64, entry: &ScheduleEntry) -> i64 {
+    let interval = entry.interval_secs();
+    let persisted = entry.state.next_run_at;
+
+    match interval {
+        Some(interval) if persisted > now => persisted.min(now + interval + jitter_secs(interval)),
+        Some(interval) => now + jitter_secs(interval),
+        // 间隔未知（依赖响应头但尚未下载过）时先运行一次以获取间隔
+        None if entry.state.last_run_at == 0 => now + rand::random_range(0..=MAX_START_JITTER_SECS),
+        None => 0,
+    }
+}
+
+// 随机抖动：间隔的 10%，不超过上限
+fn jitter_secs(interval_secs: i64) -> i64 {
+    let max = (interval_secs / 10).clamp(0, MAX_START_JITTER_SECS);
+    if max == 0 {
+        return 0;
+    }
+    rand::random_range(0..=max)
+}
+
+fn lock_scheduler() -> MutexGuard<'static, SchedulerState> {
+    match SCHEDULER.lock() {
+        Ok(guard) => guard,
+        Err(e) => {
+            log::error!("订阅调度器状态锁已中毒，继续使用恢复后的状态");
+            e.into_inner()
+        }
+    }
+}
+
+fn load_persisted() -> HashMap<String, PersistedSchedule> {
+    let path = path_service::subscription_schedule_file();
+    std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| {
+            serde_json::from_str(&content)
+                .inspect_err(|e| log::warn!("订阅自动更新计划文件损坏：{}", e))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+fn save_persisted(entries: &HashMap<String, ScheduleEntry>) {
+    let persisted: HashMap<&str, PersistedSchedule> = entries
+        .iter()
+        .map(|(id, entry)| (id.as_str(), entry.state))
+        .collect();
+
+    let path = path_service::subscription_schedule_file();
+    let result = serde_json::to_string_pretty(&persisted)
+        .map_err(|e| e.to_string())
+        .and_then(|content| std::fs::write(&path, content).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        log::warn!("保存订阅自动更新计划失败：{}", e);
+    }
+}
+
+// 取出到期的订阅并计算下次唤醒等待时间
+fn take_due_subscriptions() -> (Vec<ScheduledSubscription>, Arc<Semaphore>, Duration) {
+    let now = chrono::Utc::now().timestamp();
+    let mut scheduler = lock_scheduler();
+    let (due, wait_secs) = take_due(&mut scheduler.entries, now);
+    (
+        due,
+        scheduler.semaphore.clone(),
+        Duration::from_secs(wait_secs as u64),
+    )
+}
+
+// 标记到期的订阅为运行中，返回到期订阅与距最近一次运行的秒数
+fn take_due(
+    entries: &mut HashMap<String, ScheduleEntry>,
+    now: i64,
+) -> (Vec<ScheduledSubscription>, i64) {
+    let mut due = Vec::new();
+    let mut wait_secs = MAX_IDLE_WAIT_SECS;
+
+    for entry in entries.values_mut() {
+        if entry.is_running || entry.state.next_run_at == 0 {
+            continue;
+        }
+        if entry.state.next_run_at <= now {
+            entry.is_running = true;
+            due.push(entry.subscription.clone());
+        } else {
+            wait_secs = wait_secs.min(entry.state.next_run_at - now);
+        }
+    }
+
+    (due, wait_secs.max(1))
+}
+
+async fn run_scheduler_loop() {
+    loop {
+        let (due, semaphore, wait) = take_due_subscriptions();
+        for subscription in due {
+            let semaphore = semaphore.clone();
+            spawn(async move {
+                run_scheduled_update(subscription, semaphore).await;
+            });
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = SCHEDULER_NOTIFY.notified() => {}
+        }
+    }
+}
+
+async fn run_scheduled_update(subscription: ScheduledSubscription, semaphore: Arc<Semaphore>) {
+    let Ok(_permit) = semaphore.acquire_owned().await else {
+        log::error!("订阅调度器信号量已关闭");
+        return;
+    };
+
+    log::info!("自动更新订阅：{}", subscription.subscription_id);
+    let retry_policy = subscription.retry_policy.clone().unwrap_or_default();
+    let request_options = subscription.request_options.clone().unwrap_or_default();
+    let result = download_subscription(&DownloadParams {
+        request_id: &subscription.subscription_id,
+        url: &subscription.url,
+        proxy_mode: subscription.proxy_mode,
+        retry_policy: &retry_policy,
+        user_agent: &subscription.user_agent,
+        timeout_seconds: subscription.timeout_seconds,
+        mixed_port: subscription.mixed_port,
+        age_secret_key: subscription.age_secret_key.as_deref(),
+        request_options: &request_options,
+        max_body_bytes: subscription
+            .max_body_bytes
+            .unwrap_or(DEFAULT_MAX_BODY_BYTES),
+    })
+    .await;
+
+    let header_interval_minutes = result
+        .as_ref()
+        .ok()
+        .and_then(|outcome| outcome.subscription_info.as_ref())
+        .and_then(|info| info.update_interval_hours)
+        .map(|hours| hours.saturating_mul(60));
+
+    // 更新计划（订阅可能已在下载期间被移出计划）
+    let next_run_at = {
+        let now = chrono::Utc::now().timestamp();
+        let mut scheduler = lock_scheduler();
+        let next_run_at = match scheduler.entries.get_mut(&subscription.subscription_id) {
+            Some(entry) => {
+                // 下载失败后回退到缓存内容同样视为失败，按退避间隔重试
+                let is_successful = result
+                    .as_ref()
+                    .is_ok_and(|outcome| outcome.stale_reason.is_none());
+                entry.finish_run(now, is_successful, header_interval_minutes);
+                entry.state.next_run_at
+            }
+            None => 0,
+        };
+        save_persisted(&scheduler.entries);
+        next_run_at
+    };
+    SCHEDULER_NOTIFY.notify_one();
+
+    let signal = match result {
+        Ok(outcome) => SubscriptionUpdated {
+            subscription_id: subscription.subscription_id,
+            is_successful: true,
+            content: outcome.content,
+            subscription_info: outcome.subscription_info,
+            is_stale: outcome.stale_reason.is_some(),
+            error_message: outcome.stale_reason,
+            is_not_modified: outcome.is_not_modified,
+            failed_attempts: outcome.failed_attempts,
+            next_run_at,
+            diff: outcome.diff,
+        },
+        Err(e) => {
+            log::error!("订阅自动更新失败 [{}]：{}", subscription.subscription_id, e);
+            SubscriptionUpdated {
+                subscription_id: subscription.subscription_id,
+                is_successful: false,
+                content: String::new(),
+                subscription_info: None,
+                error_message: Some(e.message),
+                is_not_modified: false,
+                is_stale: false,
+                failed_attempts: e.failed_attempts,
+                next_run_at,
+                diff: None,
+            }
+        }
+    };
+    signal.send_signal_to_dart();
+}
+
+// 初始化 Dart 信号监听器与调度循环
+pub fn init() {
+    spawn(async {
+        let receiver = ScheduleSubscriptionsRequest::get_dart_signal_receiver();
+        while let Some(dart_signal) = receiver.recv().await {
+            dart_signal.message.handle();
+        }
+    });
+
+    spawn(run_scheduler_loop());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PersistedSchedule, ScheduleEntry, ScheduledSubscription, SchedulerState, initial_next_run,
+        take_due,
+    };
+    use crate::molecules::ProxyMode;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::Semaphore;
+
+    fn entry(interval_minutes: u64, state: PersistedSchedule) -> ScheduleEntry {
+        named_entry("sub", interval_minutes, state)
+    }
+
+    fn named_entry(id: &str, interval_minutes: u64, state: PersistedSchedule) -> ScheduleEntry {
+        ScheduleEntry {
+            subscription: ScheduledSubscription {
+                subscription_id: id.to_string(),
+                url: "https://example.com/sub".to_string(),
+                proxy_mode: ProxyMode::Direct,
+                user_agent: String::new(),
+                timeout_seconds: 30,
+                mixed_port: 7890,
+                age_secret_key: None,
+                retry_policy: None,
+                request_options: None,
+                max_body_bytes: None,
+                interval_minutes,
+            },
+            state,
+            is_running: false,
+        }
+    }
+
+    #[test]
+    fn compute_initial_next_run_from_persisted_state() {
+        let now = 1_000_000;
+
+        // 持久化时间未过期时保留
+        let persisted = PersistedSchedule {
+            next_run_at: now + 600,
+            ..Default::default()
+        };
+        assert_eq!(initial_next_run(now, &entry(60, persisted)), now + 600);
+
+        // 已错过的运行在抖动范围内尽快执行
+        let missed = PersistedSchedule {
+            next_run_at: now - 600,
+            ..Default::default()
+        };
+        let next = initial_next_run(now, &entry(60, missed));
+        assert!((now..=now + 300).contains(&next));
+
+        // 使用响应头间隔
+        let header = PersistedSchedule {
+            next_run_at: now + 7200,
+            last_run_at: now - 3600,
+            header_interval_minutes: Some(24 * 60),
+            ..Default::default()
+        };
+        assert_eq!(initial_next_run(now, &entry(0, header)), now + 7200);
+
+        // 从未运行且间隔未知时先运行一次以获取响应头间隔
+        let first = initial_next_run(now, &entry(0, PersistedSchedule::default()));
+        assert!((now..=now + 300).contains(&first));
+
+        // 已运行过但没有响应头间隔时不自动更新
+        let disabled = PersistedSchedule {
+            last_run_at: now - 100,
+            ..Default::default()
+        };
+        assert_eq!(initial_next_run(now, &entry(0, disabled)), 0);
+    }
+
+    #[test]
+    fn run_due_entries_and_reschedule() {
+        let now = 1_000_000;
+        let at = |next_run_at| PersistedSchedule {
+            next_run_at,
+            ..Default::default()
+        };
+
+        // 仅取出到期的订阅，等待时间取最近一次运行
+        let mut entries = HashMap::new();
+        for (id, next_run_at) in [("due", now - 1), ("later", now + 30), ("off", 0)] {
+            entries.insert(id.to_string(), named_entry(id, 60, at(next_run_at)));
+        }
+        let (due, wait_secs) = take_due(&mut entries, now);
+        let due_ids: Vec<_> = due.iter().map(|s| s.subscription_id.as_str()).collect();
+        assert_eq!(due_ids, vec!["due"]);
+        assert_eq!(wait_secs, 30);
+        // 运行中的订阅不会被重复取出
+        assert!(take_due(&mut entries, now).0.is_empty());
+
+        // 成功后按响应头间隔重新计划
+        let mut header = entry(0, at(now));
+        header.is_running = true;
+        header.finish_run(now, true, Some(120));
+        assert!(!header.is_running);
+        assert_eq!(header.state.header_interval_minutes, Some(120));
+        assert!((now + 7200..=now + 7200 + 300).contains(&header.state.next_run_at));
+
+        // 失败后指数退避，不超过更新间隔，成功后恢复正常间隔
+        let mut failing = entry(0, at(now));
+        failing.finish_run(now, false, None);
+        assert_eq!(failing.state.next_run_at, now + 300);
+        failing.finish_run(now, false, None);
+        assert_eq!(failing.state.next_run_at, now + 600);
+        let mut capped = entry(8, at(now));
+        for _ in 0..3 {
+            capped.finish_run(now, false, None);
+        }
+        assert_eq!(capped.state.next_run_at, now + 480);
+        capped.finish_run(now, true, None);
+        assert_eq!(capped.state.consecutive_failures, 0);
+        assert!((now + 480..=now + 480 + 48).contains(&capped.state.next_run_at));
+    }
+
+    // 调整并发上限时复用同一信号量，缩小上限需等待运行中的任务释放许可
+    #[tokio::test]
+    async fn resize_semaphore_in_place() -> Result<(), tokio::sync::AcquireError> {
+        let semaphore = Arc::new(Semaphore::new(3));
+        let mut scheduler = SchedulerState {
+            entries: HashMap::new(),
+            semaphore: semaphore.clone(),
+            max_concurrency: 3,
+        };
+
+        scheduler.set_max_concurrency(5);
+        assert_eq!(semaphore.available_permits(), 5);
+
+        let running = semaphore.clone().acquire_many_owned(4).await?;
+        scheduler.set_max_concurrency(2);
+        assert_eq!(semaphore.available_permits(), 0);
+
+        drop(running);
+        tokio::task::yield_now().await;
+        assert_eq!(semaphore.available_permits(), 2);
+        assert!(Arc::ptr_eq(&scheduler.semaphore, &semaphore));
+        Ok(())
+    }
+}
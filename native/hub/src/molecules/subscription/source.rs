@@ -0,0 +1,259 @@
+// 订阅来源
+// 除 HTTP(S) 外支持 file:// 本地文件与 data: URI，
+// 并识别未声明 Content-Encoding 的 gzip/zstd/brotli 压缩内容、限制内容大小、嗅探明显不是订阅的内容。
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use std::io::Read;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const AGE_BINARY_MAGIC: &[u8] = b"age-encryption.org/";
+
+// 默认内容大小上限（下载与解压后均适用）
+pub const DEFAULT_MAX_BODY_BYTES: u64 = 32 * 1024 * 1024;
+
+// 嗅探格式所需的前导字节数
+pub const SNIFF_LENGTH: usize = 512;
+
+// 常见二进制文件魔数
+const BINARY_SIGNATURES: &[(&[u8], &str)] = &[
+    (b"PK\x03\x04", "ZIP"),
+    (b"%PDF", "PDF"),
+    (b"\x89PNG", "PNG"),
+    (b"\xff\xd8\xff", "JPEG"),
+    (b"GIF8", "GIF"),
+    (b"\x7fELF", "ELF"),
+];
+
+// 读取本地来源；HTTP(S) 链接返回 None，由下载器处理
+pub fn read_local(url: &str, max_bytes: u64) -> Option<Result<Vec<u8>, String>> {
+    let scheme = url.split_once(':')?.0.to_ascii_lowercase();
+    let content = match scheme.as_str() {
+        "file" => read_file(url, max_bytes),
+        "data" => decode_data_uri(url),
+        _ => return None,
+    };
+    Some(content.and_then(|content| {
+        check_size(content.len() as u64, max_bytes)?;
+        sniff_format(None, &content)?;
+        Ok(content)
+    }))
+}
+
+fn read_file(url: &str, max_bytes: u64) -> Result<Vec<u8>, String> {
+    let path = url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.to_file_path().ok())
+        .ok_or_else(|| format!("无效的本地文件链接：{}", url))?;
+    // 读取前先检查大小，避免载入超大文件
+    if let Ok(metadata) = std::fs::metadata(&path) {
+        check_size(metadata.len(), max_bytes)?;
+    }
+    let content = std::fs::read(&path)
+        .map_err(|e| format!("读取本地订阅文件失败 {}：{}", path.display(), e))?;
+    log::info!("已读取本地订阅文件：{}", path.display());
+    Ok(content)
+}
+
+// 格式：data:[<mediatype>][;base64],<data>
+fn decode_data_uri(url: &str) -> Result<Vec<u8>, String> {
+    let (header, data) = url
+        .get("data:".len()..)
+        .and_then(|rest| rest.split_once(','))
+        .ok_or("无效的 data: URI，缺少逗号分隔的内容")?;
+
+    let is_base64 = header
+        .split(';')
+        .any(|param| param.trim().eq_ignore_ascii_case("base64"));
+    if !is_base64 {
+        return Ok(urlencoding::decode_binary(data.as_bytes()).into_owned());
+    }
+
+    // base64 内容可能被百分号编码或包含换行
+    let data = urlencoding::decode(data).map_err(|e| format!("data: URI 编码无效：{}", e))?;
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    STANDARD
+        .decode(&data)
+        .or_else(|_| URL_SAFE_NO_PAD.decode(data.trim_end_matches('=')))
+        .map_err(|e| format!("data: URI 的 base64 内容无效：{}", e))
+}
+
+// 按魔数识别并解压 gzip/zstd 内容；brotli 没有魔数，仅在内容不是文本时尝试解码。
+// 其他内容原样返回，解压结果同样受大小上限约束
+pub fn decompress_if_needed(content: Vec<u8>, max_bytes: u64) -> Result<Vec<u8>, String> {
+    let mut decompressed = Vec::new();
+    // 多读一个字节用于判断是否超限
+    let limit = max_bytes.saturating_add(1);
+    if content.starts_with(GZIP_MAGIC) {
+        flate2::read::MultiGzDecoder::new(content.as_slice())
+            .take(limit)
+            .read_to_end(&mut decompressed)
+            .map_err(|e| format!("gzip 解压订阅内容失败：{}", e))?;
+        check_size(decompressed.len() as u64, max_bytes)?;
+        log::info!("订阅内容为 gzip 压缩，已解压");
+    } else if content.starts_with(ZSTD_MAGIC) {
+        zstd::stream::read::Decoder::new(content.as_slice())
+            .and_then(|decoder| decoder.take(limit).read_to_end(&mut decompressed))
+            .map_err(|e| format!("zstd 解压订阅内容失败：{}", e))?;
+        check_size(decompressed.len() as u64, max_bytes)?;
+        log::info!("订阅内容为 zstd 压缩，已解压");
+    } else if std::str::from_utf8(&content).is_err() && !content.starts_with(AGE_BINARY_MAGIC) {
+        // 解码失败说明不是 brotli，原样交由后续流程报告内容错误
+        match read_brotli(&content, limit) {
+            Ok(decoded) if !decoded.is_empty() => {
+                check_size(decoded.len() as u64, max_bytes)?;
+                log::info!("订阅内容为 brotli 压缩，已解压");
+                return Ok(decoded);
+            }
+            _ => return Ok(content),
+        }
+    } else {
+        return Ok(content);
+    }
+    Ok(decompressed)
+}
+
+// 解码 brotli 内容（Content-Encoding: br），结果受大小上限约束
+pub fn decode_brotli(content: &[u8], max_bytes: u64) -> Result<Vec<u8>, String> {
+    let decompressed = read_brotli(content, max_bytes.saturating_add(1))
+        .map_err(|e| format!("brotli 解压订阅内容失败：{}", e))?;
+    check_size(decompressed.len() as u64, max_bytes)?;
+    Ok(decompressed)
+}
+
+fn read_brotli(content: &[u8], limit: u64) -> std::io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    brotli::Decompressor::new(content, 4096)
+        .take(limit)
+        .read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+pub fn check_size(size: u64, max_bytes: u64) -> Result<(), String> {
+    if size > max_bytes {
+        return Err(format!(
+            "订阅内容过大：{} 超过上限 {}",
+            format_size(size),
+            format_size(max_bytes)
+        ));
+    }
+    Ok(())
+}
+
+pub fn format_size(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+}
+
+// 根据 Content-Type 与前导字节排除网页、图片、压缩包等明显不是订阅的内容；
+// gzip/zstd、age 与文本内容交由后续流程处理
+pub fn sniff_format(content_type: Option<&str>, head: &[u8]) -> Result<(), String> {
+    let media_type = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    if ["image/", "audio/", "video/"]
+        .iter()
+        .any(|prefix| media_type.starts_with(prefix))
+    {
+        return Err(format!("返回内容类型为 {}，不是订阅内容", media_type));
+    }
+
+    if let Some((_, name)) = BINARY_SIGNATURES
+        .iter()
+        .find(|(signature, _)| head.starts_with(signature))
+    {
+        return Err(format!("返回内容为 {} 文件，不是订阅内容", name));
+    }
+
+    let head = &head[..head.len().min(SNIFF_LENGTH)];
+    let text = String::from_utf8_lossy(head);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let prefix: String = text
+        .chars()
+        .take(16)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let is_html = ["<!doctype html", "<html", "<head", "<body"]
+        .iter()
+        .any(|tag| prefix.starts_with(tag));
+    if is_html || (media_type == "text/html" && text.starts_with('<')) {
+        return Err("返回内容为网页（可能是登录页、错误页或被拦截），不是订阅内容".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DEFAULT_MAX_BODY_BYTES, decode_brotli, decompress_if_needed, read_local, sniff_format,
+    };
+    use std::io::Write;
+
+    #[test]
+    fn read_data_uri_and_decompress() -> Result<(), String> {
+        let content = read_local(
+            "data:text/plain;base64,cHJveGllczogW10=",
+            DEFAULT_MAX_BODY_BYTES,
+        )
+        .ok_or("未识别 data: URI")??;
+        assert_eq!(content, b"proxies: []");
+
+        let content = read_local(
+            "data:,trojan%3A%2F%2Fpw%40a.com%3A443%23HK",
+            DEFAULT_MAX_BODY_BYTES,
+        )
+        .ok_or("未识别 data: URI")??;
+        assert_eq!(content, b"trojan://pw@a.com:443#HK");
+        assert!(read_local("https://example.com/sub", DEFAULT_MAX_BODY_BYTES).is_none());
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(b"proxies: []")
+            .map_err(|e| e.to_string())?;
+        let gzipped = encoder.finish().map_err(|e| e.to_string())?;
+        assert_eq!(
+            decompress_if_needed(gzipped.clone(), DEFAULT_MAX_BODY_BYTES)?,
+            b"proxies: []"
+        );
+        // 解压后超过上限
+        assert!(decompress_if_needed(gzipped, 4).is_err());
+
+        let zstded = zstd::encode_all(&b"proxies: []"[..], 0).map_err(|e| e.to_string())?;
+        assert_eq!(
+            decompress_if_needed(zstded, DEFAULT_MAX_BODY_BYTES)?,
+            b"proxies: []"
+        );
+        assert_eq!(
+            decompress_if_needed(b"plain".to_vec(), DEFAULT_MAX_BODY_BYTES)?,
+            b"plain"
+        );
+
+        let mut brotlied = Vec::new();
+        brotli::CompressorWriter::new(&mut brotlied, 4096, 5, 22)
+            .write_all(b"proxies: []")
+            .map_err(|e| e.to_string())?;
+        assert_eq!(
+            decode_brotli(&brotlied, DEFAULT_MAX_BODY_BYTES)?,
+            b"proxies: []"
+        );
+        assert!(decode_brotli(&brotlied, 4).is_err());
+        // 未声明编码的 brotli 内容按非文本内容回退解码
+        assert_eq!(
+            decompress_if_needed(brotlied, DEFAULT_MAX_BODY_BYTES)?,
+            b"proxies: []"
+        );
+        // 无法解码的二进制内容原样返回
+        assert_eq!(
+            decompress_if_needed(vec![0xff, 0xfe, 0x00], DEFAULT_MAX_BODY_BYTES)?,
+            vec![0xff, 0xfe, 0x00]
+        );
+
+        assert!(sniff_format(None, b"\n<!DOCTYPE html><html>").is_err());
+        assert!(sniff_format(Some("image/png"), b"data").is_err());
+        assert!(sniff_format(None, b"PK\x03\x04rest").is_err());
+        assert!(sniff_format(Some("text/html; charset=utf-8"), b"proxies: []").is_ok());
+        assert!(sniff_format(None, b"dHJvamFuOi8v").is_ok());
+        Ok(())
+    }
+}